@@ -3,13 +3,15 @@ import fs from "fs";
 import { MetadataProgram } from "./types/metadata_program";
 import IDL from "./metadata_program.json";
 import * as anchor from "@coral-xyz/anchor";
-import { inflate, deflate } from "pako";
+import { gzip, ungzip } from "pako";
+import { createHash } from "crypto";
 import { bs58 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 import { decodeUpgradeableLoaderState } from "@coral-xyz/anchor/dist/cjs/utils/registry";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 
 const CHUNK_SIZE = 900;
 const MAX_RESIZE_STEP = 10240;
+const MAX_ACCOUNT_SIZE = 0xffffffff;
 const CONFIRMATION_COMMITMENT: anchor.web3.Commitment = "confirmed";
 
 const METADATA_PROGRAM_ID = new PublicKey(
@@ -67,6 +69,8 @@ function getConstant(name: string): string {
 export const SEED_IDL = getConstant("DATA_TYPE_IDL");
 export const SEED_METADATA = getConstant("DATA_TYPE_METADATA");
 export const METADATA_OFFSET = Number(getConstant("METADATA_ACCOUNT_SIZE"));
+export const BUFFER_OFFSET = Number(getConstant("METADATA_BUFFER_SIZE"));
+export const ARCHIVE_OFFSET = Number(getConstant("METADATA_ARCHIVE_SIZE"));
 export const DATA_TYPE_LENGTH = Number(getConstant("DATA_TYPE_LENGTH"));
 
 interface ConnectionConfig {
@@ -291,6 +295,7 @@ async function uploadGenericDataBySeed(
   // Create buffer and wait for confirmation
   const bufferAddress = await createBuffer(
     buffer,
+    programId,
     keypair,
     rpcUrl,
     priorityFeesPerCU,
@@ -311,7 +316,8 @@ async function uploadGenericDataBySeed(
     bufferAddress.publicKey,
     keypair,
     rpcUrl,
-    priorityFeesPerCU
+    priorityFeesPerCU,
+    toAnchorEnum("compression", compression)
   );
   console.log("Buffer written");
 
@@ -387,6 +393,7 @@ async function setAuthority(
 
 async function createBuffer(
   buffer: Buffer,
+  programId: PublicKey,
   keypair: Keypair,
   rpcUrl: string,
   priorityFeesPerCU: number,
@@ -405,8 +412,11 @@ async function createBuffer(
   anchor.setProvider(provider);
   const program = new anchor.Program(IDL as MetadataProgram, provider);
 
-  const idlBytes = deflate(new Uint8Array(buffer)); // Compress the data using gzip
-  const bufferSize = idlBytes.length + METADATA_OFFSET;
+  const idlBytes = compressData(new Uint8Array(buffer), compression);
+  const bufferSize = idlBytes.length + BUFFER_OFFSET;
+  // set_buffer checks the written bytes against the data hash, the content hash is for downloaders
+  const dataHash = createHash("sha256").update(idlBytes).digest();
+  const contentHash = createHash("sha256").update(buffer).digest();
   let bufferKeypair = new Keypair();
 
   let createAccountInstruction = anchor.web3.SystemProgram.createAccount({
@@ -418,9 +428,18 @@ async function createBuffer(
   });
 
   const createBufferInstruction = await program.methods
-    .createBuffer(seed, encoding, compression, format, dataSource)
+    .createBuffer(
+      seed,
+      encoding,
+      compression,
+      format,
+      dataSource,
+      Array.from(dataHash),
+      Array.from(contentHash)
+    )
     .accountsPartial({
       buffer: bufferKeypair.publicKey,
+      programId: programId,
     })
     .instruction();
 
@@ -456,10 +475,11 @@ async function writeBuffer(
   bufferAddress: PublicKey,
   keypair: Keypair,
   rpcUrl: string,
-  priorityFeesPerCU: number
+  priorityFeesPerCU: number,
+  compression: CompressionType
 ) {
   const { connection, provider, program } = setupConnection(rpcUrl, keypair);
-  const idlBytes = deflate(new Uint8Array(buffer));
+  const idlBytes = compressData(new Uint8Array(buffer), compression);
   let offset = 0;
   program.idl.types.find;
   while (offset < idlBytes.length) {
@@ -552,12 +572,16 @@ async function setBuffer(
 
   const tx = await createTransaction(connection, authority, priorityFeesPerCU);
 
-  // Get buffer size first
+  // The metadata account needs room for the written data behind its own header
   const bufferAccountInfo = await connection.getAccountInfo(bufferAddress);
   if (!bufferAccountInfo) {
     throw new IDLError("Buffer account not found");
   }
-  const bufferAccountSize = bufferAccountInfo.data.length;
+  const bufferAccount = program.coder.accounts.decode(
+    "metadataBuffer",
+    bufferAccountInfo.data
+  );
+  const targetAccountSize = METADATA_OFFSET + bufferAccount.dataLen;
 
   // Check if we need to initialize
   const initInstruction = await getInitializeInstruction(
@@ -576,62 +600,20 @@ async function setBuffer(
 
   if (initInstruction) {
     tx.add(initInstruction);
-
-    // Always resize after initialization
-    let leftOverToResize = bufferAccountSize;
-    console.log("leftOverToResize", leftOverToResize);
-    let metadataAccountSize = METADATA_OFFSET;
-    while (leftOverToResize > 0) {
-      const chunkSize = Math.min(MAX_RESIZE_STEP, leftOverToResize);
-      metadataAccountSize += chunkSize;
-      const resizeInstruction = await program.methods
-        .resize(metadataAccountSize)
-        .accountsPartial({
-          pda: metadataAccount,
-          programId: programId,
-        })
-        .instruction();
-
-      tx.add(resizeInstruction);
-      leftOverToResize -= chunkSize;
-      console.log("leftOverToResize", leftOverToResize);
-    }
-  } else if (metadataAccountInfo) {
-    // Handle resize for existing accounts
-    let metadataAccountSize = metadataAccountInfo.data.length;
-
-    if (bufferAccountSize < metadataAccountSize) {
-      const resizeInstruction = await program.methods
-        .resize(bufferAccountSize)
-        .accountsPartial({
-          pda: metadataAccount,
-          programId: programId,
-        })
-        .instruction();
-      tx.add(resizeInstruction);
-    } else if (bufferAccountSize > metadataAccountSize) {
-      let leftOverToResize = bufferAccountSize - metadataAccountSize;
-      console.log("leftOverToResize inner", leftOverToResize);
-
-      while (leftOverToResize > 0) {
-        const chunkSize = Math.min(MAX_RESIZE_STEP, leftOverToResize);
-        metadataAccountSize += chunkSize;
-        const resizeInstruction = await program.methods
-          .resize(metadataAccountSize)
-          .accountsPartial({
-            pda: metadataAccount,
-            programId: programId,
-          })
-          .instruction();
-
-        tx.add(resizeInstruction);
-        console.log(`Resize to ${chunkSize} left over ${leftOverToResize}`);
-        leftOverToResize -= chunkSize;
-        console.log("leftOverToResize inner", leftOverToResize);
-      }
-    }
   }
 
+  // A new metadata account starts with just its header
+  const resizeInstructions = await getResizeInstructions(
+    program,
+    metadataAccount,
+    programId,
+    authority,
+    initInstruction ? METADATA_OFFSET : metadataAccountInfo.data.length,
+    targetAccountSize,
+    addSignerSeed ? keypair.publicKey : null
+  );
+  tx.add(...resizeInstructions);
+
   const setBufferInstruction = await program.methods
     .setBuffer()
     .accountsPartial({
@@ -640,6 +622,11 @@ async function setBuffer(
       authority: authority,
       programId: programId,
       programData: programDataAddress,
+      archive: null,
+      payer: null,
+      systemProgram: null,
+      delegateRecord: null,
+      multisig: null,
     })
     .instruction();
 
@@ -737,7 +724,10 @@ async function fetchIDL(
     METADATA_OFFSET,
     METADATA_OFFSET + dataLength
   );
-  const decompressedData = inflate(compressedData);
+  const decompressedData = decompressData(
+    compressedData,
+    decoded.compression
+  );
   const content = new TextDecoder("utf-8").decode(decompressedData);
 
   console.log("Data type", dataType);
@@ -919,7 +909,10 @@ async function fetchProgramMetadata(
     METADATA_OFFSET,
     METADATA_OFFSET + dataLength
   );
-  const decompressedData = inflate(compressedData);
+  const decompressedData = decompressData(
+    compressedData,
+    decoded.compression
+  );
   const content = new TextDecoder("utf-8").decode(decompressedData);
 
   if ("url" in format) {
@@ -1259,6 +1252,59 @@ async function getInitializeInstruction(
         .instruction();
 }
 
+/**
+ * Gets the resize instructions that bring a metadata account to the size it needs for new data
+ * @param {anchor.Program<MetadataProgram>} program - Metadata program
+ * @param {PublicKey} metadataPdaAddress - Metadata account to resize
+ * @param {PublicKey} programId - Program ID the metadata belongs to
+ * @param {PublicKey} signer - Authority of the metadata account, pays for the rent
+ * @param {number} currentSize - Current size of the metadata account
+ * @param {number} targetSize - Metadata header plus the length of the new data
+ * @param {PublicKey | null} seedSigner - Signer the account was derived with when it is not the canonical one
+ * @returns {Promise<anchor.web3.TransactionInstruction[]>} The instructions in the order they have to run
+ */
+async function getResizeInstructions(
+  program: anchor.Program<MetadataProgram>,
+  metadataPdaAddress: PublicKey,
+  programId: PublicKey,
+  signer: PublicKey,
+  currentSize: number,
+  targetSize: number,
+  seedSigner: PublicKey | null
+): Promise<anchor.web3.TransactionInstruction[]> {
+  // resize takes the total size of the account as u32
+  if (targetSize > MAX_ACCOUNT_SIZE) {
+    throw new IDLError(
+      `Data too large, the account size is limited to ${MAX_ACCOUNT_SIZE} bytes`
+    );
+  }
+
+  const sizes: number[] = [];
+  if (targetSize < currentSize) {
+    sizes.push(targetSize);
+  }
+  // The runtime only lets an account grow by MAX_RESIZE_STEP bytes per instruction
+  for (let size = currentSize; size < targetSize; ) {
+    size = Math.min(size + MAX_RESIZE_STEP, targetSize);
+    sizes.push(size);
+  }
+
+  return Promise.all(
+    sizes.map((size) =>
+      program.methods
+        .resize(size, seedSigner)
+        .accountsPartial({
+          pda: metadataPdaAddress,
+          signer: signer,
+          programId: programId,
+          delegateRecord: null,
+          multisig: null,
+        })
+        .instruction()
+    )
+  );
+}
+
 async function getSetBufferTransaction(
   bufferAddress: PublicKey,
   programId: PublicKey,
@@ -1318,6 +1364,21 @@ async function getSetBufferTransaction(
   if (!bufferAccountInfo) {
     throw new Error("Buffer account not found");
   }
+  const bufferAccount = program.coder.accounts.decode(
+    "metadataBuffer",
+    bufferAccountInfo.data
+  );
+  const metadataAccountInfo = await connection.getAccountInfo(metadataAccount);
+  const resizeInstructions = await getResizeInstructions(
+    program,
+    metadataAccount,
+    programId,
+    authority,
+    metadataAccountInfo ? metadataAccountInfo.data.length : METADATA_OFFSET,
+    METADATA_OFFSET + bufferAccount.dataLen,
+    addSignerSeed ? authority : null
+  );
+  tx.add(...resizeInstructions);
 
   const setBufferInstruction = await program.methods
     .setBuffer()
@@ -1326,6 +1387,11 @@ async function getSetBufferTransaction(
       buffer: bufferAddress,
       authority: authority,
       programId: programId,
+      archive: null,
+      payer: null,
+      systemProgram: null,
+      delegateRecord: null,
+      multisig: null,
     })
     .instruction();
 
@@ -1503,6 +1569,47 @@ async function closeProgramMetadataByPdaAddress(
   });
 }
 
+/**
+ * Compresses data the way the compression in the account header says, so the Rust client and the
+ * program read it back the same way
+ * @param {Uint8Array} data - Data to compress
+ * @param {CompressionType} compression - Compression stored in the header
+ * @returns {Uint8Array} The compressed data
+ * @throws {IDLError} For zstd, which the JS SDK can not write
+ */
+function compressData(
+  data: Uint8Array,
+  compression: CompressionType
+): Uint8Array {
+  if ("gzip" in compression) {
+    return gzip(data);
+  }
+  if ("zstd" in compression) {
+    throw new IDLError("zstd compression is not supported by the JS SDK");
+  }
+  return data;
+}
+
+/**
+ * Reverses {@link compressData} using the compression in the account header
+ * @param {Uint8Array} data - Stored data
+ * @param {CompressionType} compression - Compression stored in the header
+ * @returns {Uint8Array} The decompressed data
+ * @throws {IDLError} For zstd, which the JS SDK can not read
+ */
+function decompressData(
+  data: Uint8Array,
+  compression: CompressionType
+): Uint8Array {
+  if ("gzip" in compression) {
+    return ungzip(data);
+  }
+  if ("zstd" in compression) {
+    throw new IDLError("zstd compression is not supported by the JS SDK");
+  }
+  return data;
+}
+
 function toAnchorEnum(enumType: string, value: number): any {
   switch (enumType) {
     case "encoding":
@@ -1523,6 +1630,8 @@ function toAnchorEnum(enumType: string, value: number): any {
 }
 
 export {
+  compressData,
+  decompressData,
   uploadIdlByJsonPath,
   uploadIdlUrl,
   uploadProgramMetadataByJsonPath,
@@ -13,6 +13,96 @@ export type MetadataProgram = {
     description: "A program to attach metadata to programs. Like for example the IDL or metadata.";
   };
   instructions: [
+    {
+      name: "acceptAuthority";
+      discriminator: [107, 86, 198, 91, 33, 12, 107, 160];
+      accounts: [
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "newAuthority";
+          signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "addDelegate";
+      discriminator: [3, 67, 128, 218, 69, 139, 53, 88];
+      accounts: [
+        {
+          name: "delegateRecord";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [100, 101, 108, 101, 103, 97, 116, 101];
+              },
+              {
+                kind: "account";
+                path: "pda";
+              },
+              {
+                kind: "arg";
+                path: "delegate";
+              }
+            ];
+          };
+        },
+        {
+          name: "pda";
+        },
+        {
+          name: "authority";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [
+        {
+          name: "delegate";
+          type: "pubkey";
+        },
+        {
+          name: "expirySlot";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "cancelAuthority";
+      discriminator: [9, 60, 251, 100, 254, 226, 62, 110];
+      accounts: [
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "authority";
+          signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
     {
       name: "closeBuffer";
       discriminator: [46, 114, 179, 58, 57, 45, 194, 172];
@@ -28,6 +118,54 @@ export type MetadataProgram = {
       ];
       args: [];
     },
+    {
+      name: "closeExecutableHash";
+      discriminator: [205, 99, 20, 65, 172, 139, 171, 128];
+      accounts: [
+        {
+          name: "hashState";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  101,
+                  120,
+                  101,
+                  99,
+                  117,
+                  116,
+                  97,
+                  98,
+                  108,
+                  101,
+                  95,
+                  104,
+                  97,
+                  115,
+                  104
+                ];
+              },
+              {
+                kind: "account";
+                path: "pda";
+              }
+            ];
+          };
+        },
+        {
+          name: "pda";
+        },
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+          relations: ["hashState"];
+        }
+      ];
+      args: [];
+    },
     {
       name: "closeMetadataAccount1";
       discriminator: [214, 59, 103, 132, 141, 70, 199, 195];
@@ -39,6 +177,10 @@ export type MetadataProgram = {
         {
           name: "authority";
           signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
         }
       ];
       args: [];
@@ -54,6 +196,10 @@ export type MetadataProgram = {
         {
           name: "authority";
           signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
         }
       ];
       args: [];
@@ -69,6 +215,10 @@ export type MetadataProgram = {
         {
           name: "authority";
           signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
         }
       ];
       args: [];
@@ -84,6 +234,9 @@ export type MetadataProgram = {
         {
           name: "authority";
           signer: true;
+        },
+        {
+          name: "programId";
         }
       ];
       args: [
@@ -122,6 +275,184 @@ export type MetadataProgram = {
               name: "dataSource";
             };
           };
+        },
+        {
+          name: "dataHash";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "contentHash";
+          type: {
+            array: ["u8", 32];
+          };
+        }
+      ];
+    },
+    {
+      name: "createMultisig";
+      discriminator: [148, 146, 240, 10, 226, 215, 167, 174];
+      accounts: [
+        {
+          name: "multisig";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "threshold";
+          type: "u8";
+        },
+        {
+          name: "signers";
+          type: {
+            vec: "pubkey";
+          };
+        }
+      ];
+    },
+    {
+      name: "finishExecutableHash";
+      discriminator: [71, 58, 89, 234, 94, 162, 38, 53];
+      accounts: [
+        {
+          name: "hashState";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  101,
+                  120,
+                  101,
+                  99,
+                  117,
+                  116,
+                  97,
+                  98,
+                  108,
+                  101,
+                  95,
+                  104,
+                  97,
+                  115,
+                  104
+                ];
+              },
+              {
+                kind: "account";
+                path: "pda";
+              }
+            ];
+          };
+        },
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "payer";
+          writable: true;
+          relations: ["hashState"];
+        },
+        {
+          name: "programId";
+        },
+        {
+          name: "programData";
+          docs: [
+            "The program data account with the executable. Only needed for programs owned by the upgradeable loader."
+          ];
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "freeze";
+      discriminator: [255, 91, 207, 84, 251, 194, 254, 63];
+      accounts: [
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "authority";
+          signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "hashExecutable";
+      discriminator: [92, 103, 157, 144, 13, 7, 0, 248];
+      accounts: [
+        {
+          name: "hashState";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  101,
+                  120,
+                  101,
+                  99,
+                  117,
+                  116,
+                  97,
+                  98,
+                  108,
+                  101,
+                  95,
+                  104,
+                  97,
+                  115,
+                  104
+                ];
+              },
+              {
+                kind: "account";
+                path: "pda";
+              }
+            ];
+          };
+        },
+        {
+          name: "pda";
+        },
+        {
+          name: "programId";
+        },
+        {
+          name: "programData";
+          docs: [
+            "The program data account with the executable. Only needed for programs owned by the upgradeable loader."
+          ];
+          optional: true;
+        }
+      ];
+      args: [
+        {
+          name: "chunks";
+          type: "u8";
         }
       ];
     },
@@ -156,9 +487,13 @@ export type MetadataProgram = {
         },
         {
           name: "programId";
+          docs: [
+            "It has to sign when the program has no authority. Checks are done in code."
+          ];
         },
         {
           name: "programData";
+          optional: true;
         }
       ];
       args: [
@@ -201,8 +536,8 @@ export type MetadataProgram = {
       ];
     },
     {
-      name: "initializeWithSignerSeed";
-      discriminator: [55, 45, 87, 223, 169, 179, 103, 71];
+      name: "initializeWithProgramSigner";
+      discriminator: [106, 194, 83, 35, 220, 43, 63, 47];
       accounts: [
         {
           name: "pda";
@@ -213,10 +548,6 @@ export type MetadataProgram = {
                 kind: "account";
                 path: "programId";
               },
-              {
-                kind: "account";
-                path: "signer";
-              },
               {
                 kind: "arg";
                 path: "seed";
@@ -225,7 +556,11 @@ export type MetadataProgram = {
           };
         },
         {
-          name: "signer";
+          name: "programSigner";
+          signer: true;
+        },
+        {
+          name: "payer";
           writable: true;
           signer: true;
         },
@@ -235,9 +570,6 @@ export type MetadataProgram = {
         },
         {
           name: "programId";
-        },
-        {
-          name: "programData";
         }
       ];
       args: [
@@ -280,12 +612,28 @@ export type MetadataProgram = {
       ];
     },
     {
-      name: "resize";
-      discriminator: [74, 27, 74, 155, 56, 134, 175, 125];
+      name: "initializeWithSignerSeed";
+      discriminator: [55, 45, 87, 223, 169, 179, 103, 71];
       accounts: [
         {
           name: "pda";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "programId";
+              },
+              {
+                kind: "account";
+                path: "signer";
+              },
+              {
+                kind: "arg";
+                path: "seed";
+              }
+            ];
+          };
         },
         {
           name: "signer";
@@ -298,19 +646,150 @@ export type MetadataProgram = {
         },
         {
           name: "programId";
+        },
+        {
+          name: "programData";
+          optional: true;
         }
       ];
       args: [
         {
-          name: "len";
-          type: "u16";
-        }
-      ];
-    },
-    {
-      name: "setAuthority";
-      discriminator: [133, 250, 37, 21, 110, 163, 26, 121];
-      accounts: [
+          name: "seed";
+          type: "string";
+        },
+        {
+          name: "encoding";
+          type: {
+            defined: {
+              name: "encoding";
+            };
+          };
+        },
+        {
+          name: "compression";
+          type: {
+            defined: {
+              name: "compression";
+            };
+          };
+        },
+        {
+          name: "format";
+          type: {
+            defined: {
+              name: "format";
+            };
+          };
+        },
+        {
+          name: "dataSource";
+          type: {
+            defined: {
+              name: "dataSource";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "migrate";
+      discriminator: [155, 234, 231, 146, 236, 158, 162, 30];
+      accounts: [
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "authority";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "programId";
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "contentHash";
+          type: {
+            array: ["u8", 32];
+          };
+        }
+      ];
+    },
+    {
+      name: "resize";
+      discriminator: [74, 27, 74, 155, 56, 134, 175, 125];
+      accounts: [
+        {
+          name: "pda";
+          writable: true;
+        },
+        {
+          name: "signer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+        {
+          name: "programId";
+        },
+        {
+          name: "delegateRecord";
+          optional: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [
+        {
+          name: "len";
+          type: "u32";
+        },
+        {
+          name: "seedSigner";
+          type: {
+            option: "pubkey";
+          };
+        }
+      ];
+    },
+    {
+      name: "revokeDelegate";
+      discriminator: [142, 66, 98, 126, 102, 60, 92, 163];
+      accounts: [
+        {
+          name: "delegateRecord";
+          writable: true;
+        },
+        {
+          name: "pda";
+        },
+        {
+          name: "authority";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "setAuthority";
+      discriminator: [133, 250, 37, 21, 110, 163, 26, 121];
+      accounts: [
         {
           name: "pda";
           writable: true;
@@ -318,7 +797,10 @@ export type MetadataProgram = {
         {
           name: "authority";
           signer: true;
-          relations: ["pda"];
+        },
+        {
+          name: "multisig";
+          optional: true;
         }
       ];
       args: [
@@ -349,7 +831,132 @@ export type MetadataProgram = {
         },
         {
           name: "programData";
-          docs: ["The program data account containing the upgrade authority"];
+          docs: [
+            "The program data account containing the upgrade authority. Only needed for programs owned by the upgradeable loader."
+          ];
+          optional: true;
+        },
+        {
+          name: "archive";
+          docs: [
+            "The address is checked in code and the account is created by the instruction."
+          ];
+          writable: true;
+          optional: true;
+        },
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+          optional: true;
+        },
+        {
+          name: "systemProgram";
+          optional: true;
+          address: "11111111111111111111111111111111";
+        },
+        {
+          name: "delegateRecord";
+          optional: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "startExecutableHash";
+      discriminator: [111, 114, 156, 7, 51, 23, 36, 209];
+      accounts: [
+        {
+          name: "hashState";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  101,
+                  120,
+                  101,
+                  99,
+                  117,
+                  116,
+                  97,
+                  98,
+                  108,
+                  101,
+                  95,
+                  104,
+                  97,
+                  115,
+                  104
+                ];
+              },
+              {
+                kind: "account";
+                path: "pda";
+              }
+            ];
+          };
+        },
+        {
+          name: "pda";
+        },
+        {
+          name: "signer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "programId";
+        },
+        {
+          name: "programData";
+          docs: [
+            "The program data account with the executable. Only needed for programs owned by the upgradeable loader."
+          ];
+          optional: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+        {
+          name: "delegateRecord";
+          optional: true;
+        },
+        {
+          name: "multisig";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "writeArchive";
+      discriminator: [3, 103, 162, 174, 187, 38, 50, 45];
+      accounts: [
+        {
+          name: "pda";
+        },
+        {
+          name: "archive";
+          docs: [
+            "The address is checked in code and the account is created by the first call."
+          ];
+          writable: true;
+        },
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
         }
       ];
       args: [];
@@ -378,6 +985,41 @@ export type MetadataProgram = {
           type: "bytes";
         }
       ];
+    },
+    {
+      name: "writeBufferAt";
+      discriminator: [149, 61, 145, 63, 145, 30, 238, 220];
+      accounts: [
+        {
+          name: "buffer";
+          writable: true;
+        },
+        {
+          name: "signer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "offset";
+          type: "u32";
+        },
+        {
+          name: "expectedDataLen";
+          type: {
+            option: "u32";
+          };
+        },
+        {
+          name: "data";
+          type: "bytes";
+        }
+      ];
     }
   ];
   accounts: [
@@ -396,6 +1038,60 @@ export type MetadataProgram = {
     {
       name: "metadataBuffer";
       discriminator: [123, 121, 190, 46, 245, 204, 102, 54];
+    },
+    {
+      name: "metadataDelegate";
+      discriminator: [170, 59, 245, 93, 95, 222, 167, 194];
+    },
+    {
+      name: "metadataExecutableHash";
+      discriminator: [218, 203, 244, 52, 131, 95, 121, 55];
+    },
+    {
+      name: "metadataMultisig";
+      discriminator: [131, 49, 236, 168, 181, 51, 93, 14];
+    }
+  ];
+  events: [
+    {
+      discriminator: [31, 19, 174, 152, 4, 82, 215, 226];
+      name: "authorityChanged";
+    },
+    {
+      discriminator: [96, 159, 58, 144, 26, 171, 141, 70];
+      name: "delegateAdded";
+    },
+    {
+      discriminator: [10, 200, 133, 29, 238, 207, 193, 124];
+      name: "delegateRevoked";
+    },
+    {
+      discriminator: [209, 68, 2, 0, 61, 66, 83, 205];
+      name: "executableHashed";
+    },
+    {
+      discriminator: [115, 86, 221, 209, 3, 52, 52, 149];
+      name: "metadataClosed";
+    },
+    {
+      discriminator: [136, 121, 122, 154, 113, 102, 118, 117];
+      name: "metadataFrozen";
+    },
+    {
+      discriminator: [94, 69, 122, 172, 146, 58, 183, 35];
+      name: "metadataInitialized";
+    },
+    {
+      discriminator: [161, 189, 41, 187, 107, 28, 58, 30];
+      name: "metadataMigrated";
+    },
+    {
+      discriminator: [133, 194, 34, 41, 35, 99, 155, 207];
+      name: "metadataResized";
+    },
+    {
+      discriminator: [190, 125, 71, 119, 14, 31, 26, 197];
+      name: "metadataSet";
     }
   ];
   errors: [
@@ -437,139 +1133,693 @@ export type MetadataProgram = {
     {
       code: 6007;
       name: "dataTypeMismatch";
-      msg: "Data type and seed must match";
-    }
-  ];
-  types: [
+      msg: "The data type of the buffer does not match the metadata account";
+    },
     {
-      name: "compression";
-      type: {
-        kind: "enum";
-        variants: [
+      code: 6008;
+      name: "dataLenMismatch";
+      msg: "The buffer data length does not match the expected data length";
+    },
+    {
+      code: 6009;
+      name: "writeOutOfBounds";
+      msg: "The write exceeds the size of the buffer account";
+    },
+    {
+      code: 6010;
+      name: "dataHashMismatch";
+      msg: "The hash of the buffer data does not match the declared data hash";
+    },
+    {
+      code: 6011;
+      name: "invalidArchiveAddress";
+      msg: "The archive account is not the archive PDA of the current version";
+    },
+    {
+      code: 6012;
+      name: "missingArchiveAccounts";
+      msg: "Archiving a version requires a payer and the system program";
+    },
+    {
+      code: 6013;
+      name: "archiveTooLarge";
+      msg: "The current version does not fit in one archive step, write the rest of the archive with write_archive first";
+    },
+    {
+      code: 6014;
+      name: "notALegacyAccount";
+      msg: "The account is not a legacy metadata account";
+    },
+    {
+      code: 6015;
+      name: "invalidMetadataAddress";
+      msg: "The metadata account address does not match the program id and data type";
+    },
+    {
+      code: 6016;
+      name: "accountFrozen";
+      msg: "The metadata account is frozen and can not be changed anymore";
+    },
+    {
+      code: 6017;
+      name: "noPendingAuthority";
+      msg: "There is no pending authority to accept or cancel";
+    },
+    {
+      code: 6018;
+      name: "invalidPendingAuthority";
+      msg: "The signer is not the pending authority";
+    },
+    {
+      code: 6019;
+      name: "missingProgramData";
+      msg: "Programs owned by the upgradeable loader need their program data account";
+    },
+    {
+      code: 6020;
+      name: "resizeTooLarge";
+      msg: "Accounts can grow by at most 10240 bytes per instruction, resize in multiple steps";
+    },
+    {
+      code: 6021;
+      name: "resizeTooSmall";
+      msg: "The new size is too small for the metadata header and the stored data";
+    },
+    {
+      code: 6022;
+      name: "invalidDelegate";
+      msg: "The delegate record does not belong to this metadata account, signer and authority";
+    },
+    {
+      code: 6023;
+      name: "delegateExpired";
+      msg: "The delegate record has expired";
+    },
+    {
+      code: 6024;
+      name: "invalidThreshold";
+      msg: "The threshold must be between 1 and the number of signers";
+    },
+    {
+      code: 6025;
+      name: "tooManySigners";
+      msg: "A multisig can have at most 10 signers";
+    },
+    {
+      code: 6026;
+      name: "duplicateSigner";
+      msg: "The signers of a multisig must be unique";
+    },
+    {
+      code: 6027;
+      name: "invalidProgramSigner";
+      msg: "The program signer is not the PDA of the program derived from the program signer seed";
+    },
+    {
+      code: 6028;
+      name: "dataCompressed";
+      msg: "The metadata is compressed and can not be read directly";
+    },
+    {
+      code: 6029;
+      name: "executableHashIncomplete";
+      msg: "The executable has not been hashed completely yet";
+    },
+    {
+      code: 6030;
+      name: "executableHashOutdated";
+      msg: "The program was upgraded or the metadata was set again while hashing the executable";
+    },
+    {
+      code: 6031;
+      name: "invalidAccountPointer";
+      msg: "Account data sources must store an uncompressed pubkey, optionally followed by an offset and length";
+    },
+    {
+      code: 6032;
+      name: "legacyDataOutOfBounds";
+      msg: "The data length of the legacy account points past the end of the account";
+    },
+    {
+      code: 6033;
+      name: "nothingToArchive";
+      msg: "Version 0 is the empty account right after initialize and is not archived";
+    },
+    {
+      code: 6034;
+      name: "delegateCannotShrink";
+      msg: "Only the authority can shrink the metadata account and receive the rent refund";
+    }
+  ];
+  types: [
+    {
+      name: "authorityChanged";
+      type: {
+        fields: [
+          {
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "pendingAuthority";
+            type: "pubkey";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "compression";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "none";
+          },
+          {
+            name: "gzip";
+          },
+          {
+            name: "zstd";
+          }
+        ];
+      };
+    },
+    {
+      name: "dataSource";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "url";
+          },
+          {
+            name: "account";
+          },
+          {
+            name: "direct";
+          }
+        ];
+      };
+    },
+    {
+      name: "delegateAdded";
+      type: {
+        fields: [
+          {
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "delegate";
+            type: "pubkey";
+          },
+          {
+            name: "expirySlot";
+            type: "u64";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "delegateRevoked";
+      type: {
+        fields: [
+          {
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "delegate";
+            type: "pubkey";
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "encoding";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "utf8";
+          },
+          {
+            name: "base58";
+          },
+          {
+            name: "base64";
+          }
+        ];
+      };
+    },
+    {
+      name: "executableHashed";
+      type: {
+        fields: [
+          {
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "version";
+            type: "u64";
+          },
+          {
+            name: "programDataSlot";
+            type: "u64";
+          },
+          {
+            name: "executableHash";
+            type: {
+              array: ["u8", 32];
+            };
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "format";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "text";
+          },
+          {
+            name: "json";
+          },
+          {
+            name: "yaml";
+          },
+          {
+            name: "toml";
+          },
+          {
+            name: "binary";
+          }
+        ];
+      };
+    },
+    {
+      name: "metadataAccount";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: {
+              array: ["u8", 16];
+            };
+          },
+          {
+            name: "dataLen";
+            type: "u32";
+          }
+        ];
+      };
+    },
+    {
+      name: "metadataAccount2";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: {
+              array: ["u8", 16];
+            };
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataLen";
+            type: "u32";
+          }
+        ];
+      };
+    },
+    {
+      name: "metadataAccount3";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: {
+              array: ["u8", 16];
+            };
+          },
+          {
+            name: "encoding";
+            type: {
+              defined: {
+                name: "encoding";
+              };
+            };
+          },
+          {
+            name: "compression";
+            type: {
+              defined: {
+                name: "compression";
+              };
+            };
+          },
+          {
+            name: "format";
+            type: {
+              defined: {
+                name: "format";
+              };
+            };
+          },
+          {
+            name: "dataSource";
+            type: {
+              defined: {
+                name: "dataSource";
+              };
+            };
+          },
+          {
+            name: "dataLen";
+            type: "u32";
+          },
+          {
+            name: "dataHash";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "contentHash";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "version";
+            type: "u64";
+          },
+          {
+            name: "frozen";
+            type: "bool";
+          },
+          {
+            name: "pendingAuthority";
+            type: "pubkey";
+          },
+          {
+            name: "programDataSlot";
+            type: "u64";
+          },
+          {
+            name: "executableHash";
+            type: {
+              array: ["u8", 32];
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "metadataBuffer";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: {
+              array: ["u8", 16];
+            };
+          },
           {
-            name: "none";
+            name: "programId";
+            type: "pubkey";
           },
           {
-            name: "gzip";
+            name: "encoding";
+            type: {
+              defined: {
+                name: "encoding";
+              };
+            };
           },
           {
-            name: "zstd";
+            name: "compression";
+            type: {
+              defined: {
+                name: "compression";
+              };
+            };
+          },
+          {
+            name: "format";
+            type: {
+              defined: {
+                name: "format";
+              };
+            };
+          },
+          {
+            name: "dataSource";
+            type: {
+              defined: {
+                name: "dataSource";
+              };
+            };
+          },
+          {
+            name: "dataLen";
+            type: "u32";
+          },
+          {
+            name: "dataHash";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "contentHash";
+            type: {
+              array: ["u8", 32];
+            };
           }
         ];
       };
     },
     {
-      name: "dataSource";
+      name: "metadataClosed";
       type: {
-        kind: "enum";
-        variants: [
+        fields: [
           {
-            name: "url";
+            name: "metadata";
+            type: "pubkey";
           },
           {
-            name: "account";
+            name: "programId";
+            type: "pubkey";
           },
           {
-            name: "direct";
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "authority";
+            type: "pubkey";
           }
         ];
+        kind: "struct";
       };
     },
     {
-      name: "encoding";
+      name: "metadataDelegate";
       type: {
-        kind: "enum";
-        variants: [
+        kind: "struct";
+        fields: [
           {
-            name: "utf8";
+            name: "metadata";
+            type: "pubkey";
           },
           {
-            name: "base58";
+            name: "delegate";
+            type: "pubkey";
           },
           {
-            name: "base64";
+            name: "authority";
+            type: "pubkey";
+          },
+          {
+            name: "expirySlot";
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "format";
+      name: "metadataExecutableHash";
       type: {
-        kind: "enum";
-        variants: [
+        kind: "struct";
+        fields: [
           {
-            name: "text";
+            name: "payer";
+            type: "pubkey";
           },
           {
-            name: "json";
+            name: "version";
+            type: "u64";
           },
           {
-            name: "yaml";
+            name: "programDataSlot";
+            type: "u64";
           },
           {
-            name: "toml";
+            name: "executableLen";
+            type: "u32";
           },
           {
-            name: "binary";
+            name: "offset";
+            type: "u32";
+          },
+          {
+            name: "digest";
+            type: {
+              array: ["u8", 32];
+            };
           }
         ];
       };
     },
     {
-      name: "metadataAccount";
+      name: "metadataFrozen";
       type: {
-        kind: "struct";
         fields: [
           {
-            name: "authority";
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
             type: "pubkey";
           },
           {
             name: "dataType";
-            type: {
-              array: ["u8", 16];
-            };
+            type: "string";
           },
           {
             name: "dataLen";
             type: "u32";
+          },
+          {
+            name: "version";
+            type: "u64";
+          },
+          {
+            name: "contentHash";
+            type: {
+              array: ["u8", 32];
+            };
           }
         ];
+        kind: "struct";
       };
     },
     {
-      name: "metadataAccount2";
+      name: "metadataInitialized";
       type: {
-        kind: "struct";
         fields: [
           {
-            name: "authority";
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
             type: "pubkey";
           },
           {
             name: "dataType";
-            type: {
-              array: ["u8", 16];
-            };
+            type: "string";
           },
           {
-            name: "programId";
+            name: "authority";
             type: "pubkey";
           },
           {
-            name: "dataLen";
-            type: "u32";
+            name: "signerSeeded";
+            type: "bool";
           }
         ];
+        kind: "struct";
       };
     },
     {
-      name: "metadataAccount3";
+      name: "metadataMigrated";
       type: {
-        kind: "struct";
         fields: [
           {
-            name: "authority";
+            name: "metadata";
             type: "pubkey";
           },
           {
@@ -578,109 +1828,139 @@ export type MetadataProgram = {
           },
           {
             name: "dataType";
-            type: {
-              array: ["u8", 16];
-            };
+            type: "string";
           },
           {
-            name: "encoding";
-            type: {
-              defined: {
-                name: "encoding";
-              };
-            };
+            name: "authority";
+            type: "pubkey";
           },
           {
-            name: "compression";
-            type: {
-              defined: {
-                name: "compression";
-              };
-            };
+            name: "dataLen";
+            type: "u32";
           },
           {
-            name: "format";
+            name: "version";
+            type: "u64";
+          },
+          {
+            name: "contentHash";
             type: {
-              defined: {
-                name: "format";
-              };
+              array: ["u8", 32];
             };
+          }
+        ];
+        kind: "struct";
+      };
+    },
+    {
+      name: "metadataMultisig";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "threshold";
+            type: "u8";
           },
           {
-            name: "dataSource";
+            name: "signers";
             type: {
-              defined: {
-                name: "dataSource";
-              };
+              vec: "pubkey";
             };
+          }
+        ];
+      };
+    },
+    {
+      name: "metadataResized";
+      type: {
+        fields: [
+          {
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
+            type: "pubkey";
+          },
+          {
+            name: "dataType";
+            type: "string";
+          },
+          {
+            name: "authority";
+            type: "pubkey";
           },
           {
             name: "dataLen";
             type: "u32";
+          },
+          {
+            name: "accountSize";
+            type: "u32";
           }
         ];
+        kind: "struct";
       };
     },
     {
-      name: "metadataBuffer";
+      name: "metadataSet";
       type: {
-        kind: "struct";
         fields: [
           {
-            name: "authority";
+            name: "metadata";
+            type: "pubkey";
+          },
+          {
+            name: "programId";
             type: "pubkey";
           },
           {
             name: "dataType";
-            type: {
-              array: ["u8", 16];
-            };
+            type: "string";
           },
           {
-            name: "programId";
+            name: "authority";
             type: "pubkey";
           },
           {
-            name: "encoding";
-            type: {
-              defined: {
-                name: "encoding";
-              };
-            };
+            name: "dataLen";
+            type: "u32";
           },
           {
-            name: "compression";
-            type: {
-              defined: {
-                name: "compression";
-              };
-            };
+            name: "version";
+            type: "u64";
           },
           {
-            name: "format";
+            name: "dataHash";
             type: {
-              defined: {
-                name: "format";
-              };
+              array: ["u8", 32];
             };
           },
           {
-            name: "dataSource";
+            name: "contentHash";
             type: {
-              defined: {
-                name: "dataSource";
-              };
+              array: ["u8", 32];
             };
           },
           {
-            name: "dataLen";
-            type: "u32";
+            name: "archived";
+            type: "bool";
+          },
+          {
+            name: "programDataSlot";
+            type: "u64";
           }
         ];
+        kind: "struct";
       };
     }
   ];
   constants: [
+    {
+      name: "archiveSeed";
+      type: "bytes";
+      value: "[97, 114, 99, 104, 105, 118, 101]";
+    },
     {
       name: "dataTypeIdl";
       type: "string";
@@ -696,10 +1976,55 @@ export type MetadataProgram = {
       type: "string";
       value: '"metadata"';
     },
+    {
+      name: "delegateSeed";
+      type: "bytes";
+      value: "[100, 101, 108, 101, 103, 97, 116, 101]";
+    },
+    {
+      name: "executableHashChunkSize";
+      type: "u32";
+      value: "65536";
+    },
+    {
+      name: "executableHashSeed";
+      type: "bytes";
+      value: "[101, 120, 101, 99, 117, 116, 97, 98, 108, 101, 95, 104, 97, 115, 104]";
+    },
     {
       name: "metadataAccountSize";
       type: "u64";
+      value: "241";
+    },
+    {
+      name: "metadataArchiveSize";
+      type: "u64";
+      value: "120";
+    },
+    {
+      name: "metadataBufferSize";
+      type: "u64";
+      value: "160";
+    },
+    {
+      name: "metadataDelegateSize";
+      type: "u64";
+      value: "112";
+    },
+    {
+      name: "metadataExecutableHashSize";
+      type: "u64";
       value: "96";
+    },
+    {
+      name: "metadataMultisigSize";
+      type: "u64";
+      value: "333";
+    },
+    {
+      name: "programSignerSeed";
+      type: "bytes";
+      value: "[109, 101, 116, 97, 100, 97, 116, 97, 95, 97, 117, 116, 104, 111, 114, 105, 116, 121]";
     }
   ];
 };
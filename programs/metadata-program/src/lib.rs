@@ -1,13 +1,23 @@
+// The cpi module generated for create_buffer takes the same arguments as the handler, see there
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+
 use anchor_lang::idl::ERASED_AUTHORITY;
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, loader_v4};
+use anchor_lang::solana_program::hash::{hash, hashv};
 
 declare_id!("pmetaypqG6SiB47xMigYVMAkuHDWeSDXcv3zzDrJJvA");
 
+#[cfg(feature = "cpi")]
+pub mod program_signer;
+#[cfg(feature = "no-entrypoint")]
+pub mod reader;
+
 #[program]
 pub mod metadata_program {
 
-    use anchor_lang::solana_program::{bpf_loader, bpf_loader_deprecated};
+    use anchor_lang::Discriminator;
 
     use super::*;
 
@@ -27,8 +37,62 @@ pub mod metadata_program {
         InvalidAuthority,
         #[msg("Wrong program ID")]
         WrongProgramId,
-        #[msg("Data type and seed must match")]
-        DataTypeMismatch
+        #[msg("The data type of the buffer does not match the metadata account")]
+        DataTypeMismatch,
+        #[msg("The buffer data length does not match the expected data length")]
+        DataLenMismatch,
+        #[msg("The write exceeds the size of the buffer account")]
+        WriteOutOfBounds,
+        #[msg("The hash of the buffer data does not match the declared data hash")]
+        DataHashMismatch,
+        #[msg("The archive account is not the archive PDA of the current version")]
+        InvalidArchiveAddress,
+        #[msg("Archiving a version requires a payer and the system program")]
+        MissingArchiveAccounts,
+        #[msg("The current version does not fit in one archive step, write the rest of the archive with write_archive first")]
+        ArchiveTooLarge,
+        #[msg("The account is not a legacy metadata account")]
+        NotALegacyAccount,
+        #[msg("The metadata account address does not match the program id and data type")]
+        InvalidMetadataAddress,
+        #[msg("The metadata account is frozen and can not be changed anymore")]
+        AccountFrozen,
+        #[msg("There is no pending authority to accept or cancel")]
+        NoPendingAuthority,
+        #[msg("The signer is not the pending authority")]
+        InvalidPendingAuthority,
+        #[msg("Programs owned by the upgradeable loader need their program data account")]
+        MissingProgramData,
+        #[msg("Accounts can grow by at most 10240 bytes per instruction, resize in multiple steps")]
+        ResizeTooLarge,
+        #[msg("The new size is too small for the metadata header and the stored data")]
+        ResizeTooSmall,
+        #[msg("The delegate record does not belong to this metadata account, signer and authority")]
+        InvalidDelegate,
+        #[msg("The delegate record has expired")]
+        DelegateExpired,
+        #[msg("The threshold must be between 1 and the number of signers")]
+        InvalidThreshold,
+        #[msg("A multisig can have at most 10 signers")]
+        TooManySigners,
+        #[msg("The signers of a multisig must be unique")]
+        DuplicateSigner,
+        #[msg("The program signer is not the PDA of the program derived from the program signer seed")]
+        InvalidProgramSigner,
+        #[msg("The metadata is compressed and can not be read directly")]
+        DataCompressed,
+        #[msg("The executable has not been hashed completely yet")]
+        ExecutableHashIncomplete,
+        #[msg("The program was upgraded or the metadata was set again while hashing the executable")]
+        ExecutableHashOutdated,
+        #[msg("Account data sources must store an uncompressed pubkey, optionally followed by an offset and length")]
+        InvalidAccountPointer,
+        #[msg("The data length of the legacy account points past the end of the account")]
+        LegacyDataOutOfBounds,
+        #[msg("Version 0 is the empty account right after initialize and is not archived")]
+        NothingToArchive,
+        #[msg("Only the authority can shrink the metadata account and receive the rent refund")]
+        DelegateCannotShrink
     }
 
     pub fn initialize(
@@ -40,47 +104,13 @@ pub mod metadata_program {
         data_source: DataSource,
     ) -> Result<()> {
         msg!("Signer {:?}!", ctx.accounts.signer.key);              
-        msg!("Authority {:?}!", ctx.accounts.program_data.upgrade_authority_address);              
 
-        if ctx.accounts.program_id.owner.key() != bpf_loader_upgradeable::ID 
-        && ctx.accounts.program_id.owner.key() != bpf_loader::ID 
-        && ctx.accounts.program_id.owner.key() != bpf_loader_deprecated::ID {
-            return Err(MyError::NotAProgram.into());
-        }
-        
-        if !ctx.accounts.program_id.executable {
-            return Err(MyError::NotExecutable.into());
-        }
-    
-        // Borrow the program's account data
-        let mut program_borrowed_data: &[u8] = &ctx.accounts.program_id.try_borrow_data()?;
-
-        // Deserialize the UpgradeableLoaderState from the program account data
-        let upgradable_loader_state =
-            UpgradeableLoaderState::try_deserialize_unchecked(&mut program_borrowed_data)?;
-
-        match upgradable_loader_state {
-            UpgradeableLoaderState::Uninitialized
-            | UpgradeableLoaderState::Buffer {
-                authority_address: _,
-            }
-            | UpgradeableLoaderState::ProgramData {
-                slot: _,
-                upgrade_authority_address: _,
-            } => {
-                return err!(MyError::ShouldBeProgramAccount);
-            }
-            UpgradeableLoaderState::Program {
-                programdata_address: program_data_address,
-            } => {
-                // Print out the program data address
-                msg!("Program Data Address: {:?}", program_data_address);
-
-                // Ensure the program data address matches the expected value
-                if program_data_address != ctx.accounts.program_data.key() {
-                    return err!(MyError::WrongAuthority);
-                }
-            }
+        // The canonical metadata account can only be created by the authority of the program
+        let program_data = ctx.accounts.program_data.as_ref();
+        let program_authority = get_program_authority(&ctx.accounts.program_id, program_data)?;
+        msg!("Authority {:?}!", program_authority);
+        if !is_program_authority(&ctx.accounts.program_id, program_authority, ctx.accounts.signer.key) {
+            return err!(MyError::WrongAuthority);
         }
 
         // When all is good create PDA and save authority for later upgrades.
@@ -93,6 +123,14 @@ pub mod metadata_program {
         metadata.format = format;
         metadata.data_source = data_source;
         metadata.data_len = 0;
+
+        emit!(MetadataInitialized {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            signer_seeded: false,
+        });
         
         Ok(())
     }
@@ -106,48 +144,12 @@ pub mod metadata_program {
         data_source: DataSource,
     ) -> Result<()> {
         msg!("Signer {:?}!", ctx.accounts.signer.key);              
-        msg!("Authority {:?}!", ctx.accounts.program_data.upgrade_authority_address);              
 
-        if ctx.accounts.program_id.owner.key() != bpf_loader_upgradeable::ID 
-        && ctx.accounts.program_id.owner.key() != bpf_loader::ID 
-        && ctx.accounts.program_id.owner.key() != bpf_loader_deprecated::ID {
-            return Err(MyError::NotAProgram.into());
-        }
-        
-        if !ctx.accounts.program_id.executable {
-            return Err(MyError::NotExecutable.into());
-        }
-    
-        // Borrow the program's account data
-        let mut program_borrowed_data: &[u8] = &ctx.accounts.program_id.try_borrow_data()?;
-
-        // Deserialize the UpgradeableLoaderState from the program account data
-        let upgradable_loader_state =
-            UpgradeableLoaderState::try_deserialize_unchecked(&mut program_borrowed_data)?;
-
-        match upgradable_loader_state {
-            UpgradeableLoaderState::Uninitialized
-            | UpgradeableLoaderState::Buffer {
-                authority_address: _,
-            }
-            | UpgradeableLoaderState::ProgramData {
-                slot: _,
-                upgrade_authority_address: _,
-            } => {
-                return err!(MyError::ShouldBeProgramAccount);
-            }
-            UpgradeableLoaderState::Program {
-                programdata_address: program_data_address,
-            } => {
-                // Print out the program data address
-                msg!("Program Data Address: {:?}", program_data_address);
-
-                // Ensure the program data address matches the expected value
-                if program_data_address != ctx.accounts.program_data.key() {
-                    return err!(MyError::WrongAuthority);
-                }
-            }
-        }
+        // When we add signer seed we do NOT check if the signer is the program authority,
+        // only that the program exists.
+        let program_data = ctx.accounts.program_data.as_ref();
+        let program_authority = get_program_authority(&ctx.accounts.program_id, program_data)?;
+        msg!("Authority {:?}!", program_authority);
 
         // When all is good create PDA and save authority for later upgrades.
         let metadata = &mut ctx.accounts.pda;
@@ -159,10 +161,54 @@ pub mod metadata_program {
         metadata.format = format;
         metadata.data_source = data_source;
         metadata.data_len = 0;
+
+        emit!(MetadataInitialized {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            signer_seeded: true,
+        });
         
         Ok(())
     }
 
+    // Called by a program through CPI to publish its own metadata. The program signs with its
+    // PDA [PROGRAM_SIGNER_SEED], which becomes the authority of the canonical metadata account.
+    // Only the program itself can sign for that PDA, so no upgrade authority is needed.
+    // The trade-off: the upgrade authority can not set_authority, freeze or close this account,
+    // it can only take it back with set_buffer, which makes it the authority again. Once the
+    // program is immutable, only code the program ships can change or close the account.
+    pub fn initialize_with_program_signer(
+        ctx: Context<InitializeWithProgramSigner>,
+        seed: String,
+        encoding: Encoding,
+        compression: Compression,
+        format: Format,
+        data_source: DataSource,
+    ) -> Result<()> {
+        msg!("Program signer {:?}!", ctx.accounts.program_signer.key);
+
+        let metadata = &mut ctx.accounts.pda;
+        metadata.authority = ctx.accounts.program_signer.key();
+        metadata.program_id = ctx.accounts.program_id.key();
+        metadata.set_data_type(&seed)?;
+        metadata.encoding = encoding;
+        metadata.compression = compression;
+        metadata.format = format;
+        metadata.data_source = data_source;
+        metadata.data_len = 0;
+
+        emit!(MetadataInitialized {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            signer_seeded: false,
+        });
+        Ok(())
+    }
+
     pub fn write_buffer(ctx: Context<WriteBuffer>, data: Vec<u8>) -> Result<()> {
         let prev_len: usize =
             ::std::convert::TryInto::<usize>::try_into(ctx.accounts.buffer.data_len).unwrap();
@@ -183,6 +229,42 @@ pub mod metadata_program {
         Ok(())
     }
 
+    // Writes `data` at an explicit offset instead of appending it. Sending the same chunk twice
+    // or sending chunks out of order always results in the same buffer content.
+    // If `expected_data_len` is set the write only succeeds when the buffer currently has that length.
+    pub fn write_buffer_at(
+        ctx: Context<WriteBuffer>,
+        offset: u32,
+        expected_data_len: Option<u32>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        if let Some(expected_data_len) = expected_data_len {
+            require_eq!(ctx.accounts.buffer.data_len, expected_data_len, MyError::DataLenMismatch);
+        }
+
+        let start: usize = ::std::convert::TryInto::<usize>::try_into(offset).unwrap();
+        let end: usize = start
+            .checked_add(data.len())
+            .ok_or(MyError::WriteOutOfBounds)?;
+
+        {
+            let mut trailing_bytes = ctx.accounts.buffer.trailing_data_mut();
+            require_gte!(trailing_bytes.len(), end, MyError::WriteOutOfBounds);
+            trailing_bytes[start..end].copy_from_slice(&data[..]);
+        }
+
+        // The data length only ever grows so a late retry of an earlier chunk can not truncate the buffer.
+        let end = ::std::convert::TryInto::<u32>::try_into(end)
+            .map_err(|_| MyError::WriteOutOfBounds)?;
+        if end > ctx.accounts.buffer.data_len {
+            ctx.accounts.buffer.data_len = end;
+        }
+        Ok(())
+    }
+
+    // The arguments are the instruction data every client encodes. Grouping them in a struct would
+    // change the IDL, so the lint is allowed here and for the generated cpi function.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_buffer(
         ctx: Context<CreateBuffer>, 
         _seed: String,
@@ -190,27 +272,183 @@ pub mod metadata_program {
         compression: Compression,
         format: Format,
         data_source: DataSource,
+        data_hash: [u8; 32],
+        content_hash: [u8; 32],
     ) -> Result<()> {
         let buffer = &mut ctx.accounts.buffer;
         buffer.authority = *ctx.accounts.authority.key;
         buffer.set_data_type(&_seed)?;
+        // Binds the buffer to the metadata account it is meant for, checked again by set_buffer
+        buffer.program_id = ctx.accounts.program_id.key();
         buffer.encoding = encoding;
         buffer.compression = compression;
         buffer.format = format;
         buffer.data_source = data_source;
         buffer.data_len = 0;
+        buffer.data_hash = data_hash;
+        buffer.content_hash = content_hash;
+        Ok(())
+    }
+
+    pub fn close_metadata_account_3(ctx: Context<CloseMetadataAccount3>) -> Result<()> {
+        let metadata = &ctx.accounts.metadata_account;
+        if !is_authority(&metadata.authority, ctx.accounts.authority.key, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+        emit!(MetadataClosed {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+        });
         Ok(())
     }
 
-    pub fn close_metadata_account_3(_ctx: Context<CloseMetadataAccount3>) -> Result<()> {
+    pub fn close_metadata_account_2(ctx: Context<CloseMetadataAccount2>) -> Result<()> {
+        let metadata = &ctx.accounts.metadata_account;
+        if !is_authority(&metadata.authority, ctx.accounts.authority.key, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+        emit!(MetadataClosed {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: data_type_to_string(&metadata.data_type),
+            authority: metadata.authority,
+        });
         Ok(())
     }
 
-    pub fn close_metadata_account_2(_ctx: Context<CloseMetadataAccount2>) -> Result<()> {
+    pub fn close_metadata_account_1(ctx: Context<CloseMetadataAccount1>) -> Result<()> {
+        // MetadataAccount does not store the program id
+        let metadata = &ctx.accounts.metadata_account;
+        if !is_authority(&metadata.authority, ctx.accounts.authority.key, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+        emit!(MetadataClosed {
+            metadata: metadata.key(),
+            program_id: Pubkey::default(),
+            data_type: data_type_to_string(&metadata.data_type),
+            authority: metadata.authority,
+        });
         Ok(())
     }
 
-    pub fn close_metadata_account_1(_ctx: Context<CloseMetadataAccount1>) -> Result<()> {
+    // Converts a MetadataAccount, MetadataAccount2 or a MetadataAccount3 with the old 96 byte header in place
+    // into a current MetadataAccount3. The account keeps its address and trailing data. MetadataAccount and
+    // MetadataAccount2 were always written as gzip compressed json, so those are used as the data header.
+    // The content hash can not be computed on chain and is passed in.
+    pub fn migrate(ctx: Context<Migrate>, content_hash: [u8; 32]) -> Result<()> {
+        let pda = &ctx.accounts.pda;
+        if pda.owner != ctx.program_id {
+            return err!(MyError::NotALegacyAccount);
+        }
+
+        let (authority, data_type, stored_program_id, data_len, data_offset, data_header) = {
+            let data = pda.try_borrow_data()?;
+            let mut reader: &[u8] = &data;
+            let gzip_json = (Encoding::Utf8, Compression::Gzip, Format::Json, DataSource::Direct);
+            if data.starts_with(&MetadataAccount::DISCRIMINATOR) {
+                let legacy = MetadataAccount::try_deserialize(&mut reader)?;
+                (legacy.authority, legacy.data_type, None, legacy.data_len, LEGACY_METADATA_ACCOUNT_SIZE, gzip_json)
+            } else if data.starts_with(&MetadataAccount2::DISCRIMINATOR) {
+                let legacy = MetadataAccount2::try_deserialize(&mut reader)?;
+                (legacy.authority, legacy.data_type, Some(legacy.program_id), legacy.data_len, LEGACY_METADATA_ACCOUNT_2_SIZE, gzip_json)
+            } else if data.starts_with(&MetadataAccount3::DISCRIMINATOR) && is_legacy_metadata_account_3(&data) {
+                let legacy = LegacyMetadataAccount3::deserialize(&mut &data[8..])?;
+                let data_header = (legacy.encoding, legacy.compression, legacy.format, legacy.data_source);
+                (legacy.authority, legacy.data_type, Some(legacy.program_id), legacy.data_len, LEGACY_METADATA_ACCOUNT_3_SIZE, data_header)
+            } else {
+                return err!(MyError::NotALegacyAccount);
+            }
+        };
+        let (encoding, compression, format, data_source) = data_header;
+
+        if authority != ctx.accounts.authority.key() {
+            return err!(MyError::InvalidAuthority);
+        }
+        // MetadataAccount2 and 3 store the program id. MetadataAccount only has it in its address.
+        if let Some(stored_program_id) = stored_program_id {
+            if stored_program_id != ctx.accounts.program_id.key() {
+                return err!(MyError::WrongProgramId);
+            }
+        }
+
+        // Only accounts at the canonical or the signer seeded address can be migrated
+        let data_type_len = data_type.iter().position(|&x| x == 0).unwrap_or(DATA_TYPE_LENGTH as usize);
+        let seed = &data_type[..data_type_len];
+        let program_key = ctx.accounts.program_id.key();
+        let (canonical_address, _) = Pubkey::find_program_address(&[program_key.as_ref(), seed], ctx.program_id);
+        let (signer_seeded_address, _) = Pubkey::find_program_address(&[program_key.as_ref(), authority.as_ref(), seed], ctx.program_id);
+        if pda.key() != canonical_address && pda.key() != signer_seeded_address {
+            return err!(MyError::InvalidMetadataAddress);
+        }
+
+        // The stored data length is not checked by older program versions, so it has to fit the account
+        let data_len_usize = ::std::convert::TryInto::<usize>::try_into(data_len)
+            .map_err(|_| MyError::LegacyDataOutOfBounds)?;
+        let data_offset = data_offset as usize;
+        let old_size = pda.data_len();
+        let data_end = data_offset.checked_add(data_len_usize).ok_or(MyError::LegacyDataOutOfBounds)?;
+        if data_end > old_size {
+            return err!(MyError::LegacyDataOutOfBounds);
+        }
+
+        // Grow the account so the bigger header fits in front of the trailing data
+        let new_size = (METADATA_ACCOUNT_SIZE as usize)
+            .checked_add(data_len_usize)
+            .ok_or(MyError::LegacyDataOutOfBounds)?;
+        let minimum_balance = Rent::get()?.minimum_balance(new_size.max(old_size));
+        let lamports = pda.lamports();
+        if minimum_balance > lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pda.to_account_info(),
+                    },
+                ),
+                minimum_balance - lamports,
+            )?;
+        }
+        if new_size > old_size {
+            pda.realloc(new_size, false)?;
+        }
+
+        let mut data = pda.try_borrow_mut_data()?;
+        data.copy_within(data_offset..data_end, METADATA_ACCOUNT_SIZE as usize);
+        let data_hash = hash(&data[(METADATA_ACCOUNT_SIZE as usize)..new_size]).to_bytes();
+
+        let metadata = MetadataAccount3 {
+            authority,
+            program_id: program_key,
+            data_type,
+            encoding,
+            compression,
+            format,
+            data_source,
+            data_len,
+            data_hash,
+            content_hash,
+            // The legacy data counts as the first version
+            version: if data_len > 0 { 1 } else { 0 },
+            frozen: false,
+            pending_authority: Pubkey::default(),
+            program_data_slot: 0,
+            executable_hash: [0; 32],
+        };
+        let mut writer: &mut [u8] = &mut data[..];
+        metadata.try_serialize(&mut writer)?;
+
+        emit!(MetadataMigrated {
+            metadata: pda.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            data_len: metadata.data_len,
+            version: metadata.version,
+            content_hash: metadata.content_hash,
+        });
         Ok(())
     }
 
@@ -218,75 +456,401 @@ pub mod metadata_program {
         Ok(())
     }
 
-    pub fn resize(_ctx: Context<Resize>, _len: u16) -> Result<()> {
+    // Grows or shrinks the metadata account to len bytes. The runtime only allows an account to grow by
+    // MAX_PERMITTED_DATA_INCREASE bytes per instruction, so bigger accounts are grown over several resize calls.
+    // seed_signer is the key a signer-seeded account was derived with, None for the canonical account.
+    pub fn resize(ctx: Context<Resize>, len: u32, seed_signer: Option<Pubkey>) -> Result<()> {
+        let program_key = ctx.accounts.program_id.key();
+        let data_type = ctx.accounts.pda.get_data_type();
+        let expected_address = match seed_signer {
+            Some(seed_signer) => Pubkey::find_program_address(
+                &[program_key.as_ref(), seed_signer.as_ref(), data_type.as_bytes()],
+                ctx.program_id,
+            ).0,
+            None => Pubkey::find_program_address(&[program_key.as_ref(), data_type.as_bytes()], ctx.program_id).0,
+        };
+        if ctx.accounts.pda.key() != expected_address {
+            return err!(MyError::InvalidMetadataAddress);
+        }
+
+        let signer = ctx.accounts.signer.key();
+        let is_pda_authority =
+            is_authority(&ctx.accounts.pda.authority, &signer, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts);
+        if !is_pda_authority && !is_active_delegate(ctx.accounts.delegate_record.as_ref(), &ctx.accounts.pda, &signer)? {
+            return err!(MyError::InvalidAuthority);
+        }
+
+        let pda = ctx.accounts.pda.to_account_info();
+        let old_size = pda.data_len();
+        let new_size = ::std::convert::TryInto::<usize>::try_into(len).unwrap();
+
+        let min_size = (METADATA_ACCOUNT_SIZE as usize)
+            .checked_add(ctx.accounts.pda.data_len as usize)
+            .ok_or(MyError::ResizeTooSmall)?;
+        if new_size < min_size {
+            return err!(MyError::ResizeTooSmall);
+        }
+        if new_size.saturating_sub(old_size) > MAX_PERMITTED_DATA_INCREASE {
+            return err!(MyError::ResizeTooLarge);
+        }
+        // The rent refund of a shrink goes to the signer, so a delegate could drain the rent the authority paid
+        if new_size < old_size && !is_pda_authority {
+            return err!(MyError::DelegateCannotShrink);
+        }
+
+        let minimum_balance = Rent::get()?.minimum_balance(new_size);
+        let lamports = pda.lamports();
+        if minimum_balance > lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.signer.to_account_info(),
+                        to: pda.clone(),
+                    },
+                ),
+                minimum_balance - lamports,
+            )?;
+        } else if lamports > minimum_balance {
+            // Refund the rent that is not needed anymore after shrinking
+            let refund = lamports - minimum_balance;
+            **pda.try_borrow_mut_lamports()? -= refund;
+            **ctx.accounts.signer.try_borrow_mut_lamports()? += refund;
+        }
+        pda.realloc(new_size, true)?;
+
+        let metadata = &ctx.accounts.pda;
+        emit!(MetadataResized {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            data_len: metadata.data_len,
+            account_size: len,
+        });
         Ok(())
     }
 
+    // Proposes a new authority. The change only takes effect once the new authority signs accept_authority.
+    // Erasing the authority is applied right away because nobody can sign for the erased authority.
     pub fn set_authority(ctx: Context<MetadataAccounts>, new_authority: Pubkey) -> Result<()> {
-        ctx.accounts.pda.authority = new_authority;
+        ctx.accounts.check_authority(ctx.remaining_accounts)?;
+        if new_authority == ERASED_AUTHORITY {
+            ctx.accounts.pda.authority = ERASED_AUTHORITY;
+            ctx.accounts.pda.pending_authority = Pubkey::default();
+        } else {
+            ctx.accounts.pda.pending_authority = new_authority;
+        }
+        emit!(authority_changed_event(&ctx.accounts.pda));
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        // A multisig accepts with threshold of its signers
+        if !is_authority(
+            &ctx.accounts.pda.pending_authority,
+            ctx.accounts.new_authority.key,
+            ctx.accounts.multisig.as_ref(),
+            ctx.remaining_accounts,
+        ) {
+            return err!(MyError::InvalidPendingAuthority);
+        }
+
+        let pda = &mut ctx.accounts.pda;
+        pda.authority = pda.pending_authority;
+        pda.pending_authority = Pubkey::default();
+        emit!(authority_changed_event(pda));
+        Ok(())
+    }
+
+    pub fn cancel_authority(ctx: Context<MetadataAccounts>) -> Result<()> {
+        ctx.accounts.check_authority(ctx.remaining_accounts)?;
+        if ctx.accounts.pda.pending_authority == Pubkey::default() {
+            return err!(MyError::NoPendingAuthority);
+        }
+        ctx.accounts.pda.pending_authority = Pubkey::default();
+        emit!(authority_changed_event(&ctx.accounts.pda));
+        Ok(())
+    }
+
+    // Permanently freezes the metadata account. The authority is erased so nobody, not even the
+    // program upgrade authority, can change or close the account afterwards.
+    pub fn freeze(ctx: Context<MetadataAccounts>) -> Result<()> {
+        ctx.accounts.check_authority(ctx.remaining_accounts)?;
+        ctx.accounts.pda.authority = ERASED_AUTHORITY;
+        ctx.accounts.pda.pending_authority = Pubkey::default();
+        ctx.accounts.pda.frozen = true;
+
+        let metadata = &ctx.accounts.pda;
+        emit!(MetadataFrozen {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            data_len: metadata.data_len,
+            version: metadata.version,
+            content_hash: metadata.content_hash,
+        });
+        Ok(())
+    }
+
+    // Creates a threshold authority. Setting a metadata account's authority to the multisig account
+    // requires threshold of the signers to sign every instruction of the authority.
+    // The signers can not be changed afterwards, transfer the authority to a new multisig instead.
+    pub fn create_multisig(ctx: Context<CreateMultisig>, threshold: u8, signers: Vec<Pubkey>) -> Result<()> {
+        require!(signers.len() <= MAX_MULTISIG_SIGNERS, MyError::TooManySigners);
+        require!(threshold > 0 && threshold as usize <= signers.len(), MyError::InvalidThreshold);
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), MyError::DuplicateSigner);
+        }
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.threshold = threshold;
+        multisig.signers = signers;
+        Ok(())
+    }
+
+    // Lets the delegate commit new versions through set_buffer until expiry_slot. 0 means it never expires.
+    // The delegate can not change the authority of the account or close it.
+    pub fn add_delegate(ctx: Context<AddDelegate>, delegate: Pubkey, expiry_slot: u64) -> Result<()> {
+        if !is_authority(&ctx.accounts.pda.authority, ctx.accounts.authority.key, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+
+        // The record is bound to the metadata authority, which is the multisig and not the signer for a multisig
+        let record = &mut ctx.accounts.delegate_record;
+        record.metadata = ctx.accounts.pda.key();
+        record.delegate = delegate;
+        record.authority = ctx.accounts.pda.authority;
+        record.expiry_slot = expiry_slot;
+
+        let metadata = &ctx.accounts.pda;
+        emit!(DelegateAdded {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            delegate,
+            expiry_slot,
+        });
+        Ok(())
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        let metadata = &ctx.accounts.pda;
+        if !is_authority(&metadata.authority, ctx.accounts.authority.key, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+        emit!(DelegateRevoked {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            delegate: ctx.accounts.delegate_record.delegate,
+        });
+        Ok(())
+    }
+
+    // Binds the current version to the deployed executable. Hashing a large program does not fit into
+    // one transaction, so start_executable_hash records what is attested, hash_executable hashes the
+    // executable in chunks and finish_executable_hash writes the digest into the metadata account.
+    pub fn start_executable_hash(ctx: Context<StartExecutableHash>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        if !is_authority(&ctx.accounts.pda.authority, &signer, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts)
+            && !is_active_delegate(ctx.accounts.delegate_record.as_ref(), &ctx.accounts.pda, &signer)?
+        {
+            return err!(MyError::InvalidAuthority);
+        }
+
+        let program_data = ctx.accounts.program_data.as_ref();
+        get_program_authority(&ctx.accounts.program_id, program_data)?;
+        let (executable_account, executable_offset) = get_executable_account(&ctx.accounts.program_id, program_data)?;
+        let executable_len = executable_account.data_len().saturating_sub(executable_offset);
+
+        let hash_state = &mut ctx.accounts.hash_state;
+        hash_state.payer = signer;
+        hash_state.version = ctx.accounts.pda.version;
+        hash_state.program_data_slot = get_program_deploy_slot(&ctx.accounts.program_id, program_data)?;
+        hash_state.executable_len = ::std::convert::TryInto::<u32>::try_into(executable_len).unwrap();
+        hash_state.offset = 0;
+        hash_state.digest = [0; 32];
+        Ok(())
+    }
+
+    // Hashes the next chunks of EXECUTABLE_HASH_CHUNK_SIZE bytes. Anyone can continue the hash,
+    // the result only depends on the executable.
+    pub fn hash_executable(ctx: Context<HashExecutable>, chunks: u8) -> Result<()> {
+        let program_data = ctx.accounts.program_data.as_ref();
+        get_program_authority(&ctx.accounts.program_id, program_data)?;
+        ctx.accounts.hash_state.check_current(&ctx.accounts.pda, &ctx.accounts.program_id, program_data)?;
+
+        let (executable_account, executable_offset) = get_executable_account(&ctx.accounts.program_id, program_data)?;
+        let executable_data = executable_account.try_borrow_data()?;
+        let executable = &executable_data[executable_offset..];
+
+        let hash_state = &mut ctx.accounts.hash_state;
+        for _ in 0..chunks {
+            let start = hash_state.offset as usize;
+            if start >= executable.len() {
+                break;
+            }
+            let end = start.saturating_add(EXECUTABLE_HASH_CHUNK_SIZE as usize).min(executable.len());
+            hash_state.digest = hashv(&[&hash_state.digest, &executable[start..end]]).to_bytes();
+            hash_state.offset = end as u32;
+        }
+        Ok(())
+    }
+
+    pub fn finish_executable_hash(ctx: Context<FinishExecutableHash>) -> Result<()> {
+        let program_data = ctx.accounts.program_data.as_ref();
+        get_program_authority(&ctx.accounts.program_id, program_data)?;
+        let hash_state = &ctx.accounts.hash_state;
+        hash_state.check_current(&ctx.accounts.pda, &ctx.accounts.program_id, program_data)?;
+        if hash_state.offset != hash_state.executable_len {
+            return err!(MyError::ExecutableHashIncomplete);
+        }
+
+        let executable_hash = hash_state.digest;
+        let metadata = &mut ctx.accounts.pda;
+        metadata.executable_hash = executable_hash;
+        emit!(ExecutableHashed {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            version: metadata.version,
+            program_data_slot: metadata.program_data_slot,
+            executable_hash,
+        });
+        Ok(())
+    }
+
+    // Gives the rent of an unfinished hash back, for example after the program was upgraded while hashing
+    pub fn close_executable_hash(_ctx: Context<CloseExecutableHash>) -> Result<()> {
+        Ok(())
+    }
+
+    // Copies the current version into its archive PDA. Like resize, every call grows the archive by at most
+    // MAX_PERMITTED_DATA_INCREASE bytes, so versions too large for set_buffer to archive at once are written
+    // with this first. Anyone can pay for it because the archive only ever holds the current version.
+    pub fn write_archive(ctx: Context<WriteArchive>) -> Result<()> {
+        if ctx.accounts.pda.version == 0 {
+            return err!(MyError::NothingToArchive);
+        }
+        write_archive_step(
+            &ctx.accounts.pda,
+            &ctx.accounts.archive,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+        )?;
         Ok(())
     }
 
     pub fn set_buffer(ctx: Context<SetBuffer>) -> Result<()> {
+        // A frozen account can not be overwritten, also not by the program upgrade authority
+        if ctx.accounts.pda.frozen {
+            return err!(MyError::AccountFrozen);
+        }
+
         // First verify that the PDA belongs to the provided program ID
         if ctx.accounts.pda.program_id != ctx.accounts.program_id.key() {
             return err!(MyError::WrongProgramId);
         }
 
-        // First check if it's the program's authority by checking program data
-        let is_program_authority = {
-            if ctx.accounts.program_id.owner.key() != bpf_loader_upgradeable::ID 
-                && ctx.accounts.program_id.owner.key() != bpf_loader::ID 
-                && ctx.accounts.program_id.owner.key() != bpf_loader_deprecated::ID {
-                return err!(MyError::NotAProgram);
-            }
-            
-            if !ctx.accounts.program_id.executable {
-                return err!(MyError::NotExecutable);
-            }
-
-            // Borrow the program's account data
-            let mut program_borrowed_data: &[u8] = &ctx.accounts.program_id.try_borrow_data()?;
-            let upgradable_loader_state = UpgradeableLoaderState::try_deserialize_unchecked(&mut program_borrowed_data)?;
-
-            match upgradable_loader_state {
-                UpgradeableLoaderState::Program { programdata_address } => {
-                    // Ensure the program data address matches
-                    if programdata_address != ctx.accounts.program_data.key() {
-                        return err!(MyError::WrongAuthority);
-                    }
-                    ctx.accounts.program_data.upgrade_authority_address == Some(ctx.accounts.authority.key())
-                },
-                _ => return err!(MyError::ShouldBeProgramAccount)
-            }
-        };
+        // The buffer must have been created for this program and data type
+        if ctx.accounts.buffer.program_id != ctx.accounts.pda.program_id {
+            return err!(MyError::WrongProgramId);
+        }
+        if ctx.accounts.buffer.data_type != ctx.accounts.pda.data_type {
+            return err!(MyError::DataTypeMismatch);
+        }
 
-        // If not program authority, then must be PDA authority
-        if !is_program_authority && ctx.accounts.pda.authority != ctx.accounts.authority.key() {
+        // First check if it's the program's authority. Programs without an authority can not override the PDA authority.
+        let program_data = ctx.accounts.program_data.as_ref();
+        let is_program_authority = get_program_authority(&ctx.accounts.program_id, program_data)?
+            == Some(ctx.accounts.authority.key());
+
+        // If not program authority, then must be PDA authority or one of its delegates
+        let authority = ctx.accounts.authority.key();
+        if !is_program_authority
+            && !is_authority(&ctx.accounts.pda.authority, &authority, ctx.accounts.multisig.as_ref(), ctx.remaining_accounts)
+            && !is_active_delegate(ctx.accounts.delegate_record.as_ref(), &ctx.accounts.pda, &authority)?
+        {
             return err!(MyError::InvalidAuthority);
         }
 
-        // If it's the program authority, set the PDA authority to match
-        if is_program_authority {
+        // If it's the program authority, set the PDA authority to match.
+        // A transfer proposed by the previous PDA authority is dropped with it.
+        if is_program_authority && ctx.accounts.pda.authority != ctx.accounts.authority.key() {
             ctx.accounts.pda.authority = ctx.accounts.authority.key();
+            ctx.accounts.pda.pending_authority = Pubkey::default();
         }
 
+        // Keep a copy of the current version in its own archive PDA before it gets overwritten.
+        // Version 0 is the empty account right after initialize so there is nothing to archive.
+        let archived = ctx.accounts.archive.is_some() && ctx.accounts.pda.version > 0;
+        if let Some(archive) = &ctx.accounts.archive {
+            if archived {
+                let (payer, system_program) = match (&ctx.accounts.payer, &ctx.accounts.system_program) {
+                    (Some(payer), Some(system_program)) => (payer, system_program),
+                    _ => return err!(MyError::MissingArchiveAccounts),
+                };
+
+                // Versions larger than one archive step are written with write_archive before
+                if !write_archive_step(&ctx.accounts.pda, archive, payer, system_program, ctx.program_id)? {
+                    return err!(MyError::ArchiveTooLarge);
+                }
+            }
+        }
+        ctx.accounts.pda.version = ctx.accounts.pda.version.checked_add(1).unwrap();
+
+        // Verify that the written bytes are exactly what the uploader declared when creating the buffer
+        let buffer_len = ::std::convert::TryInto::<usize>::try_into(ctx.accounts.buffer.data_len).unwrap();
+        let data_hash = hash(&ctx.accounts.buffer.trailing_data()[..buffer_len]).to_bytes();
+        if data_hash != ctx.accounts.buffer.data_hash {
+            return err!(MyError::DataHashMismatch);
+        }
+        if ctx.accounts.buffer.data_source == DataSource::Account {
+            check_account_pointer(
+                &ctx.accounts.buffer.trailing_data()[..buffer_len],
+                &ctx.accounts.buffer.compression,
+                &ctx.accounts.pda.key(),
+            )?;
+        }
+
+        // Remember which deployment of the program this version was written for
+        let program_data_slot = get_program_deploy_slot(&ctx.accounts.program_id, ctx.accounts.program_data.as_ref())?;
+
         // Copy metadata from buffer to PDA
         let buffer = &ctx.accounts.buffer;
         let metadata = &mut ctx.accounts.pda;
+        metadata.program_data_slot = program_data_slot;
+        // The new version has not been attested against the executable yet
+        metadata.executable_hash = [0; 32];
         
         metadata.encoding = buffer.encoding.clone();
         metadata.compression = buffer.compression.clone();
         metadata.format = buffer.format.clone();
         metadata.data_source = buffer.data_source.clone();
         metadata.data_len = buffer.data_len;
+        metadata.data_hash = data_hash;
+        metadata.content_hash = buffer.content_hash;
+
+        emit!(MetadataSet {
+            metadata: metadata.key(),
+            program_id: metadata.program_id,
+            data_type: metadata.get_data_type(),
+            authority: metadata.authority,
+            data_len: metadata.data_len,
+            version: metadata.version,
+            data_hash: metadata.data_hash,
+            content_hash: metadata.content_hash,
+            archived,
+            program_data_slot: metadata.program_data_slot,
+        });
 
         // Perform the buffer set operation
         // Here we on purpose do not allow changing the data type.
         // ctx.accounts.pda.set_data_type(&ctx.accounts.buffer.get_data_type())?;
 
         use MetadataUploadTrailingData;
-        let buffer_len = ::std::convert::TryInto::<usize>::try_into(ctx.accounts.buffer.data_len).unwrap();
         let mut target = ctx.accounts.pda.trailing_data_mut();
         let source = &ctx.accounts.buffer.trailing_data()[..buffer_len];
         require_gte!(target.len(), buffer_len);
@@ -320,15 +884,312 @@ pub mod metadata_program {
     impl<'a, 'info: 'a> MetadataBufferUploadTrailingData<'a> for &'a Account<'info, MetadataBuffer> {
         fn trailing_data(self) -> Ref<'a, [u8]> {
             let info: &AccountInfo<'info> = self.as_ref();
-            Ref::map(info.try_borrow_data().unwrap(), |d| &d[(METADATA_ACCOUNT_SIZE as usize )..])
+            Ref::map(info.try_borrow_data().unwrap(), |d| &d[(METADATA_BUFFER_SIZE as usize )..])
         }
         fn trailing_data_mut(self) -> RefMut<'a, [u8]> {
             let info: &AccountInfo<'info> = self.as_ref();
-            RefMut::map(info.try_borrow_mut_data().unwrap(), |d| &mut d[(METADATA_ACCOUNT_SIZE as usize )..])
+            RefMut::map(info.try_borrow_mut_data().unwrap(), |d| &mut d[(METADATA_BUFFER_SIZE as usize )..])
         }
     }
 }
 
+// How the authority of a program is established depends on its loader:
+// - bpf_loader_upgradeable: the upgrade authority in the ProgramData account. None once the program is immutable.
+// - loader-v4: the authority in the program header. None once the program is finalized.
+// - bpf_loader and bpf_loader_deprecated: these programs are immutable and never have an authority.
+pub fn get_program_authority(
+    program_id: &AccountInfo,
+    program_data: Option<&Account<ProgramData>>,
+) -> Result<Option<Pubkey>> {
+    let loader = program_id.owner.key();
+    if loader != bpf_loader_upgradeable::ID
+        && loader != bpf_loader::ID
+        && loader != bpf_loader_deprecated::ID
+        && loader != loader_v4::ID {
+        return err!(MyError::NotAProgram);
+    }
+
+    if !program_id.executable {
+        return err!(MyError::NotExecutable);
+    }
+
+    if loader == bpf_loader_upgradeable::ID {
+        // Borrow the program's account data
+        let mut program_borrowed_data: &[u8] = &program_id.try_borrow_data()?;
+        let upgradable_loader_state = UpgradeableLoaderState::try_deserialize_unchecked(&mut program_borrowed_data)?;
+
+        match upgradable_loader_state {
+            UpgradeableLoaderState::Program { programdata_address } => {
+                // Ensure the program data address matches
+                let program_data = program_data.ok_or(MyError::MissingProgramData)?;
+                if programdata_address != program_data.key() {
+                    return err!(MyError::WrongAuthority);
+                }
+                Ok(program_data.upgrade_authority_address)
+            },
+            _ => err!(MyError::ShouldBeProgramAccount)
+        }
+    } else if loader == loader_v4::ID {
+        // LoaderV4State: slot (u64), authority_address (Pubkey), status (u64)
+        let program_borrowed_data = program_id.try_borrow_data()?;
+        if program_borrowed_data.len() < LOADER_V4_STATE_SIZE {
+            return err!(MyError::NotAProgram);
+        }
+        let authority = Pubkey::try_from(&program_borrowed_data[8..40]).unwrap();
+        let status = u64::from_le_bytes(program_borrowed_data[40..48].try_into().unwrap());
+        if status == LOADER_V4_STATUS_FINALIZED {
+            Ok(None)
+        } else {
+            Ok(Some(authority))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Slot of the last deployment. Programs of bpf_loader and bpf_loader_deprecated do not record it.
+// The program data account must have been validated by get_program_authority before.
+pub fn get_program_deploy_slot(
+    program_id: &AccountInfo,
+    program_data: Option<&Account<ProgramData>>,
+) -> Result<u64> {
+    let loader = program_id.owner.key();
+    if loader == bpf_loader_upgradeable::ID {
+        Ok(program_data.ok_or(MyError::MissingProgramData)?.slot)
+    } else if loader == loader_v4::ID {
+        let program_borrowed_data = program_id.try_borrow_data()?;
+        if program_borrowed_data.len() < LOADER_V4_STATE_SIZE {
+            return err!(MyError::NotAProgram);
+        }
+        Ok(u64::from_le_bytes(program_borrowed_data[0..8].try_into().unwrap()))
+    } else {
+        Ok(0)
+    }
+}
+
+// The account that holds the executable of the program and the offset at which the executable starts.
+// The program data account must have been validated by get_program_authority before.
+pub fn get_executable_account<'info>(
+    program_id: &AccountInfo<'info>,
+    program_data: Option<&Account<'info, ProgramData>>,
+) -> Result<(AccountInfo<'info>, usize)> {
+    let loader = program_id.owner.key();
+    if loader == bpf_loader_upgradeable::ID {
+        let program_data = program_data.ok_or(MyError::MissingProgramData)?;
+        Ok((program_data.to_account_info(), UpgradeableLoaderState::size_of_programdata_metadata()))
+    } else if loader == loader_v4::ID {
+        Ok((program_id.clone(), LOADER_V4_STATE_SIZE))
+    } else {
+        Ok((program_id.clone(), 0))
+    }
+}
+
+// The data of a DataSource::Account is a pointer to the account that holds the data: its pubkey,
+// optionally followed by the offset (u32 le) and length (u32 le) of the data in that account.
+// Without a range readers take the whole account or, for a metadata account, its data.
+pub fn check_account_pointer(pointer: &[u8], compression: &Compression, metadata: &Pubkey) -> Result<()> {
+    if *compression != Compression::None {
+        return err!(MyError::InvalidAccountPointer);
+    }
+    match pointer.len() {
+        ACCOUNT_POINTER_SIZE => {}
+        ACCOUNT_POINTER_WITH_RANGE_SIZE => {
+            let offset = u32::from_le_bytes(pointer[32..36].try_into().unwrap());
+            let length = u32::from_le_bytes(pointer[36..40].try_into().unwrap());
+            if length == 0 || offset.checked_add(length).is_none() {
+                return err!(MyError::InvalidAccountPointer);
+            }
+        }
+        _ => return err!(MyError::InvalidAccountPointer),
+    }
+    // An account pointing to itself could never be resolved
+    if pointer[..32] == metadata.to_bytes() {
+        return err!(MyError::InvalidAccountPointer);
+    }
+    Ok(())
+}
+
+// Creates the archive of the current version or grows it by one step, copying the data it gained from the
+// metadata account. Returns whether the archive is complete.
+pub fn write_archive_step<'info>(
+    metadata: &Account<'info, MetadataAccount3>,
+    archive: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    program_id: &Pubkey,
+) -> Result<bool> {
+    let metadata_key = metadata.key();
+    let version_bytes = metadata.version.to_le_bytes();
+    let (archive_address, bump) = Pubkey::find_program_address(
+        &[ARCHIVE_SEED, metadata_key.as_ref(), &version_bytes],
+        program_id,
+    );
+    if archive.key() != archive_address {
+        return err!(MyError::InvalidArchiveAddress);
+    }
+
+    let data_len = ::std::convert::TryInto::<usize>::try_into(metadata.data_len).unwrap();
+    let space = (METADATA_ARCHIVE_SIZE as usize).checked_add(data_len).unwrap();
+    let old_size = if archive.owner == program_id { archive.data_len() } else { 0 };
+    let new_size = space.min(old_size.saturating_add(MAX_PERMITTED_DATA_INCREASE));
+
+    if old_size == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: archive.clone(),
+                },
+                &[&[ARCHIVE_SEED, metadata_key.as_ref(), &version_bytes, &[bump]]],
+            ),
+            Rent::get()?.minimum_balance(new_size),
+            new_size as u64,
+            program_id,
+        )?;
+
+        let header = MetadataArchive {
+            metadata: metadata_key,
+            version: metadata.version,
+            encoding: metadata.encoding.clone(),
+            compression: metadata.compression.clone(),
+            format: metadata.format.clone(),
+            data_source: metadata.data_source.clone(),
+            data_len: metadata.data_len,
+            data_hash: metadata.data_hash,
+            content_hash: metadata.content_hash,
+        };
+        let mut archive_data = archive.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut archive_data[..];
+        header.try_serialize(&mut writer)?;
+    } else if new_size > old_size {
+        let minimum_balance = Rent::get()?.minimum_balance(new_size);
+        let lamports = archive.lamports();
+        if minimum_balance > lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: archive.clone(),
+                    },
+                ),
+                minimum_balance - lamports,
+            )?;
+        }
+        archive.realloc(new_size, false)?;
+    }
+
+    // Copy the part of the data the archive gained in this step
+    let header_size = METADATA_ARCHIVE_SIZE as usize;
+    let copied = old_size.max(header_size) - header_size;
+    let end = new_size - header_size;
+    archive.try_borrow_mut_data()?[header_size + copied..new_size]
+        .copy_from_slice(&metadata.trailing_data()[copied..end]);
+    Ok(new_size == space)
+}
+
+// MetadataAccount3 accounts with the old 96 byte header share the discriminator with current ones.
+// A current account always has room for its header and data and stores the hash of that data where
+// the old header already had its trailing data.
+pub fn is_legacy_metadata_account_3(data: &[u8]) -> bool {
+    let legacy_size = LEGACY_METADATA_ACCOUNT_3_SIZE as usize;
+    if data.len() < legacy_size {
+        return false;
+    }
+    let data_len = u32::from_le_bytes(data[legacy_size - 4..legacy_size].try_into().unwrap()) as usize;
+    let header_size = METADATA_ACCOUNT_SIZE as usize;
+    match header_size.checked_add(data_len) {
+        Some(end) if end <= data.len() => {
+            data_len > 0 && hash(&data[header_size..end]).to_bytes()[..] != data[legacy_size..legacy_size + 32]
+        }
+        _ => true,
+    }
+}
+
+// Programs of the non-upgradeable loaders never had an authority. They can only be claimed with a one time
+// attestation of the deployer: the program account itself has to sign, which needs the keypair the program
+// was deployed with. Upgradeable programs with a revoked authority and finalized loader-v4 programs were made
+// immutable on purpose, so nobody can claim those.
+pub fn is_program_authority(program_id: &AccountInfo, program_authority: Option<Pubkey>, signer: &Pubkey) -> bool {
+    match program_authority {
+        Some(program_authority) => program_authority == *signer,
+        None => {
+            program_id.is_signer
+                && (*program_id.owner == bpf_loader::ID || *program_id.owner == bpf_loader_deprecated::ID)
+        }
+    }
+}
+
+pub fn authority_changed_event(metadata: &Account<MetadataAccount3>) -> AuthorityChanged {
+    AuthorityChanged {
+        metadata: metadata.key(),
+        program_id: metadata.program_id,
+        data_type: metadata.get_data_type(),
+        authority: metadata.authority,
+        pending_authority: metadata.pending_authority,
+    }
+}
+
+// The authority of a metadata account is a single key or a MetadataMultisig account. For a multisig
+// the signer and the signing remaining accounts together have to reach its threshold.
+pub fn is_authority(
+    metadata_authority: &Pubkey,
+    signer: &Pubkey,
+    multisig: Option<&Account<MetadataMultisig>>,
+    remaining_accounts: &[AccountInfo],
+) -> bool {
+    if metadata_authority == signer {
+        return true;
+    }
+    let Some(multisig) = multisig else {
+        return false;
+    };
+    if multisig.key() != *metadata_authority {
+        return false;
+    }
+
+    let mut approvals: Vec<&Pubkey> = Vec::new();
+    let signers = std::iter::once(signer)
+        .chain(remaining_accounts.iter().filter(|account| account.is_signer).map(|account| account.key));
+    for key in signers {
+        if multisig.signers.contains(key) && !approvals.contains(&key) {
+            approvals.push(key);
+        }
+    }
+    approvals.len() >= multisig.threshold as usize
+}
+
+// The PDA a program signs with to be the authority of its own metadata accounts
+pub fn get_program_signer_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id)
+}
+
+// A delegate record only counts while the authority that added it is still the authority of the
+// metadata account, so transferring the authority drops all existing delegates.
+pub fn is_active_delegate(
+    delegate_record: Option<&Account<MetadataDelegate>>,
+    metadata: &Account<MetadataAccount3>,
+    signer: &Pubkey,
+) -> Result<bool> {
+    let Some(record) = delegate_record else {
+        return Ok(false);
+    };
+    if record.metadata != metadata.key() || record.delegate != *signer || record.authority != metadata.authority {
+        return err!(MyError::InvalidDelegate);
+    }
+    if record.expiry_slot != 0 && Clock::get()?.slot > record.expiry_slot {
+        return err!(MyError::DelegateExpired);
+    }
+    Ok(true)
+}
+
+// Data types are stored null padded
+pub fn data_type_to_string(data_type: &[u8; 16]) -> String {
+    let len = data_type.iter().position(|&x| x == 0).unwrap_or(DATA_TYPE_LENGTH as usize);
+    String::from_utf8_lossy(&data_type[..len]).to_string()
+}
+
 #[derive(Accounts)]
 #[instruction(
     seed: String,  // Remove data_type, only keep seed
@@ -350,10 +1211,11 @@ pub struct Initialize<'info> {
     pub signer: Signer<'info>,
     pub system_program: Program<'info, System>,
     /// CHECK: This is the program id of the program you want to upload the metadata for.
+    /// It has to sign when the program has no authority. Checks are done in code.
     pub program_id: AccountInfo<'info>,
-    // Make sure that the signer is actually the upgrade authority of the program.
-    #[account(constraint = program_data.upgrade_authority_address == Some(signer.key()))]
-    pub program_data: Account<'info, ProgramData>,
+    // Only needed for programs owned by the upgradeable loader. Checks that the signer
+    // is the upgrade authority of the program are done in code.
+    pub program_data: Option<Account<'info, ProgramData>>,
 }
 
 #[derive(Accounts)]
@@ -378,28 +1240,84 @@ pub struct InitializeWithSignerSeed<'info> {
     pub system_program: Program<'info, System>,
     /// CHECK: This is the program id of the program you want to upload the metadata for. Checks are done in code.
     pub program_id: AccountInfo<'info>,
-    // When we add signer seed we do NOT check if the signer is the program authority
-    pub program_data: Account<'info, ProgramData>,
+    // When we add signer seed we do NOT check if the signer is the program authority.
+    // Only needed for programs owned by the upgradeable loader.
+    pub program_data: Option<Account<'info, ProgramData>>,
 }
 
+#[derive(Accounts)]
+#[instruction(seed: String)]
+pub struct InitializeWithProgramSigner<'info> {
+    #[account(
+        init,
+        seeds = [program_id.key.as_ref(), seed.as_ref()],
+        bump,
+        payer = payer,
+        space = METADATA_ACCOUNT_SIZE as usize,
+    )]
+    pub pda: Account<'info, MetadataAccount3>,
+    #[account(
+        constraint = program_signer.key() == get_program_signer_address(program_id.key).0 @ MyError::InvalidProgramSigner
+    )]
+    pub program_signer: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: The program that publishes its own metadata. It proves this by signing with its program signer PDA.
+    #[account(constraint = program_id.executable @ MyError::NotExecutable)]
+    pub program_id: AccountInfo<'info>,
+}
+
+// The authority is checked in code with check_authority, because a multisig authority
+// needs the other signers from the remaining accounts.
 #[derive(Accounts)]
 pub struct MetadataAccounts<'info> {
-    #[account(mut, has_one = authority)]
+    #[account(mut, constraint = !pda.frozen @ MyError::AccountFrozen)]
     pub pda: Account<'info, MetadataAccount3>,
     #[account(constraint = authority.key != &ERASED_AUTHORITY)]
     pub authority: Signer<'info>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
+}
+
+impl<'info> MetadataAccounts<'info> {
+    pub fn check_authority(&self, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        if !is_authority(&self.pda.authority, self.authority.key, self.multisig.as_ref(), remaining_accounts) {
+            return err!(MyError::InvalidAuthority);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(init, payer = payer, space = METADATA_MULTISIG_SIZE as usize)]
+    pub multisig: Account<'info, MetadataMultisig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = pda.pending_authority != Pubkey::default() @ MyError::NoPendingAuthority,
+        constraint = !pda.frozen @ MyError::AccountFrozen
+    )]
+    pub pda: Account<'info, MetadataAccount3>,
+    pub new_authority: Signer<'info>,
+    // Only needed when the pending authority is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
 }
 
 #[derive(Accounts)]
 // Seed can be dynamic. For IDL use "idl" as seed. For metadata use "metadata" as seed.
-#[instruction(len: u16)]
 pub struct Resize<'info> {
     #[account(
         mut,
-        realloc = len as usize, 
-        realloc::zero = true, 
-        realloc::payer = signer,
-        constraint = pda.authority == signer.key()
+        constraint = pda.program_id == program_id.key() @ MyError::WrongProgramId,
+        constraint = !pda.frozen @ MyError::AccountFrozen
     )]
     pub pda: Account<'info, MetadataAccount3>,
     #[account(mut)]
@@ -407,6 +1325,24 @@ pub struct Resize<'info> {
     pub system_program: Program<'info, System>,
     /// CHECK: This is the program id of the program you want to upload the metadata for.
     pub program_id: AccountInfo<'info>,
+    // Only needed when the signer is a delegate instead of the authority
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
+}
+
+// Accounts for migrating a MetadataAccount or MetadataAccount2 to a MetadataAccount3.
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// CHECK: Legacy metadata account. The layout is detected by its discriminator in code.
+    #[account(mut)]
+    pub pda: UncheckedAccount<'info>,
+    // The authority stored in the legacy account. Pays for the bigger header.
+    #[account(mut, constraint = authority.key != &ERASED_AUTHORITY)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is the program id of the program the metadata belongs to. Checked against the address in code.
+    pub program_id: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 // Accounts for creating an metadata buffer.
@@ -417,33 +1353,44 @@ pub struct CreateBuffer<'info> {
     pub buffer: Account<'info, MetadataBuffer>,
     #[account(constraint = authority.key != &ERASED_AUTHORITY)]
     pub authority: Signer<'info>,
+    /// CHECK: The program the buffer is written for. It is compared with the metadata account in set_buffer.
+    pub program_id: AccountInfo<'info>,
 }
 
-// Close metadata account to claim back SOL. Only the authority can close the account.
+// Close metadata account to claim back SOL. Only the authority can close the account, it is checked
+// in code because a multisig authority needs the other signers from the remaining accounts.
 #[derive(Accounts)]
 pub struct CloseMetadataAccount2<'info> {
-    #[account(mut, close = authority, constraint = metadata_account.authority == authority.key())]
+    #[account(mut, close = authority)]
     pub metadata_account: Account<'info, MetadataAccount2>,
     #[account(constraint = authority.key != &ERASED_AUTHORITY)]
     pub authority: Signer<'info>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
 }
 
-// Close metadata account to claim back SOL. Only the authority can close the account.
+// Close metadata account to claim back SOL. Only the authority can close the account, it is checked
+// in code because a multisig authority needs the other signers from the remaining accounts.
 #[derive(Accounts)]
 pub struct CloseMetadataAccount3<'info> {
-    #[account(mut, close = authority, constraint = metadata_account.authority == authority.key())]
+    #[account(mut, close = authority, constraint = !metadata_account.frozen @ MyError::AccountFrozen)]
     pub metadata_account: Account<'info, MetadataAccount3>,
     #[account(constraint = authority.key != &ERASED_AUTHORITY)]
     pub authority: Signer<'info>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
 }
 
-// Close metadata account to claim back SOL. Only the authority can close the account.
+// Close metadata account to claim back SOL. Only the authority can close the account, it is checked
+// in code because a multisig authority needs the other signers from the remaining accounts.
 #[derive(Accounts)]
 pub struct CloseMetadataAccount1<'info> {
-    #[account(mut, close = authority, constraint = metadata_account.authority == authority.key())]
+    #[account(mut, close = authority)]
     pub metadata_account: Account<'info, MetadataAccount>,
     #[account(constraint = authority.key != &ERASED_AUTHORITY)]
     pub authority: Signer<'info>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
 }
 
 // Close buffer to claim back SOL
@@ -464,6 +1411,124 @@ pub struct WriteBuffer<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct AddDelegate<'info> {
+    #[account(
+        init,
+        seeds = [DELEGATE_SEED, pda.key().as_ref(), delegate.as_ref()],
+        bump,
+        payer = authority,
+        space = METADATA_DELEGATE_SIZE as usize,
+    )]
+    pub delegate_record: Account<'info, MetadataDelegate>,
+    #[account(constraint = !pda.frozen @ MyError::AccountFrozen)]
+    pub pda: Account<'info, MetadataAccount3>,
+    // The metadata authority or, for a multisig authority, one of its signers. Pays for the record.
+    #[account(mut, constraint = authority.key != &ERASED_AUTHORITY)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
+}
+
+// The current authority can also revoke records added by a previous authority
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        constraint = delegate_record.metadata == pda.key() @ MyError::InvalidDelegate
+    )]
+    pub delegate_record: Account<'info, MetadataDelegate>,
+    pub pda: Account<'info, MetadataAccount3>,
+    #[account(mut, constraint = authority.key != &ERASED_AUTHORITY)]
+    pub authority: Signer<'info>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
+}
+
+#[derive(Accounts)]
+pub struct StartExecutableHash<'info> {
+    #[account(
+        init,
+        seeds = [EXECUTABLE_HASH_SEED, pda.key().as_ref()],
+        bump,
+        payer = signer,
+        space = METADATA_EXECUTABLE_HASH_SIZE as usize,
+    )]
+    pub hash_state: Account<'info, MetadataExecutableHash>,
+    #[account(
+        constraint = pda.program_id == program_id.key() @ MyError::WrongProgramId,
+        constraint = !pda.frozen @ MyError::AccountFrozen
+    )]
+    pub pda: Account<'info, MetadataAccount3>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: The program the metadata belongs to. Checked against the metadata account.
+    pub program_id: AccountInfo<'info>,
+    /// The program data account with the executable. Only needed for programs owned by the upgradeable loader.
+    pub program_data: Option<Account<'info, ProgramData>>,
+    pub system_program: Program<'info, System>,
+    // Only needed when the signer is a delegate instead of the authority
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
+}
+
+#[derive(Accounts)]
+pub struct HashExecutable<'info> {
+    #[account(mut, seeds = [EXECUTABLE_HASH_SEED, pda.key().as_ref()], bump)]
+    pub hash_state: Account<'info, MetadataExecutableHash>,
+    #[account(constraint = pda.program_id == program_id.key() @ MyError::WrongProgramId)]
+    pub pda: Account<'info, MetadataAccount3>,
+    /// CHECK: The program the metadata belongs to. Checked against the metadata account.
+    pub program_id: AccountInfo<'info>,
+    /// The program data account with the executable. Only needed for programs owned by the upgradeable loader.
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[derive(Accounts)]
+pub struct FinishExecutableHash<'info> {
+    #[account(mut, close = payer, has_one = payer, seeds = [EXECUTABLE_HASH_SEED, pda.key().as_ref()], bump)]
+    pub hash_state: Account<'info, MetadataExecutableHash>,
+    #[account(
+        mut,
+        constraint = pda.program_id == program_id.key() @ MyError::WrongProgramId,
+        constraint = !pda.frozen @ MyError::AccountFrozen
+    )]
+    pub pda: Account<'info, MetadataAccount3>,
+    /// CHECK: Receives the rent of the hash state. Checked against the payer stored in it.
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+    /// CHECK: The program the metadata belongs to. Checked against the metadata account.
+    pub program_id: AccountInfo<'info>,
+    /// The program data account with the executable. Only needed for programs owned by the upgradeable loader.
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseExecutableHash<'info> {
+    #[account(mut, close = payer, has_one = payer, seeds = [EXECUTABLE_HASH_SEED, pda.key().as_ref()], bump)]
+    pub hash_state: Account<'info, MetadataExecutableHash>,
+    pub pda: Account<'info, MetadataAccount3>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WriteArchive<'info> {
+    pub pda: Account<'info, MetadataAccount3>,
+    /// CHECK: The archive PDA of the current version.
+    /// The address is checked in code and the account is created by the first call.
+    #[account(mut)]
+    pub archive: UncheckedAccount<'info>,
+    // Pays the rent for the archive account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // Accounts for upgrading the canonical MetadataAccount with the buffer.
 #[derive(Accounts)]
 pub struct SetBuffer<'info> {
@@ -481,8 +1546,20 @@ pub struct SetBuffer<'info> {
     pub authority: Signer<'info>,
     /// CHECK: This is the program id of the program you want to upload the IDL for.
     pub program_id: AccountInfo<'info>,
-    /// The program data account containing the upgrade authority
-    pub program_data: Account<'info, ProgramData>,
+    /// The program data account containing the upgrade authority. Only needed for programs owned by the upgradeable loader.
+    pub program_data: Option<Account<'info, ProgramData>>,
+    /// CHECK: Optional archive PDA that receives the version which is about to be overwritten.
+    /// The address is checked in code and the account is created by the instruction.
+    #[account(mut)]
+    pub archive: Option<UncheckedAccount<'info>>,
+    // Pays the rent for the archive account. Only needed when an archive is passed.
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+    // Only needed when the authority is a delegate of the metadata account
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    // Only needed when the authority of the metadata account is a multisig
+    pub multisig: Option<Account<'info, MetadataMultisig>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -525,6 +1602,22 @@ pub struct MetadataAccount3 {
     pub format: Format,
     pub data_source: DataSource,
     pub data_len: u32,
+    // SHA-256 of the stored trailing bytes. Verified by set_buffer.
+    pub data_hash: [u8; 32],
+    // SHA-256 of the payload before compression and encoding, as declared by the uploader.
+    pub content_hash: [u8; 32],
+    // Incremented by every set_buffer. 0 means no data was set yet.
+    pub version: u64,
+    // Set by freeze. A frozen account can never be changed or closed again.
+    pub frozen: bool,
+    // Authority proposed by set_authority. Pubkey::default() means no transfer is pending.
+    pub pending_authority: Pubkey,
+    // Deployment slot of the program when the current version was set. Compare it with the slot in the
+    // ProgramData account to find data that predates the last upgrade. 0 for programs without one.
+    pub program_data_slot: u64,
+    // Digest of the executable the current version was attested against by finish_executable_hash.
+    // All zero when the current version was not attested.
+    pub executable_hash: [u8; 32],
     // trailing data...
 }
 
@@ -539,6 +1632,199 @@ pub struct MetadataBuffer {
     pub format: Format,
     pub data_source: DataSource,
     pub data_len: u32,
+    // SHA-256 of the stored trailing bytes. Verified by set_buffer.
+    pub data_hash: [u8; 32],
+    // SHA-256 of the payload before compression and encoding, as declared by the uploader.
+    pub content_hash: [u8; 32],
+    // trailing data...
+}
+
+// Snapshot of a previous version of a MetadataAccount3.
+// Seeded by [ARCHIVE_SEED, metadata account, version (u64 le)]. Using the metadata account instead of
+// program id and data type keeps the archives of canonical and signer seeded accounts apart.
+#[account]
+pub struct MetadataArchive {
+    pub metadata: Pubkey,
+    pub version: u64,
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub format: Format,
+    pub data_source: DataSource,
+    pub data_len: u32,
+    pub data_hash: [u8; 32],
+    pub content_hash: [u8; 32],
+    // trailing data...
+}
+
+// Allows the delegate to commit buffers to one metadata account.
+// Seeded by [DELEGATE_SEED, metadata account, delegate].
+#[account]
+pub struct MetadataDelegate {
+    pub metadata: Pubkey,
+    pub delegate: Pubkey,
+    // The authority that added the delegate. The record is ignored once the authority changes.
+    pub authority: Pubkey,
+    // Last slot in which the delegate can commit buffers. 0 means no expiry.
+    pub expiry_slot: u64,
+}
+
+// Progress of hashing the executable of a program. Seeded by [EXECUTABLE_HASH_SEED, metadata account].
+// The digest is chained over chunks of EXECUTABLE_HASH_CHUNK_SIZE bytes:
+// digest = sha256(digest || chunk), starting with 32 zero bytes.
+#[account]
+pub struct MetadataExecutableHash {
+    // Paid the rent and gets it back when the hash is finished or closed
+    pub payer: Pubkey,
+    // Version of the metadata account that is attested
+    pub version: u64,
+    // Deployment slot of the program that is hashed
+    pub program_data_slot: u64,
+    pub executable_len: u32,
+    // Number of bytes hashed so far
+    pub offset: u32,
+    pub digest: [u8; 32],
+}
+
+impl MetadataExecutableHash {
+    // The hash is only valid as long as neither the program nor the metadata changed since it was started
+    pub fn check_current(
+        &self,
+        metadata: &Account<MetadataAccount3>,
+        program_id: &AccountInfo,
+        program_data: Option<&Account<ProgramData>>,
+    ) -> Result<()> {
+        if self.version != metadata.version
+            || self.program_data_slot != get_program_deploy_slot(program_id, program_data)?
+        {
+            return err!(MyError::ExecutableHashOutdated);
+        }
+        Ok(())
+    }
+}
+
+// Threshold authority. Created with a fresh keypair like a buffer.
+#[account]
+pub struct MetadataMultisig {
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+// Events emitted for every change of a metadata account, so indexers can follow
+// the logs of the program instead of polling the accounts.
+#[event]
+pub struct MetadataInitialized {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub signer_seeded: bool,
+}
+
+#[event]
+pub struct MetadataSet {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub version: u64,
+    pub data_hash: [u8; 32],
+    pub content_hash: [u8; 32],
+    // Whether the previous version was written to an archive account
+    pub archived: bool,
+    pub program_data_slot: u64,
+}
+
+#[event]
+pub struct ExecutableHashed {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub version: u64,
+    pub program_data_slot: u64,
+    pub executable_hash: [u8; 32],
+}
+
+// Emitted by set_authority, accept_authority and cancel_authority
+#[event]
+pub struct AuthorityChanged {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    // Pubkey::default() when no transfer is pending
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct MetadataResized {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub account_size: u32,
+}
+
+#[event]
+pub struct MetadataFrozen {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub data_len: u32,
+    pub version: u64,
+    pub content_hash: [u8; 32],
+}
+
+#[event]
+pub struct MetadataMigrated {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub version: u64,
+    pub content_hash: [u8; 32],
+}
+
+#[event]
+pub struct DelegateAdded {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub delegate: Pubkey,
+    pub expiry_slot: u64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub delegate: Pubkey,
+}
+
+// The program id is Pubkey::default() for MetadataAccount, which does not store it
+#[event]
+pub struct MetadataClosed {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+}
+
+// Header of MetadataAccount3 accounts written before the hashes, version and authority transfer fields
+// were added. It has the discriminator of MetadataAccount3, see is_legacy_metadata_account_3.
+#[derive(AnchorDeserialize)]
+pub struct LegacyMetadataAccount3 {
+    pub authority: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: [u8; 16],
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub format: Format,
+    pub data_source: DataSource,
+    pub data_len: u32,
     // trailing data...
 }
 
@@ -565,6 +1851,17 @@ pub struct MetadataAccount {
 pub const DATA_TYPE_IDL: &str = "idl";
 #[constant]
 pub const DATA_TYPE_METADATA: &str = "metadata";
+#[constant]
+pub const ARCHIVE_SEED: &[u8] = b"archive";
+#[constant]
+pub const DELEGATE_SEED: &[u8] = b"delegate";
+#[constant]
+pub const PROGRAM_SIGNER_SEED: &[u8] = b"metadata_authority";
+#[constant]
+pub const EXECUTABLE_HASH_SEED: &[u8] = b"executable_hash";
+// 64 KiB cost about 33k compute units to hash
+#[constant]
+pub const EXECUTABLE_HASH_CHUNK_SIZE: u32 = 65536;
 // But users can also use their own types:
 // "game.stats"
 // "dao.config.v1"
@@ -572,10 +1869,20 @@ pub const DATA_TYPE_METADATA: &str = "metadata";
 // "social.profile"
 // "audited.by.json"
 
+// Sizes of the data of a DataSource::Account, see check_account_pointer
+pub const ACCOUNT_POINTER_SIZE: usize = 32;
+pub const ACCOUNT_POINTER_WITH_RANGE_SIZE: usize = 40;
+
+// Layout of the loader-v4 program header
+pub const LOADER_V4_STATE_SIZE: usize = 48;
+pub const LOADER_V4_STATUS_FINALIZED: u64 = 2;
+
 // Size constants for composability
 pub const PUBKEY_LENGTH: u64 = 32;
 pub const PROGRAM_ID_LENGTH: u64 = 32;
 pub const U32_LENGTH: u64 = 4;
+pub const U64_LENGTH: u64 = 8;
+pub const HASH_LENGTH: u64 = 32;
 
 #[constant]
 pub const DATA_TYPE_LENGTH: u64 = 16;
@@ -590,6 +1897,94 @@ pub const METADATA_ACCOUNT_SIZE: u64 =
     1 +                            // format enum
     1 +                            // data_source enum (1 byte discriminator)
     U32_LENGTH +                   // data_len
+    HASH_LENGTH +                  // data_hash
+    HASH_LENGTH +                  // content_hash
+    U64_LENGTH +                   // version
+    1 +                            // frozen
+    PUBKEY_LENGTH +                 // pending_authority
+    U64_LENGTH +                   // program_data_slot
+    HASH_LENGTH +                  // executable_hash
+    8;                             // discriminator
+
+// Size of the MetadataAccount header. Only used to migrate legacy accounts.
+pub const LEGACY_METADATA_ACCOUNT_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // authority
+    DATA_TYPE_LENGTH +              // data_type
+    U32_LENGTH +                   // data_len
+    8;                             // discriminator
+
+// Size of the MetadataAccount2 header. Only used to migrate legacy accounts.
+pub const LEGACY_METADATA_ACCOUNT_2_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // authority
+    DATA_TYPE_LENGTH +              // data_type
+    PROGRAM_ID_LENGTH +             // program_id
+    U32_LENGTH +                   // data_len
+    8;                             // discriminator
+
+// Size of the old MetadataAccount3 header. Only used to migrate legacy accounts.
+pub const LEGACY_METADATA_ACCOUNT_3_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // authority
+    PROGRAM_ID_LENGTH +             // program_id
+    DATA_TYPE_LENGTH +              // data_type
+    1 +                            // encoding enum
+    1 +                            // compression enum
+    1 +                            // format enum
+    1 +                            // data_source enum
+    U32_LENGTH +                   // data_len
+    8;                             // discriminator
+
+#[constant]
+pub const METADATA_BUFFER_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // authority
+    DATA_TYPE_LENGTH +              // data_type
+    PROGRAM_ID_LENGTH +             // program_id
+    1 +                            // encoding enum
+    1 +                            // compression enum
+    1 +                            // format enum
+    1 +                            // data_source enum
+    U32_LENGTH +                   // data_len
+    HASH_LENGTH +                  // data_hash
+    HASH_LENGTH +                  // content_hash
+    8;                             // discriminator
+
+#[constant]
+pub const METADATA_ARCHIVE_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // metadata
+    U64_LENGTH +                   // version
+    1 +                            // encoding enum
+    1 +                            // compression enum
+    1 +                            // format enum
+    1 +                            // data_source enum
+    U32_LENGTH +                   // data_len
+    HASH_LENGTH +                  // data_hash
+    HASH_LENGTH +                  // content_hash
+    8;                             // discriminator
+
+#[constant]
+pub const METADATA_DELEGATE_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // metadata
+    PUBKEY_LENGTH +                 // delegate
+    PUBKEY_LENGTH +                 // authority
+    U64_LENGTH +                   // expiry_slot
+    8;                             // discriminator
+
+#[constant]
+pub const METADATA_EXECUTABLE_HASH_SIZE: u64 = 
+    PUBKEY_LENGTH +                 // payer
+    U64_LENGTH +                   // version
+    U64_LENGTH +                   // program_data_slot
+    U32_LENGTH +                   // executable_len
+    U32_LENGTH +                   // offset
+    HASH_LENGTH +                  // digest
+    8;                             // discriminator
+
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+#[constant]
+pub const METADATA_MULTISIG_SIZE: u64 = 
+    1 +                            // threshold
+    U32_LENGTH +                   // signers length
+    PUBKEY_LENGTH * MAX_MULTISIG_SIGNERS as u64 + // signers
     8;                             // discriminator
 
 impl MetadataAccount3 {
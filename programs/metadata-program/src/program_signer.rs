@@ -0,0 +1,115 @@
+// Helpers for programs that publish their own metadata through CPI.
+//
+// The calling program signs with its PDA [PROGRAM_SIGNER_SEED], which is the authority of the metadata
+// accounts it initializes with initialize_with_program_signer. The buffer account has to be allocated
+// with the metadata program as owner before create_buffer is called, like the CLI does with a keypair.
+// The PDA pays the rent when the metadata account grows, so it has to hold lamports.
+//
+//     let signer = ProgramSigner::new(ctx.accounts.metadata_program.to_account_info(), ctx.program_id);
+//     signer.create_buffer(accounts, "config", data_format, data_hash, data_hash)?;
+//     signer.write(write_accounts, 0, data)?;
+//     signer.resize(resize_accounts, METADATA_ACCOUNT_SIZE as u32 + data_len)?;
+//     signer.set(set_accounts)?;
+//     signer.close_buffer(close_accounts)?;
+use anchor_lang::prelude::*;
+
+use crate::cpi::accounts::{CloseBuffer, CreateBuffer, InitializeWithProgramSigner, Resize, SetBuffer, WriteBuffer};
+use crate::{get_program_signer_address, Compression, DataSource, Encoding, Format, PROGRAM_SIGNER_SEED};
+
+// How the published data is stored
+#[derive(Clone, Debug)]
+pub struct DataFormat {
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub format: Format,
+    pub data_source: DataSource,
+}
+
+pub struct ProgramSigner<'info> {
+    pub metadata_program: AccountInfo<'info>,
+    pub bump: u8,
+}
+
+impl<'info> ProgramSigner<'info> {
+    // program_id is the id of the calling program, not of the metadata program
+    pub fn new(metadata_program: AccountInfo<'info>, program_id: &Pubkey) -> Self {
+        let (_, bump) = get_program_signer_address(program_id);
+        Self { metadata_program, bump }
+    }
+
+    pub fn initialize(
+        &self,
+        accounts: InitializeWithProgramSigner<'info>,
+        data_type: &str,
+        data_format: DataFormat,
+    ) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::initialize_with_program_signer(
+            CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds),
+            data_type.to_string(),
+            data_format.encoding,
+            data_format.compression,
+            data_format.format,
+            data_format.data_source,
+        )
+    }
+
+    pub fn create_buffer(
+        &self,
+        accounts: CreateBuffer<'info>,
+        data_type: &str,
+        data_format: DataFormat,
+        data_hash: [u8; 32],
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::create_buffer(
+            CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds),
+            data_type.to_string(),
+            data_format.encoding,
+            data_format.compression,
+            data_format.format,
+            data_format.data_source,
+            data_hash,
+            content_hash,
+        )
+    }
+
+    // Writes data at offset of the buffer, see write_buffer_at
+    pub fn write(&self, accounts: WriteBuffer<'info>, offset: u32, data: Vec<u8>) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::write_buffer_at(
+            CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds),
+            offset,
+            None,
+            data,
+        )
+    }
+
+    // Resizes the canonical metadata account to len bytes, see resize. Grow it to the header plus the
+    // data length before set, and shrink it only after set.
+    pub fn resize(&self, accounts: Resize<'info>, len: u32) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::resize(
+            CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds),
+            len,
+            None,
+        )
+    }
+
+    pub fn set(&self, accounts: SetBuffer<'info>) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::set_buffer(CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds))
+    }
+
+    pub fn close_buffer(&self, accounts: CloseBuffer<'info>) -> Result<()> {
+        let bump = [self.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PROGRAM_SIGNER_SEED, &bump]];
+        crate::cpi::close_buffer(CpiContext::new_with_signer(self.metadata_program.clone(), accounts, signer_seeds))
+    }
+}
@@ -0,0 +1,289 @@
+// Helpers for programs that read the metadata of another program on-chain, for example to check a
+// declared version before a CPI. Nothing is deserialized or copied: the header fields are read from
+// the account data in place and the trailing data is returned as a borrowed slice.
+//
+//     let data = read_metadata(&ctx.accounts.metadata, &target_program, DATA_TYPE_METADATA)?;
+//     let metadata = MetadataHeader::parse(&data)?;
+//     let version = metadata.version();
+//     let json = metadata.data()?;
+use std::cell::Ref;
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::metadata_program::MyError;
+use crate::{
+    Compression, DataSource, Encoding, Format, MetadataAccount3, ACCOUNT_POINTER_SIZE, ACCOUNT_POINTER_WITH_RANGE_SIZE,
+    METADATA_ACCOUNT_SIZE,
+};
+
+// Offsets of the MetadataAccount3 header fields, including the 8 byte discriminator
+const AUTHORITY_OFFSET: usize = 8;
+const PROGRAM_ID_OFFSET: usize = 40;
+const DATA_TYPE_OFFSET: usize = 72;
+const ENCODING_OFFSET: usize = 88;
+const COMPRESSION_OFFSET: usize = 89;
+const FORMAT_OFFSET: usize = 90;
+const DATA_SOURCE_OFFSET: usize = 91;
+const DATA_LEN_OFFSET: usize = 92;
+const DATA_HASH_OFFSET: usize = 96;
+const CONTENT_HASH_OFFSET: usize = 128;
+const VERSION_OFFSET: usize = 160;
+const FROZEN_OFFSET: usize = 168;
+const PENDING_AUTHORITY_OFFSET: usize = 169;
+const PROGRAM_DATA_SLOT_OFFSET: usize = 201;
+const EXECUTABLE_HASH_OFFSET: usize = 209;
+
+// An account pointer: the account the data points to and, if set, the offset and length of the data in it
+pub type AccountPointer = (Pubkey, Option<(u32, u32)>);
+
+// Address of the canonical metadata account of program_id for data_type, e.g. "idl" or "metadata"
+pub fn find_metadata_address(program_id: &Pubkey, data_type: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[program_id.as_ref(), data_type.as_bytes()], &crate::ID)
+}
+
+// Checks that account is the canonical metadata account of program_id for data_type and owned by the
+// metadata program, then borrows its data for MetadataHeader::parse.
+pub fn read_metadata<'a>(account: &'a AccountInfo, program_id: &Pubkey, data_type: &str) -> Result<Ref<'a, [u8]>> {
+    if account.key() != find_metadata_address(program_id, data_type).0 {
+        return err!(MyError::InvalidMetadataAddress);
+    }
+    if *account.owner != crate::ID {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Ref::map(data, |data| &data[..]))
+}
+
+// Zero-copy view of a MetadataAccount3
+pub struct MetadataHeader<'a> {
+    account_data: &'a [u8],
+}
+
+impl<'a> MetadataHeader<'a> {
+    // Validates the discriminator and the size of the header. The owner has to be checked by the caller,
+    // read_metadata does that.
+    pub fn parse(account_data: &'a [u8]) -> Result<Self> {
+        if account_data.len() < 8 || account_data[..8] != MetadataAccount3::DISCRIMINATOR {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        if account_data.len() < METADATA_ACCOUNT_SIZE as usize {
+            return Err(ErrorCode::AccountDidNotDeserialize.into());
+        }
+        Ok(Self { account_data })
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.pubkey_at(AUTHORITY_OFFSET)
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.pubkey_at(PROGRAM_ID_OFFSET)
+    }
+
+    // The data type without its null padding
+    pub fn data_type(&self) -> &'a [u8] {
+        let data_type = &self.account_data[DATA_TYPE_OFFSET..ENCODING_OFFSET];
+        let len = data_type.iter().position(|&x| x == 0).unwrap_or(data_type.len());
+        &data_type[..len]
+    }
+
+    pub fn encoding(&self) -> Result<Encoding> {
+        Ok(Encoding::try_from_slice(&self.account_data[ENCODING_OFFSET..ENCODING_OFFSET + 1])?)
+    }
+
+    pub fn compression(&self) -> Result<Compression> {
+        Ok(Compression::try_from_slice(&self.account_data[COMPRESSION_OFFSET..COMPRESSION_OFFSET + 1])?)
+    }
+
+    pub fn format(&self) -> Result<Format> {
+        Ok(Format::try_from_slice(&self.account_data[FORMAT_OFFSET..FORMAT_OFFSET + 1])?)
+    }
+
+    pub fn data_source(&self) -> Result<DataSource> {
+        Ok(DataSource::try_from_slice(&self.account_data[DATA_SOURCE_OFFSET..DATA_SOURCE_OFFSET + 1])?)
+    }
+
+    pub fn data_len(&self) -> u32 {
+        u32::from_le_bytes(self.account_data[DATA_LEN_OFFSET..DATA_HASH_OFFSET].try_into().unwrap())
+    }
+
+    pub fn data_hash(&self) -> &'a [u8] {
+        &self.account_data[DATA_HASH_OFFSET..CONTENT_HASH_OFFSET]
+    }
+
+    pub fn content_hash(&self) -> &'a [u8] {
+        &self.account_data[CONTENT_HASH_OFFSET..VERSION_OFFSET]
+    }
+
+    pub fn version(&self) -> u64 {
+        u64::from_le_bytes(self.account_data[VERSION_OFFSET..FROZEN_OFFSET].try_into().unwrap())
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.account_data[FROZEN_OFFSET] != 0
+    }
+
+    // None when no authority transfer is pending
+    pub fn pending_authority(&self) -> Option<Pubkey> {
+        Some(self.pubkey_at(PENDING_AUTHORITY_OFFSET)).filter(|pending_authority| *pending_authority != Pubkey::default())
+    }
+
+    // Deployment slot of the program when the current version was set, 0 if unknown
+    pub fn program_data_slot(&self) -> u64 {
+        u64::from_le_bytes(
+            self.account_data[PROGRAM_DATA_SLOT_OFFSET..PROGRAM_DATA_SLOT_OFFSET + 8].try_into().unwrap(),
+        )
+    }
+
+    // Digest of the executable the current version was attested against, None if it was not attested
+    pub fn executable_hash(&self) -> Option<&'a [u8]> {
+        Some(&self.account_data[EXECUTABLE_HASH_OFFSET..EXECUTABLE_HASH_OFFSET + 32])
+            .filter(|executable_hash| executable_hash.iter().any(|&byte| byte != 0))
+    }
+
+    // The stored trailing bytes as they are, still encoded and compressed
+    pub fn raw_data(&self) -> Result<&'a [u8]> {
+        let start = METADATA_ACCOUNT_SIZE as usize;
+        let end = start
+            .checked_add(self.data_len() as usize)
+            .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+        self.account_data
+            .get(start..end)
+            .ok_or_else(|| ErrorCode::AccountDidNotDeserialize.into())
+    }
+
+    // None unless the data source is DataSource::Account
+    pub fn account_pointer(&self) -> Result<Option<AccountPointer>> {
+        if self.data_source()? != DataSource::Account {
+            return Ok(None);
+        }
+        let pointer = self.raw_data()?;
+        // Initialize can declare the data source before any pointer was set
+        if pointer.len() != ACCOUNT_POINTER_SIZE && pointer.len() != ACCOUNT_POINTER_WITH_RANGE_SIZE {
+            return err!(MyError::InvalidAccountPointer);
+        }
+        let address = Pubkey::try_from(&pointer[..ACCOUNT_POINTER_SIZE]).unwrap();
+        let range = (pointer.len() == ACCOUNT_POINTER_WITH_RANGE_SIZE).then(|| {
+            (
+                u32::from_le_bytes(pointer[32..36].try_into().unwrap()),
+                u32::from_le_bytes(pointer[36..40].try_into().unwrap()),
+            )
+        });
+        Ok(Some((address, range)))
+    }
+
+    // The trailing data of uncompressed metadata. Compressed data can not be inflated on-chain.
+    pub fn data(&self) -> Result<&'a [u8]> {
+        if self.compression()? != Compression::None {
+            return err!(MyError::DataCompressed);
+        }
+        self.raw_data()
+    }
+
+    fn pubkey_at(&self, offset: usize) -> Pubkey {
+        Pubkey::try_from(&self.account_data[offset..offset + 32]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_account_data(data_source: DataSource, compression: Compression, data: &[u8]) -> Vec<u8> {
+        let mut data_type = [0u8; 16];
+        data_type[..4].copy_from_slice(b"test");
+        let account = MetadataAccount3 {
+            authority: Pubkey::new_from_array([1; 32]),
+            program_id: Pubkey::new_from_array([2; 32]),
+            data_type,
+            encoding: Encoding::Base64,
+            compression,
+            format: Format::Toml,
+            data_source,
+            data_len: data.len() as u32,
+            data_hash: [3; 32],
+            content_hash: [4; 32],
+            version: 0x0102_0304_0506_0708,
+            frozen: true,
+            pending_authority: Pubkey::new_from_array([5; 32]),
+            program_data_slot: 0x1112_1314_1516_1718,
+            executable_hash: [6; 32],
+        };
+        let mut account_data = vec![];
+        account.try_serialize(&mut account_data).unwrap();
+        assert_eq!(account_data.len(), METADATA_ACCOUNT_SIZE as usize);
+        account_data.extend_from_slice(data);
+        account_data
+    }
+
+    #[test]
+    fn header_fields_are_read_at_their_offsets() {
+        let account_data = metadata_account_data(DataSource::Direct, Compression::Gzip, b"trailing");
+        let header = MetadataHeader::parse(&account_data).unwrap();
+
+        assert_eq!(header.authority(), Pubkey::new_from_array([1; 32]));
+        assert_eq!(header.program_id(), Pubkey::new_from_array([2; 32]));
+        assert_eq!(header.data_type(), b"test");
+        assert_eq!(header.encoding().unwrap(), Encoding::Base64);
+        assert_eq!(header.compression().unwrap(), Compression::Gzip);
+        assert_eq!(header.format().unwrap(), Format::Toml);
+        assert_eq!(header.data_source().unwrap(), DataSource::Direct);
+        assert_eq!(header.data_len(), 8);
+        assert_eq!(header.data_hash(), &[3; 32]);
+        assert_eq!(header.content_hash(), &[4; 32]);
+        assert_eq!(header.version(), 0x0102_0304_0506_0708);
+        assert!(header.frozen());
+        assert_eq!(header.pending_authority(), Some(Pubkey::new_from_array([5; 32])));
+        assert_eq!(header.program_data_slot(), 0x1112_1314_1516_1718);
+        assert_eq!(header.executable_hash(), Some(&[6; 32][..]));
+        assert_eq!(header.raw_data().unwrap(), b"trailing");
+        assert!(header.data().is_err(), "Compressed data can not be read on-chain");
+        assert_eq!(header.account_pointer().unwrap(), None);
+    }
+
+    #[test]
+    fn parse_rejects_other_accounts() {
+        let account_data = metadata_account_data(DataSource::Direct, Compression::None, b"");
+        assert!(MetadataHeader::parse(&account_data[..METADATA_ACCOUNT_SIZE as usize - 1]).is_err());
+
+        let mut wrong_discriminator = account_data.clone();
+        wrong_discriminator[0] ^= 1;
+        assert!(MetadataHeader::parse(&wrong_discriminator).is_err());
+    }
+
+    #[test]
+    fn data_is_bounded_by_data_len() {
+        let mut account_data = metadata_account_data(DataSource::Direct, Compression::None, b"{}");
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert_eq!(header.data().unwrap(), b"{}");
+
+        // An account larger than its data, e.g. before it was shrunk
+        account_data.extend_from_slice(&[0; 8]);
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert_eq!(header.data().unwrap(), b"{}");
+
+        account_data.truncate(METADATA_ACCOUNT_SIZE as usize + 1);
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert!(header.raw_data().is_err());
+    }
+
+    #[test]
+    fn account_pointers_are_decoded() {
+        let address = Pubkey::new_from_array([7; 32]);
+        let account_data = metadata_account_data(DataSource::Account, Compression::None, address.as_ref());
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert_eq!(header.account_pointer().unwrap(), Some((address, None)));
+
+        let mut pointer = address.to_bytes().to_vec();
+        pointer.extend_from_slice(&16u32.to_le_bytes());
+        pointer.extend_from_slice(&64u32.to_le_bytes());
+        let account_data = metadata_account_data(DataSource::Account, Compression::None, &pointer);
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert_eq!(header.account_pointer().unwrap(), Some((address, Some((16, 64)))));
+
+        let account_data = metadata_account_data(DataSource::Account, Compression::None, &pointer[..33]);
+        let header = MetadataHeader::parse(&account_data).unwrap();
+        assert!(header.account_pointer().is_err());
+    }
+}
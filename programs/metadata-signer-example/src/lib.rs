@@ -0,0 +1,149 @@
+// Example of a program that publishes its own metadata through CPI with the program signer helpers of
+// the metadata program. It is deployed by the tests to run the whole flow.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use metadata_program::cpi::accounts::{
+    CloseBuffer, CreateBuffer, InitializeWithProgramSigner, Resize, SetBuffer, WriteBuffer,
+};
+use metadata_program::program::MetadataProgram;
+use metadata_program::program_signer::{DataFormat, ProgramSigner};
+use metadata_program::{Compression, DataSource, Encoding, Format, METADATA_ACCOUNT_SIZE, PROGRAM_SIGNER_SEED};
+
+declare_id!("2gwN4F2FUfTki9erS8nVZrBfefhh62oaHY9eXXabN2uk");
+
+// The data type of the metadata account the program publishes
+pub const DATA_TYPE: &str = "config";
+
+#[program]
+pub mod metadata_signer_example {
+    use super::*;
+
+    // Creates the canonical "config" metadata account of this program with the program signer as authority
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let signer = ProgramSigner::new(ctx.accounts.metadata_program.to_account_info(), ctx.program_id);
+        signer.initialize(
+            InitializeWithProgramSigner {
+                pda: ctx.accounts.metadata.to_account_info(),
+                program_signer: ctx.accounts.program_signer.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                program_id: ctx.accounts.program.to_account_info(),
+            },
+            DATA_TYPE,
+            data_format(),
+        )
+    }
+
+    // Publishes data as the next version of the metadata account. The client allocates the buffer
+    // account with the metadata program as owner in the same transaction.
+    pub fn publish(ctx: Context<Publish>, data: Vec<u8>) -> Result<()> {
+        let signer = ProgramSigner::new(ctx.accounts.metadata_program.to_account_info(), ctx.program_id);
+        let data_hash = hash(&data).to_bytes();
+        let data_len = u32::try_from(data.len()).unwrap();
+
+        signer.create_buffer(
+            CreateBuffer {
+                buffer: ctx.accounts.buffer.to_account_info(),
+                authority: ctx.accounts.program_signer.to_account_info(),
+                program_id: ctx.accounts.program.to_account_info(),
+            },
+            DATA_TYPE,
+            data_format(),
+            data_hash,
+            data_hash,
+        )?;
+        signer.write(
+            WriteBuffer {
+                buffer: ctx.accounts.buffer.to_account_info(),
+                signer: ctx.accounts.program_signer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            0,
+            data,
+        )?;
+
+        // Grow before set_buffer, shrink after it, because the account can not be smaller than its current data
+        let account_size = u32::try_from(ctx.accounts.metadata.data_len()).unwrap();
+        let target_size = METADATA_ACCOUNT_SIZE as u32 + data_len;
+        if target_size > account_size {
+            signer.resize(resize_accounts(ctx.accounts), target_size)?;
+        }
+        signer.set(SetBuffer {
+            buffer: ctx.accounts.buffer.to_account_info(),
+            pda: ctx.accounts.metadata.to_account_info(),
+            authority: ctx.accounts.program_signer.to_account_info(),
+            program_id: ctx.accounts.program.to_account_info(),
+            program_data: Some(ctx.accounts.program_data.to_account_info()),
+            archive: None,
+            payer: None,
+            system_program: None,
+            delegate_record: None,
+            multisig: None,
+        })?;
+        if target_size < account_size {
+            signer.resize(resize_accounts(ctx.accounts), target_size)?;
+        }
+
+        signer.close_buffer(CloseBuffer {
+            buffer: ctx.accounts.buffer.to_account_info(),
+            authority: ctx.accounts.program_signer.to_account_info(),
+        })
+    }
+}
+
+fn data_format() -> DataFormat {
+    DataFormat {
+        encoding: Encoding::Utf8,
+        compression: Compression::None,
+        format: Format::Json,
+        data_source: DataSource::Direct,
+    }
+}
+
+fn resize_accounts<'info>(accounts: &Publish<'info>) -> Resize<'info> {
+    Resize {
+        pda: accounts.metadata.to_account_info(),
+        signer: accounts.program_signer.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        program_id: accounts.program.to_account_info(),
+        delegate_record: None,
+        multisig: None,
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// CHECK: Created and checked by the metadata program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Only signs, the metadata program checks the address
+    #[account(seeds = [PROGRAM_SIGNER_SEED], bump)]
+    pub program_signer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: This program
+    #[account(address = crate::ID)]
+    pub program: UncheckedAccount<'info>,
+    pub metadata_program: Program<'info, MetadataProgram>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Publish<'info> {
+    /// CHECK: Allocated by the client with the metadata program as owner, initialized by create_buffer
+    #[account(mut)]
+    pub buffer: UncheckedAccount<'info>,
+    /// CHECK: Checked by the metadata program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Signs for the metadata account. Holds the lamports for the rent when the metadata account grows.
+    #[account(mut, seeds = [PROGRAM_SIGNER_SEED], bump)]
+    pub program_signer: UncheckedAccount<'info>,
+    /// CHECK: This program
+    #[account(address = crate::ID)]
+    pub program: UncheckedAccount<'info>,
+    /// CHECK: The program data account of this program, checked by the metadata program
+    pub program_data: UncheckedAccount<'info>,
+    pub metadata_program: Program<'info, MetadataProgram>,
+    pub system_program: Program<'info, System>,
+}
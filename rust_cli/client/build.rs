@@ -0,0 +1,413 @@
+//! Fails the build when the generated client in `src/codama_sdk` no longer matches the
+//! metadata program in `programs/metadata-program`.
+//!
+//! The program source is parsed line by line, which is enough for the way lib.rs is written,
+//! and compared with the generated instructions, accounts, types and errors by name, field
+//! order and type. Regenerate the client with `codama/generateCodama.ts` when this fails.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+type Fields = Vec<(String, String)>;
+
+#[derive(Default)]
+struct Definitions {
+    // Instruction name and arguments
+    instructions: Vec<(String, Fields)>,
+    // Accounts of every instruction and whether they are writable, signer or optional
+    instruction_accounts: Vec<(String, Fields)>,
+    accounts: Vec<(String, Fields)>,
+    types: Vec<(String, Vec<String>)>,
+    // Error name, code and message
+    errors: Vec<(String, (u32, String))>,
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let program_path = manifest_dir.join("../../programs/metadata-program/src/lib.rs");
+    let sdk_path = manifest_dir.join("src/codama_sdk");
+    println!("cargo:rerun-if-changed={}", program_path.display());
+    println!("cargo:rerun-if-changed={}", sdk_path.display());
+
+    // The program is not packaged with the crate, so the check only runs inside the repository
+    let Ok(program_source) = fs::read_to_string(&program_path) else {
+        return;
+    };
+    let program = parse_program(&program_source);
+    let client = parse_client(&sdk_path);
+
+    let mut mismatches = Vec::new();
+    compare(
+        "instruction",
+        &program.instructions,
+        &client.instructions,
+        &mut mismatches,
+    );
+    compare(
+        "accounts of instruction",
+        &program.instruction_accounts,
+        &client.instruction_accounts,
+        &mut mismatches,
+    );
+    compare("account", &program.accounts, &client.accounts, &mut mismatches);
+    compare("type", &program.types, &client.types, &mut mismatches);
+    compare("error", &program.errors, &client.errors, &mut mismatches);
+
+    if !mismatches.is_empty() {
+        panic!(
+            "src/codama_sdk does not match programs/metadata-program, regenerate it with codama/generateCodama.ts:\n  {}",
+            mismatches.join("\n  ")
+        );
+    }
+}
+
+// Compares items by name, ignoring case and underscores since codama renames
+// `close_metadata_account_3` to `CloseMetadataAccount3`.
+fn compare<T: PartialEq + std::fmt::Debug>(
+    kind: &str,
+    program: &[(String, T)],
+    client: &[(String, T)],
+    mismatches: &mut Vec<String>,
+) {
+    let find = |items: &[(String, T)], name: &str| {
+        items
+            .iter()
+            .position(|(other, _)| normalize(other) == normalize(name))
+    };
+    for (name, definition) in program {
+        match find(client, name) {
+            None => mismatches.push(format!("{} {} is missing from the client", kind, name)),
+            Some(index) if client[index].1 != *definition => mismatches.push(format!(
+                "{} {} differs:\n    program: {:?}\n    client:  {:?}",
+                kind, name, definition, client[index].1
+            )),
+            Some(_) => {}
+        }
+    }
+    for (name, _) in client {
+        if find(program, name).is_none() {
+            mismatches.push(format!("{} {} does not exist in the program", kind, name));
+        }
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.replace('_', "").to_lowercase()
+}
+
+fn parse_program(source: &str) -> Definitions {
+    let lines: Vec<String> = source.lines().map(strip_comment).collect();
+    let mut definitions = Definitions::default();
+    // Instruction handler name and the name of its accounts struct
+    let mut contexts = Vec::new();
+    let mut accounts_structs = Vec::new();
+    let mut attribute = "";
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index].trim();
+        if line.starts_with("#[derive(Accounts)]") {
+            attribute = "accounts";
+        } else if line == "#[account]" {
+            attribute = "account";
+        } else if line.starts_with("#[error_code]") {
+            attribute = "error";
+        } else if line.starts_with("#[derive(") && line.contains("AnchorSerialize") {
+            attribute = "type";
+        } else if line.starts_with("#[event]") {
+            attribute = "";
+        } else if let Some(rest) = line.strip_prefix("pub fn ") {
+            // Instruction handlers are the only functions taking a `Context`
+            let signature = collect_until(&lines, &mut index, ')');
+            if let Some((name, context, args)) = parse_handler(rest, &signature) {
+                contexts.push((name.clone(), context));
+                definitions.instructions.push((name, args));
+            }
+        } else if line.starts_with("pub struct ") || line.starts_with("pub enum ") {
+            let name = item_name(line);
+            let body = collect_body(&lines, &mut index);
+            match attribute {
+                "accounts" => accounts_structs.push((name, parse_program_account_metas(&body))),
+                "account" => definitions.accounts.push((name, parse_fields(&body))),
+                "type" => definitions.types.push((name, parse_variants(&body))),
+                "error" => definitions.errors = parse_program_errors(&body),
+                _ => {}
+            }
+            attribute = "";
+        }
+        index += 1;
+    }
+
+    for (instruction, context) in contexts {
+        if let Some((_, accounts)) = accounts_structs.iter().find(|(name, _)| *name == context) {
+            definitions.instruction_accounts.push((instruction, accounts.clone()));
+        }
+    }
+    definitions
+}
+
+fn parse_client(sdk_path: &Path) -> Definitions {
+    let mut definitions = Definitions::default();
+    for source in read_generated_files(&sdk_path.join("instructions")) {
+        let lines: Vec<String> = source.lines().map(String::from).collect();
+        let Some(start) = lines.iter().position(|line| line.starts_with("pub struct ")) else {
+            continue;
+        };
+        let name = item_name(&lines[start]);
+        // The docs of the CPI builder list the accounts with their flags, without marking
+        // accounts that have a default as optional like the docs of the builder do
+        let cpi_docs = format!("/// Instruction builder for `{}` via CPI.", name);
+        let accounts = lines
+            .iter()
+            .skip_while(|line| line.trim() != cpi_docs)
+            .filter_map(|line| parse_client_account_meta(line))
+            .collect();
+        let args_struct = format!("pub struct {}InstructionArgs {{", name);
+        let args = match lines.iter().position(|line| line.trim() == args_struct) {
+            Some(mut index) => parse_fields(&collect_body(&lines, &mut index)),
+            None => Vec::new(),
+        };
+        definitions.instructions.push((name.clone(), args));
+        definitions.instruction_accounts.push((name, accounts));
+    }
+    for source in read_generated_files(&sdk_path.join("accounts")) {
+        let lines: Vec<String> = source.lines().map(String::from).collect();
+        if let Some(mut index) = lines.iter().position(|line| line.starts_with("pub struct ")) {
+            let name = item_name(&lines[index]);
+            let fields = parse_fields(&collect_body(&lines, &mut index))
+                .into_iter()
+                .filter(|(name, _)| name != "discriminator")
+                .collect();
+            definitions.accounts.push((name, fields));
+        }
+    }
+    for source in read_generated_files(&sdk_path.join("types")) {
+        let lines: Vec<String> = source.lines().map(String::from).collect();
+        if let Some(mut index) = lines.iter().position(|line| line.starts_with("pub enum ")) {
+            let name = item_name(&lines[index]);
+            definitions
+                .types
+                .push((name, parse_variants(&collect_body(&lines, &mut index))));
+        }
+    }
+    for source in read_generated_files(&sdk_path.join("errors")) {
+        let mut message = String::new();
+        for line in source.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("#[error(\"") {
+                message = rest.trim_end_matches("\")]").to_string();
+            } else if let Some((name, code)) = line.trim_end_matches(',').split_once(" = 0x") {
+                let code = u32::from_str_radix(code, 16).unwrap_or_default();
+                definitions
+                    .errors
+                    .push((name.to_string(), (code, std::mem::take(&mut message))));
+            }
+        }
+    }
+    definitions
+}
+
+// Contents of the generated files of a directory, without its mod.rs
+fn read_generated_files(dir: &Path) -> Vec<String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().is_some_and(|name| name != "mod.rs"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+// `pub fn name(` and the full parameter list, returns the name, the accounts struct
+// of the `Context` and the remaining parameters
+fn parse_handler(rest: &str, signature: &str) -> Option<(String, String, Fields)> {
+    let name = rest.split('(').next()?.trim().to_string();
+    let signature = signature.split("->").next()?;
+    let params = &signature[signature.find('(')? + 1..signature.rfind(')')?];
+    let mut params = split_top_level(params).into_iter();
+    let context_param = params.next()?;
+    let (_, context) = context_param.split_once(':')?;
+    let context = context.trim().strip_prefix("Context<")?;
+    let context = context.split('<').next()?.trim_end_matches('>').to_string();
+    let args = params
+        .filter_map(|param| {
+            let (name, ty) = param.split_once(':')?;
+            Some((name.trim().trim_start_matches('_').to_string(), normalize_type(ty)))
+        })
+        .collect();
+    Some((name, context, args))
+}
+
+fn parse_fields(body: &[String]) -> Fields {
+    body.iter()
+        .filter_map(|line| {
+            let (name, ty) = line.trim().strip_prefix("pub ")?.split_once(':')?;
+            Some((name.trim().to_string(), normalize_type(ty.trim_end().trim_end_matches(','))))
+        })
+        .collect()
+}
+
+fn parse_variants(body: &[String]) -> Vec<String> {
+    body.iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("///"))
+        .map(|line| line.trim_end_matches(',').trim().to_string())
+        .collect()
+}
+
+fn parse_program_errors(body: &[String]) -> Vec<(String, (u32, String))> {
+    let mut errors = Vec::new();
+    let mut message = String::new();
+    for line in body.iter().map(|line| line.trim()) {
+        if let Some(rest) = line.strip_prefix("#[msg(\"") {
+            message = rest.trim_end_matches("\")]").to_string();
+        } else if !line.is_empty() && !line.starts_with('#') {
+            let code = 6000 + errors.len() as u32;
+            let name = line.trim_end_matches(',').to_string();
+            errors.push((name, (code, std::mem::take(&mut message))));
+        }
+    }
+    errors
+}
+
+// The fields of an accounts struct with the flags anchor puts into the IDL
+fn parse_program_account_metas(body: &[String]) -> Fields {
+    let mut accounts = Vec::new();
+    let mut attribute = String::new();
+    for line in body.iter().map(|line| line.trim()) {
+        let Some((name, ty)) = line.strip_prefix("pub ").and_then(|field| field.split_once(':')) else {
+            attribute.push_str(line);
+            attribute.push(' ');
+            continue;
+        };
+        let words: Vec<&str> = attribute
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .collect();
+        let writable = words.iter().any(|word| ["mut", "init", "zero"].contains(word));
+        // Accounts created with `init` without seeds are fresh keypairs that have to sign
+        let signer = ty.contains("Signer<") || (words.contains(&"init") && !words.contains(&"seeds"));
+        let ty = ty.trim();
+        let flags = account_flags(writable, signer, ty.starts_with("Option<"));
+        accounts.push((name.trim().to_string(), flags));
+        attribute.clear();
+    }
+    accounts
+}
+
+// `///   0. `[writable, signer]` name` in the docs of a generated builder
+fn parse_client_account_meta(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix("///")?.trim();
+    let (position, rest) = rest.split_once(". `[")?;
+    position.parse::<usize>().ok()?;
+    let (flags, name) = rest.split_once("]` ")?;
+    let name = name.split_whitespace().next()?.to_string();
+    let flags: Vec<&str> = flags.split(", ").collect();
+    Some((
+        name,
+        account_flags(
+            flags.contains(&"writable"),
+            flags.contains(&"signer"),
+            flags.contains(&"optional"),
+        ),
+    ))
+}
+
+fn account_flags(writable: bool, signer: bool, optional: bool) -> String {
+    [(writable, "writable"), (signer, "signer"), (optional, "optional")]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| *flag)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn normalize_type(ty: &str) -> String {
+    ty.split_whitespace().collect::<Vec<_>>().join("").replace(";", "; ")
+}
+
+// `pub struct Name<'info> {` -> `Name`
+fn item_name(line: &str) -> String {
+    line.split_whitespace()
+        .nth(2)
+        .unwrap_or_default()
+        .split(['<', '{', '('])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+// Joins the lines from `index` up to the first one containing `end`, leaving `index` there
+fn collect_until(lines: &[String], index: &mut usize, end: char) -> String {
+    let mut text = String::new();
+    while *index < lines.len() {
+        text.push_str(&lines[*index]);
+        text.push(' ');
+        if lines[*index].contains(end) && balanced(&text) {
+            break;
+        }
+        *index += 1;
+    }
+    text
+}
+
+// The lines between the `{` on line `index` and its closing `}`, leaving `index` on the `}`
+fn collect_body(lines: &[String], index: &mut usize) -> Vec<String> {
+    let mut body = Vec::new();
+    let mut depth = 0i32;
+    while *index < lines.len() {
+        let line = &lines[*index];
+        let opened = depth > 0;
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        if opened && depth > 0 {
+            body.push(line.clone());
+        }
+        if opened && depth <= 0 {
+            break;
+        }
+        *index += 1;
+    }
+    body
+}
+
+fn balanced(text: &str) -> bool {
+    text.matches('(').count() == text.matches(')').count()
+}
+
+fn split_top_level(params: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in params.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts.into_iter().filter(|part| !part.trim().is_empty()).collect()
+}
+
+// Removes a `//` comment that is not inside a string literal
+fn strip_comment(line: &str) -> String {
+    let mut in_string = false;
+    let mut previous = ' ';
+    let chars: Vec<char> = line.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '"' && previous != '\\' {
+            in_string = !in_string;
+        } else if !in_string && c == '/' && chars.get(i + 1) == Some(&'/') {
+            return chars[..i].iter().collect();
+        }
+        previous = c;
+    }
+    line.to_string()
+}
@@ -0,0 +1,72 @@
+//! Addresses of the accounts of the metadata program.
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::METADATA_PROGRAM_ID;
+
+/// Data type of the IDL of a program.
+pub const IDL_SEED: &str = "idl";
+/// Data type of the security.txt style metadata of a program.
+pub const METADATA_SEED: &str = "metadata";
+pub const ARCHIVE_SEED: &str = "archive";
+pub const DELEGATE_SEED: &str = "delegate";
+pub const EXECUTABLE_HASH_SEED: &str = "executable_hash";
+
+/// Canonical metadata account of `program_pubkey` for the data type `seed`, e.g. [`IDL_SEED`].
+pub fn get_metadata_address(seed: &str, program_pubkey: &Pubkey) -> Pubkey {
+    let (metadata_address, _) = Pubkey::find_program_address(
+        &[program_pubkey.as_ref(), seed.as_bytes()],
+        &METADATA_PROGRAM_ID,
+    );
+    metadata_address
+}
+
+/// Metadata account of `program_pubkey` for the data type `seed` that `signer` created with
+/// `initialize_with_signer_seed`. Anyone can publish metadata about a program this way, without
+/// being its authority.
+pub fn get_signer_seeded_metadata_address(seed: &str, program_pubkey: &Pubkey, signer: &Pubkey) -> Pubkey {
+    let (metadata_address, _) = Pubkey::find_program_address(
+        &[program_pubkey.as_ref(), signer.as_ref(), seed.as_bytes()],
+        &METADATA_PROGRAM_ID,
+    );
+    metadata_address
+}
+
+/// Archive that holds `version` of a metadata account after it was replaced.
+pub fn get_archive_address(metadata_address: &Pubkey, version: u64) -> Pubkey {
+    let (archive_address, _) = Pubkey::find_program_address(
+        &[ARCHIVE_SEED.as_bytes(), metadata_address.as_ref(), &version.to_le_bytes()],
+        &METADATA_PROGRAM_ID,
+    );
+    archive_address
+}
+
+/// Record that lets `delegate` upload new versions of a metadata account.
+pub fn get_delegate_address(metadata_address: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    let (delegate_address, _) = Pubkey::find_program_address(
+        &[DELEGATE_SEED.as_bytes(), metadata_address.as_ref(), delegate.as_ref()],
+        &METADATA_PROGRAM_ID,
+    );
+    delegate_address
+}
+
+/// State of an executable hash that is being computed for a metadata account.
+pub fn get_executable_hash_address(metadata_address: &Pubkey) -> Pubkey {
+    let (hash_state_address, _) = Pubkey::find_program_address(
+        &[EXECUTABLE_HASH_SEED.as_bytes(), metadata_address.as_ref()],
+        &METADATA_PROGRAM_ID,
+    );
+    hash_state_address
+}
+
+/// Program data account of a program owned by `loader`. Only programs of the upgradeable loader have one.
+pub fn get_program_data_address(program_pubkey: &Pubkey, loader: &Pubkey) -> Option<Pubkey> {
+    if *loader != solana_sdk::bpf_loader_upgradeable::ID {
+        return None;
+    }
+    let (program_data_address, _) = Pubkey::find_program_address(
+        &[program_pubkey.as_ref()],
+        &solana_sdk::bpf_loader_upgradeable::ID,
+    );
+    Some(program_data_address)
+}
@@ -0,0 +1,1716 @@
+//! RPC client for reading and writing metadata accounts.
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonce_utils::data_from_account;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    hash::{hash, Hash},
+    instruction::{AccountMeta, Instruction, InstructionError},
+    message::Message,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::advance_nonce_account,
+    transaction::{Transaction, TransactionError},
+};
+use std::io::Read;
+
+use crate::address::{
+    get_archive_address, get_delegate_address, get_executable_hash_address, get_metadata_address,
+    get_program_data_address, get_signer_seeded_metadata_address,
+};
+use crate::codama_sdk::accounts::MetadataExecutableHash;
+use crate::codama_sdk::instructions::{
+    AcceptAuthority, AddDelegate, AddDelegateInstructionArgs, CancelAuthority, CloseBuffer,
+    CloseExecutableHash, CloseMetadataAccount3, CreateBufferBuilder, FinishExecutableHash, Freeze,
+    HashExecutable, HashExecutableInstructionArgs, Initialize, InitializeInstructionArgs,
+    InitializeWithSignerSeed, InitializeWithSignerSeedInstructionArgs, Migrate,
+    MigrateInstructionArgs, Resize, ResizeInstructionArgs, RevokeDelegate, SetAuthority,
+    SetAuthorityInstructionArgs, SetBuffer, StartExecutableHash, WriteArchive, WriteBufferAt,
+    WriteBufferAtInstructionArgs,
+};
+use crate::codama_sdk::types::DataSource;
+use crate::data::{decode_stored_data, decompress_stored_data, fetch_data_from_url};
+use crate::error::{Error, Result};
+use crate::executable::{
+    EXECUTABLE_HASH_CHUNK_SIZE, LOADER_V4_STATE_SIZE, LOADER_V4_STATUS_FINALIZED,
+    PROGRAM_DATA_METADATA_SIZE,
+};
+use crate::state::{
+    account_discriminator, is_legacy_metadata_account_3, read_data_header, DelegateRecord, MetadataAccount, StoredData,
+    ARCHIVE_OFFSET, BUFFER_OFFSET, DATA_TYPE_OFFSET, DELEGATE_METADATA_OFFSET, DELEGATE_SIZE,
+    LEGACY_2_DATA_LEN_OFFSET, LEGACY_2_OFFSET, LEGACY_3_DATA_HEADER_OFFSET, LEGACY_3_DATA_LEN_OFFSET,
+    LEGACY_3_OFFSET, LEGACY_DATA_LEN_OFFSET, LEGACY_OFFSET, METADATA_OFFSET, PROGRAM_ID_OFFSET,
+};
+use crate::METADATA_PROGRAM_ID;
+
+const CHUNK_SIZE: usize = 900;
+// The runtime only allows an account to grow by 10 KiB per instruction, also archives
+const MAX_RESIZE_STEP: usize = 10240;
+const MAX_WRITE_ATTEMPTS: u32 = 5;
+// Download follows at most this many account pointers
+const MAX_POINTER_DEPTH: usize = 4;
+// Hashing 4 chunks stays below the default compute unit limit of 200k
+const EXECUTABLE_HASH_CHUNKS_PER_TRANSACTION: u8 = 4;
+// Compute unit limit for simulations, the real transaction requests what the simulation consumed
+const SIMULATION_COMPUTE_UNIT_LIMIT: u32 = 200000;
+// A freshly created account can take a moment to show up on the RPC node
+const ACCOUNT_RETRY_ATTEMPTS: u32 = 10;
+const ACCOUNT_RETRY_DELAY_SECS: u64 = 2;
+
+/// Options of [`MetadataClient::upload`].
+#[derive(Default)]
+pub struct UploadOptions<'a> {
+    /// Programs of the old loaders have no authority. The deployer initializes their metadata
+    /// account by signing with the program keypair.
+    pub program_keypair: Option<&'a Keypair>,
+    /// Keeps the replaced version in an archive account that the signer pays for.
+    pub archive: bool,
+    /// Uploads to the signer-seeded metadata account derived from this key instead of the canonical
+    /// one, see [`get_signer_seeded_metadata_address`]. Only this key can create the account.
+    pub seed_signer: Option<Pubkey>,
+    /// Sets the buffer as signer of a multisig that is the authority of the metadata account.
+    pub multisig: Option<MultisigAuthority<'a>>,
+}
+
+/// A multisig that is the authority of a metadata account. The signer of a call and `co_signers`
+/// together have to reach the threshold of the multisig.
+#[derive(Clone, Copy)]
+pub struct MultisigAuthority<'a> {
+    pub multisig: Pubkey,
+    pub co_signers: &'a [Keypair],
+}
+
+/// A durable nonce account whose blockhash an exported message uses, so it does not expire
+/// before the authority signs it.
+#[derive(Clone, Copy, Debug)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    /// Signs the exported message to advance the nonce. Defaults to the authority stored in the
+    /// nonce account.
+    pub authority: Option<Pubkey>,
+}
+
+/// Outcome of [`MetadataClient::upload`].
+#[derive(Debug)]
+pub struct UploadResult {
+    pub metadata_address: Pubkey,
+    /// Set when the upload created the metadata account.
+    pub initialize_signature: Option<Signature>,
+    /// The buffer the data was written to. It is closed again by the upload.
+    pub buffer: Pubkey,
+    /// Set when the signer uploaded as a delegate of the authority.
+    pub delegate_record: Option<Pubkey>,
+    /// Archive of the replaced version, if it was archived.
+    pub archive: Option<Pubkey>,
+    /// The version the upload created.
+    pub version: u64,
+    pub signature: Signature,
+}
+
+/// Outcome of [`MetadataClient::export_upload`].
+#[derive(Debug)]
+pub struct ExportedUpload {
+    pub metadata_address: Pubkey,
+    /// The buffer the data was written to. Its authority closes it with [`MetadataClient::close_buffer`]
+    /// once the exported transaction landed.
+    pub buffer: Pubkey,
+    /// Pays the fees and the rent of the resized account.
+    pub authority: Pubkey,
+    /// Every key that has to sign the message: the authority, the co-signers of a multisig and the
+    /// nonce authority.
+    pub signers: Vec<Pubkey>,
+    /// Set when the authority uploads as a delegate.
+    pub delegate_record: Option<Pubkey>,
+    /// Archive of the replaced version, if it is archived.
+    pub archive: Option<Pubkey>,
+    /// The version the transaction creates.
+    pub version: u64,
+    /// Creates the metadata account if needed, resizes it and sets the buffer. Serialize it with
+    /// [`Message::serialize`] to sign it offline or propose it to a multisig.
+    pub message: Message,
+}
+
+/// Outcome of [`MetadataClient::attest`].
+#[derive(Debug)]
+pub struct Attestation {
+    /// The attested version.
+    pub version: u64,
+    pub executable_hash: [u8; 32],
+    pub signature: Signature,
+}
+
+/// Outcome of [`MetadataClient::migrate`].
+#[derive(Debug)]
+pub struct Migration {
+    /// The legacy account type that was migrated, e.g. `MetadataAccount2`.
+    pub layout: &'static str,
+    pub signature: Signature,
+}
+
+// State of the metadata account an upload writes to, read before the buffer is created
+struct UploadTarget {
+    metadata_address: Pubkey,
+    program_id: Pubkey,
+    program_data: Option<Pubkey>,
+    account_size: usize,
+    version: u64,
+    // Length of the data of the current version, which an archive copies
+    data_len: usize,
+    delegate_record: Option<Pubkey>,
+}
+
+// Archives grow by 10 KiB per instruction like resize. set_buffer writes the last step itself, so only
+// larger archives need write_archive instructions in front of it.
+fn write_archive_instructions(payer: &Pubkey, target: &UploadTarget, archive: &Pubkey) -> Vec<Instruction> {
+    let archive_size = ARCHIVE_OFFSET + target.data_len;
+    let steps = archive_size.div_ceil(MAX_RESIZE_STEP);
+    let write_archive_ix = WriteArchive {
+        pda: target.metadata_address,
+        archive: *archive,
+        payer: *payer,
+        system_program: solana_sdk::system_program::ID,
+    }.instruction();
+    vec![write_archive_ix; steps - 1]
+}
+
+// The multisig account of the instruction and the co-signers, which the program reads as signing
+// remaining accounts
+fn multisig_accounts(multisig: Option<&MultisigAuthority>) -> (Option<Pubkey>, Vec<AccountMeta>) {
+    match multisig {
+        Some(multisig) => (
+            Some(multisig.multisig),
+            multisig.co_signers.iter().map(|co_signer| AccountMeta::new_readonly(co_signer.pubkey(), true)).collect(),
+        ),
+        None => (None, vec![]),
+    }
+}
+
+fn upload_address(program_id: &Pubkey, seed: &str, options: &UploadOptions) -> Pubkey {
+    match &options.seed_signer {
+        Some(seed_signer) => get_signer_seeded_metadata_address(seed, program_id, seed_signer),
+        None => get_metadata_address(seed, program_id),
+    }
+}
+
+/// Reads and writes metadata accounts over RPC.
+///
+/// Transactions are simulated first, so errors of the metadata program are returned as
+/// [`Error::Program`] before any fees are paid, and then sent with the compute unit limit
+/// the simulation consumed. They are confirmed with the commitment of the RPC client.
+pub struct MetadataClient {
+    rpc_client: RpcClient,
+    priority_fees_per_cu: u64,
+}
+
+impl MetadataClient {
+    pub fn new(rpc_client: RpcClient) -> Self {
+        Self {
+            rpc_client,
+            priority_fees_per_cu: 0,
+        }
+    }
+
+    /// Sets the priority fee in micro-lamports per compute unit of every transaction.
+    pub fn with_priority_fees(mut self, priority_fees_per_cu: u64) -> Self {
+        self.priority_fees_per_cu = priority_fees_per_cu;
+        self
+    }
+
+    pub fn rpc_client(&self) -> &RpcClient {
+        &self.rpc_client
+    }
+
+    // Reading
+
+    /// Fetches and decodes a metadata account.
+    pub fn fetch_metadata(&self, metadata_address: &Pubkey) -> Result<MetadataAccount> {
+        let account = self.get_account(metadata_address, "get account data")?;
+        MetadataAccount::from_account_data(&account.data)
+    }
+
+    /// Loads the given version, or the current one for None, from the metadata account or,
+    /// for older versions, from its archive.
+    pub fn fetch_stored_data(&self, metadata_address: &Pubkey, version: Option<u64>) -> Result<StoredData> {
+        let metadata = self.fetch_metadata(metadata_address)?;
+        match version {
+            Some(version) if version != metadata.version => {
+                if version == 0 || version > metadata.version {
+                    return Err(Error::VersionNotFound { version, current_version: metadata.version });
+                }
+                let archive = get_archive_address(metadata_address, version);
+                let archive_account = self.rpc_client.get_account(&archive)
+                    .map_err(|_| Error::VersionNotArchived { version, archive })?;
+                StoredData::from_archive_data(&archive_account.data)
+            }
+            _ => metadata.data.ok_or(Error::NoData(*metadata_address)),
+        }
+    }
+
+    /// The archived versions of a metadata account older than `current_version`, oldest first.
+    /// Only versions that were replaced by an upload with archiving enabled have an archive.
+    pub fn fetch_archives(&self, metadata_address: &Pubkey, current_version: u64) -> Result<Vec<(u64, StoredData)>> {
+        let versions: Vec<u64> = (1..current_version).collect();
+        let mut archives = Vec::new();
+        for versions in versions.chunks(100) {
+            let addresses: Vec<Pubkey> = versions
+                .iter()
+                .map(|version| get_archive_address(metadata_address, *version))
+                .collect();
+            let accounts = self.rpc_client.get_multiple_accounts(&addresses)
+                .map_err(Error::rpc("get archive accounts"))?;
+            for (version, account) in versions.iter().zip(accounts) {
+                if let Some(account) = account {
+                    archives.push((*version, StoredData::from_archive_data(&account.data)?));
+                }
+            }
+        }
+        Ok(archives)
+    }
+
+    /// Downloads the given version, or the current one for None, and resolves it to its content.
+    pub fn download(&self, metadata_address: &Pubkey, version: Option<u64>) -> Result<Vec<u8>> {
+        let stored_data = self.fetch_stored_data(metadata_address, version)?;
+        self.resolve_data(metadata_address, &stored_data)
+    }
+
+    /// The content the stored data of a metadata account describes. Direct data is decoded,
+    /// a URL is fetched and an account pointer is followed.
+    pub fn resolve_data(&self, metadata_address: &Pubkey, stored_data: &StoredData) -> Result<Vec<u8>> {
+        self.resolve_stored_data(&mut vec![*metadata_address], stored_data)
+    }
+
+    /// Records the hash of the data an account pointer resolves to now as its content hash,
+    /// so later downloads reject data that changed since.
+    pub fn pin_account_pointer(&self, metadata_address: &Pubkey, stored_data: &mut StoredData) -> Result<()> {
+        let content = self.resolve_data(metadata_address, stored_data)?;
+        stored_data.content_hash = hash(&content).to_bytes();
+        Ok(())
+    }
+
+    /// The delegate records of a metadata account with their addresses.
+    pub fn fetch_delegates(&self, metadata_address: &Pubkey) -> Result<Vec<(Pubkey, DelegateRecord)>> {
+        let records = self.rpc_client.get_program_accounts_with_config(
+            &METADATA_PROGRAM_ID,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::DataSize(DELEGATE_SIZE as u64),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, account_discriminator("MetadataDelegate").to_vec())),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(DELEGATE_METADATA_OFFSET, metadata_address.to_bytes().to_vec())),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        ).map_err(Error::rpc("get delegate records"))?;
+
+        records
+            .into_iter()
+            .map(|(address, record)| Ok((address, DelegateRecord::from_account_data(&record.data)?)))
+            .collect()
+    }
+
+    /// The signer-seeded metadata accounts of `program_id` for the data type `seed`, i.e. all
+    /// metadata accounts of the program and data type except the canonical one.
+    pub fn fetch_signer_seeded_metadata(&self, program_id: &Pubkey, seed: &str) -> Result<Vec<(Pubkey, MetadataAccount)>> {
+        // Data types are stored null padded, so the filter also matches their end
+        let mut data_type = [0u8; 16];
+        let seed_len = seed.len().min(data_type.len());
+        data_type[..seed_len].copy_from_slice(&seed.as_bytes()[..seed_len]);
+
+        let accounts = self.rpc_client.get_program_accounts_with_config(
+            &METADATA_PROGRAM_ID,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, account_discriminator("MetadataAccount3").to_vec())),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(PROGRAM_ID_OFFSET, program_id.to_bytes().to_vec())),
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(DATA_TYPE_OFFSET, data_type.to_vec())),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        ).map_err(Error::rpc("get metadata accounts"))?;
+
+        let canonical_address = get_metadata_address(seed, program_id);
+        accounts
+            .into_iter()
+            .filter(|(address, _)| *address != canonical_address)
+            .map(|(address, account)| Ok((address, MetadataAccount::from_account_data(&account.data)?)))
+            .collect()
+    }
+
+    /// Slot of the last deployment of the program, None for loaders that do not record it.
+    pub fn fetch_program_deploy_slot(&self, program_id: &Pubkey) -> Result<Option<u64>> {
+        let program_account = self.get_account(program_id, "get program account")?;
+        let slot_bytes = if program_account.owner == solana_sdk::bpf_loader_upgradeable::ID {
+            // UpgradeableLoaderState::ProgramData: a u32 tag followed by the slot
+            let program_data = self.get_program_data(program_id, &program_account)?;
+            program_data.data.get(4..12).map(|slot| slot.to_vec())
+        } else if program_account.owner == solana_sdk::loader_v4::ID {
+            // The loader-v4 program header starts with the slot
+            program_account.data.get(0..8).map(|slot| slot.to_vec())
+        } else {
+            return Ok(None);
+        };
+        let slot_bytes = slot_bytes
+            .and_then(|slot| <[u8; 8]>::try_from(slot).ok())
+            .ok_or_else(|| Error::InvalidAccount("Program account data is shorter than its header".to_string()))?;
+        Ok(Some(u64::from_le_bytes(slot_bytes)))
+    }
+
+    /// Upgrade authority of the program, None if the program is immutable.
+    pub fn fetch_program_authority(&self, program_id: &Pubkey) -> Result<Option<Pubkey>> {
+        let program_account = self.get_account(program_id, "get program account")?;
+        let (header, authority_offset) = if program_account.owner == solana_sdk::bpf_loader_upgradeable::ID {
+            // UpgradeableLoaderState::ProgramData: tag, slot and an optional authority
+            let program_data = self.get_program_data(program_id, &program_account)?;
+            if program_data.data.get(12) == Some(&0) {
+                return Ok(None);
+            }
+            (program_data.data, 13)
+        } else if program_account.owner == solana_sdk::loader_v4::ID {
+            // The loader-v4 program header is slot, authority and status
+            if program_account.data.get(40..48) == Some(&LOADER_V4_STATUS_FINALIZED.to_le_bytes()[..]) {
+                return Ok(None);
+            }
+            (program_account.data, 8)
+        } else if program_account.owner == solana_sdk::bpf_loader::ID
+            || program_account.owner == solana_sdk::bpf_loader_deprecated::ID
+        {
+            return Ok(None);
+        } else {
+            return Err(Error::NotAProgram(*program_id));
+        };
+        header.get(authority_offset..authority_offset + 32)
+            .and_then(|authority| Pubkey::try_from(authority).ok())
+            .map(Some)
+            .ok_or_else(|| Error::InvalidAccount("Program account data is shorter than its header".to_string()))
+    }
+
+    /// The executable bytes of the deployed program, as hashed by [`crate::executable::executable_hash`].
+    pub fn fetch_deployed_executable(&self, program_id: &Pubkey) -> Result<Vec<u8>> {
+        let program_account = self.get_account(program_id, "get program account")?;
+        let (mut executable, offset) = if program_account.owner == solana_sdk::bpf_loader_upgradeable::ID {
+            (self.get_program_data(program_id, &program_account)?.data, PROGRAM_DATA_METADATA_SIZE)
+        } else if program_account.owner == solana_sdk::loader_v4::ID {
+            (program_account.data, LOADER_V4_STATE_SIZE)
+        } else if program_account.owner == solana_sdk::bpf_loader::ID
+            || program_account.owner == solana_sdk::bpf_loader_deprecated::ID
+        {
+            (program_account.data, 0)
+        } else {
+            return Err(Error::NotAProgram(*program_id));
+        };
+        if executable.len() < offset {
+            return Err(Error::InvalidAccount("Program account data is shorter than its header".to_string()));
+        }
+        Ok(executable.split_off(offset))
+    }
+
+    // Writing
+
+    /// Uploads stored data, see [`crate::encode_data`], to the metadata account of `program_id` for
+    /// the data type `seed`. The account is created first if it does not exist yet. The data is
+    /// written through a buffer that is set and closed in a single transaction.
+    ///
+    /// Signers that are not the authority upload through their delegate record. With
+    /// [`UploadOptions::seed_signer`] the signer-seeded account is written instead, which does not
+    /// require the signer to be the authority of the program.
+    pub fn upload(
+        &self,
+        signer: &Keypair,
+        program_id: &Pubkey,
+        seed: &str,
+        stored_data: &StoredData,
+        options: &UploadOptions,
+    ) -> Result<UploadResult> {
+        let metadata_address = upload_address(program_id, seed, options);
+        let initialize_signature = self.initialize(signer, &metadata_address, program_id, seed, stored_data, options)?;
+
+        // Fail before paying for a buffer that can never be set
+        let account = self.get_account_with_retry(&metadata_address)?;
+        let target = self.upload_target(&signer.pubkey(), &metadata_address, program_id, Some(account), options)?;
+
+        let buffer_keypair = self.create_buffer(signer, program_id, seed, stored_data)?;
+        self.write_buffer(signer, &buffer_keypair.pubkey(), &stored_data.data)?;
+
+        let buffer = buffer_keypair.pubkey();
+        let (mut instructions, archive) =
+            self.set_buffer_instructions(&signer.pubkey(), &target, &buffer, stored_data.data.len(), options)?;
+        instructions.push(CloseBuffer {
+            buffer,
+            authority: signer.pubkey(),
+        }.instruction());
+        let signature = self.send_as_authority(instructions, signer, options.multisig.as_ref())?;
+
+        Ok(UploadResult {
+            metadata_address,
+            initialize_signature,
+            buffer,
+            delegate_record: target.delegate_record,
+            archive,
+            version: target.version + 1,
+            signature,
+        })
+    }
+
+    /// Like [`Self::upload`], but only the buffer is created and written with `signer`. Initializing,
+    /// resizing and setting the buffer is returned as an unsigned message for `authority`, for
+    /// example a multisig or an offline key.
+    ///
+    /// Without `authority` the message is for the authority of the metadata account, or for the
+    /// authority of the program if the account does not exist yet. The buffer stays open until its
+    /// authority closes it, so that a multisig authority does not need `signer` to sign the message.
+    /// With `nonce` the message uses the blockhash of the durable nonce and advances it, so it can be
+    /// signed at any time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_upload(
+        &self,
+        signer: &Keypair,
+        authority: Option<Pubkey>,
+        program_id: &Pubkey,
+        seed: &str,
+        stored_data: &StoredData,
+        options: &UploadOptions,
+        nonce: Option<&DurableNonce>,
+    ) -> Result<ExportedUpload> {
+        let metadata_address = upload_address(program_id, seed, options);
+        let account = self.rpc_client.get_account(&metadata_address).ok();
+        let authority = match (authority, &account) {
+            (Some(authority), _) => authority,
+            (None, Some(account)) => MetadataAccount::from_account_data(&account.data)?.authority,
+            (None, None) => self.fetch_program_authority(program_id)?
+                .ok_or(Error::NoProgramAuthority(*program_id))?,
+        };
+
+        let mut instructions = vec![];
+        if account.is_none() {
+            instructions.push(self.initialize_instruction(&authority, &metadata_address, program_id, seed, stored_data, options)?);
+        }
+        let target = self.upload_target(&authority, &metadata_address, program_id, account, options)?;
+
+        let buffer_keypair = self.create_buffer(signer, program_id, seed, stored_data)?;
+        self.write_buffer(signer, &buffer_keypair.pubkey(), &stored_data.data)?;
+
+        let buffer = buffer_keypair.pubkey();
+        let (set_buffer_instructions, archive) =
+            self.set_buffer_instructions(&authority, &target, &buffer, stored_data.data.len(), options)?;
+        instructions.extend(set_buffer_instructions);
+
+        let message = match nonce {
+            Some(nonce) => {
+                let (nonce_blockhash, nonce_authority) = self.get_nonce(nonce)?;
+                let advance_nonce_ix = advance_nonce_account(&nonce.account, &nonce_authority);
+                self.create_message(Some(advance_nonce_ix), instructions, &authority, nonce_blockhash)?
+            }
+            None => self.create_message(None, instructions, &authority, self.get_latest_blockhash()?)?,
+        };
+        let signers = message.account_keys[..message.header.num_required_signatures as usize].to_vec();
+
+        Ok(ExportedUpload {
+            metadata_address,
+            buffer,
+            authority,
+            signers,
+            delegate_record: target.delegate_record,
+            archive,
+            version: target.version + 1,
+            message,
+        })
+    }
+
+    /// Closes a buffer that was not set, or that was set by an exported transaction, and returns
+    /// its rent to the authority of the buffer.
+    pub fn close_buffer(&self, authority: &Keypair, buffer: &Pubkey) -> Result<Signature> {
+        let close_ix = CloseBuffer {
+            buffer: *buffer,
+            authority: authority.pubkey(),
+        }.instruction();
+        self.send_instruction(close_ix, authority)
+    }
+
+    /// Closes a metadata account and returns its rent to the authority, or to the signer of a
+    /// multisig authority.
+    pub fn close(&self, authority: &Keypair, metadata_address: &Pubkey, multisig: Option<&MultisigAuthority>) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let close_ix = CloseMetadataAccount3 {
+            metadata_account: *metadata_address,
+            authority: authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![close_ix], authority, multisig)
+    }
+
+    /// Proposes a new authority. It has to be accepted with [`MetadataClient::accept_authority`]
+    /// before it takes effect. `Pubkey::default()` erases the authority immediately.
+    pub fn set_authority(
+        &self,
+        authority: &Keypair,
+        metadata_address: &Pubkey,
+        new_authority: &Pubkey,
+        multisig: Option<&MultisigAuthority>,
+    ) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let set_authority_ix = SetAuthority {
+            pda: *metadata_address,
+            authority: authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(SetAuthorityInstructionArgs {
+            new_authority: *new_authority,
+        }, &remaining_accounts);
+        self.send_as_authority(vec![set_authority_ix], authority, multisig)
+    }
+
+    /// Completes an authority transfer. Signed by the proposed authority, or by threshold of the
+    /// signers when a multisig was proposed.
+    pub fn accept_authority(
+        &self,
+        new_authority: &Keypair,
+        metadata_address: &Pubkey,
+        multisig: Option<&MultisigAuthority>,
+    ) -> Result<Signature> {
+        let metadata = self.fetch_metadata(metadata_address)?;
+        let accepting_authority = multisig.map_or(new_authority.pubkey(), |multisig| multisig.multisig);
+        if metadata.pending_authority != Some(accepting_authority) {
+            return Err(Error::NotPendingAuthority {
+                signer: accepting_authority,
+                metadata: *metadata_address,
+            });
+        }
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let accept_authority_ix = AcceptAuthority {
+            pda: *metadata_address,
+            new_authority: new_authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![accept_authority_ix], new_authority, multisig)
+    }
+
+    /// Withdraws a proposed authority transfer.
+    pub fn cancel_authority(&self, authority: &Keypair, metadata_address: &Pubkey, multisig: Option<&MultisigAuthority>) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let cancel_authority_ix = CancelAuthority {
+            pda: *metadata_address,
+            authority: authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![cancel_authority_ix], authority, multisig)
+    }
+
+    /// Lets the delegate upload new versions until the expiry slot, without giving it the authority.
+    /// The signer pays for the delegate record.
+    pub fn add_delegate(
+        &self,
+        authority: &Keypair,
+        metadata_address: &Pubkey,
+        delegate: &Pubkey,
+        expiry_slot: Option<u64>,
+        multisig: Option<&MultisigAuthority>,
+    ) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let add_delegate_ix = AddDelegate {
+            delegate_record: get_delegate_address(metadata_address, delegate),
+            pda: *metadata_address,
+            authority: authority.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(AddDelegateInstructionArgs {
+            delegate: *delegate,
+            expiry_slot: expiry_slot.unwrap_or(0),
+        }, &remaining_accounts);
+        self.send_as_authority(vec![add_delegate_ix], authority, multisig)
+    }
+
+    pub fn revoke_delegate(
+        &self,
+        authority: &Keypair,
+        metadata_address: &Pubkey,
+        delegate: &Pubkey,
+        multisig: Option<&MultisigAuthority>,
+    ) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let revoke_delegate_ix = RevokeDelegate {
+            delegate_record: get_delegate_address(metadata_address, delegate),
+            pda: *metadata_address,
+            authority: authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![revoke_delegate_ix], authority, multisig)
+    }
+
+    /// Permanently freezes a metadata account. This can not be undone.
+    pub fn freeze(&self, authority: &Keypair, metadata_address: &Pubkey, multisig: Option<&MultisigAuthority>) -> Result<Signature> {
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let freeze_ix = Freeze {
+            pda: *metadata_address,
+            authority: authority.pubkey(),
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![freeze_ix], authority, multisig)
+    }
+
+    /// Converts a MetadataAccount, MetadataAccount2 or MetadataAccount3 with the old 96 byte header into a
+    /// current MetadataAccount3 at the same address.
+    /// Returns None when the account is already a MetadataAccount3.
+    pub fn migrate(&self, authority: &Keypair, metadata_address: &Pubkey, program_id: &Pubkey) -> Result<Option<Migration>> {
+        let Some((layout, migrate_ix)) = self.migrate_instruction(&authority.pubkey(), metadata_address, program_id)? else {
+            return Ok(None);
+        };
+        let signature = self.send_instruction(migrate_ix, authority)?;
+        Ok(Some(Migration { layout, signature }))
+    }
+
+    // Detects the legacy layout of the account and builds the migrate instruction with its content hash
+    fn migrate_instruction(
+        &self,
+        authority: &Pubkey,
+        metadata_address: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Option<(&'static str, Instruction)>> {
+        let account = self.get_account(metadata_address, "get account data")?;
+
+        // Detect the layout by the anchor account discriminator
+        let discriminator = account.data.get(..8)
+            .ok_or_else(|| Error::InvalidAccount(format!("Account {} has no discriminator", metadata_address)))?;
+        let (layout, data_len_offset, data_offset) = if discriminator == account_discriminator("MetadataAccount3") {
+            if !is_legacy_metadata_account_3(&account.data) {
+                return Ok(None);
+            }
+            ("MetadataAccount3 with the old 96 byte header", LEGACY_3_DATA_LEN_OFFSET, LEGACY_3_OFFSET)
+        } else if discriminator == account_discriminator("MetadataAccount2") {
+            ("MetadataAccount2", LEGACY_2_DATA_LEN_OFFSET, LEGACY_2_OFFSET)
+        } else if discriminator == account_discriminator("MetadataAccount") {
+            ("MetadataAccount", LEGACY_DATA_LEN_OFFSET, LEGACY_OFFSET)
+        } else {
+            return Err(Error::InvalidAccount(format!("{} is not a metadata account", metadata_address)));
+        };
+
+        // The program can not decompress the legacy data, so the content hash is computed here
+        let data_len_bytes = account.data.get(data_len_offset..data_len_offset + 4)
+            .and_then(|data_len| <[u8; 4]>::try_from(data_len).ok())
+            .ok_or_else(|| Error::InvalidAccount("Account data is shorter than its header".to_string()))?;
+        let data_length = u32::from_le_bytes(data_len_bytes) as usize;
+        let legacy_data = account.data
+            .get(data_offset..data_offset + data_length)
+            .ok_or_else(|| Error::InvalidAccount("Account data is shorter than its data length".to_string()))?;
+        let content_hash = if data_offset == LEGACY_3_OFFSET {
+            // The old MetadataAccount3 header already stored how its data is encoded
+            let (encoding, compression, format, data_source) =
+                read_data_header(&account.data[LEGACY_3_DATA_HEADER_OFFSET..LEGACY_3_DATA_LEN_OFFSET])?;
+            if data_source == DataSource::Direct {
+                let content = decompress_stored_data(&StoredData {
+                    encoding,
+                    compression,
+                    format,
+                    data_source,
+                    data_hash: hash(legacy_data).to_bytes(),
+                    content_hash: [0u8; 32],
+                    data: legacy_data.to_vec(),
+                })?;
+                hash(&content).to_bytes()
+            } else {
+                // Like an upload, URLs and account pointers are not pinned to a content hash
+                [0u8; 32]
+            }
+        } else {
+            // MetadataAccount and MetadataAccount2 always hold gzip compressed data
+            let mut content = Vec::new();
+            flate2::read::GzDecoder::new(legacy_data)
+                .read_to_end(&mut content)
+                .map_err(|e| Error::Decode(format!("legacy data is not gzip compressed: {}", e)))?;
+            hash(&content).to_bytes()
+        };
+
+        let migrate_ix = Migrate {
+            pda: *metadata_address,
+            authority: *authority,
+            program_id: *program_id,
+            system_program: solana_sdk::system_program::ID,
+        }.instruction(MigrateInstructionArgs { content_hash });
+        Ok(Some((layout, migrate_ix)))
+    }
+
+    /// Hashes the deployed executable of the program on chain and records the digest in the
+    /// metadata account. A previous attestation of the signer that did not finish is closed first,
+    /// one started by another key fails with [`Error::AttestationInProgress`]. With a multisig
+    /// authority only starting the attestation needs the co-signers.
+    pub fn attest(&self, signer: &Keypair, metadata_address: &Pubkey, multisig: Option<&MultisigAuthority>) -> Result<Attestation> {
+        let metadata = self.fetch_metadata(metadata_address)?;
+        let program_id = metadata.program_id;
+        let hash_state = get_executable_hash_address(metadata_address);
+
+        // For example because the program was upgraded meanwhile. Only its payer can close it.
+        let stale_state = self.rpc_client.get_account(&hash_state).ok()
+            .map(|account| MetadataExecutableHash::from_bytes(&account.data))
+            .transpose()
+            .map_err(|e| Error::InvalidAccount(format!("Failed to deserialize executable hash state: {}", e)))?;
+        if let Some(stale_state) = &stale_state {
+            if stale_state.payer != signer.pubkey() {
+                return Err(Error::AttestationInProgress { metadata: *metadata_address, payer: stale_state.payer });
+            }
+        }
+
+        let delegate_record = match multisig {
+            Some(_) => None,
+            None => self.get_delegate_record(metadata_address, &metadata, &signer.pubkey())?,
+        };
+        let program_account = self.get_account(&program_id, "get program account")?;
+        let program_data = get_program_data_address(&program_id, &program_account.owner);
+        let executable = self.fetch_deployed_executable(&program_id)?;
+
+        if stale_state.is_some() {
+            let close_ix = CloseExecutableHash {
+                hash_state,
+                pda: *metadata_address,
+                payer: signer.pubkey(),
+            }.instruction();
+            self.send_instruction(close_ix, signer)?;
+        }
+
+        let (multisig_account, remaining_accounts) = multisig_accounts(multisig);
+        let start_ix = StartExecutableHash {
+            hash_state,
+            pda: *metadata_address,
+            signer: signer.pubkey(),
+            program_id,
+            program_data,
+            system_program: solana_sdk::system_program::ID,
+            delegate_record,
+            multisig: multisig_account,
+        }.instruction_with_remaining_accounts(&remaining_accounts);
+        self.send_as_authority(vec![start_ix], signer, multisig)?;
+
+        let chunks = executable.len().div_ceil(EXECUTABLE_HASH_CHUNK_SIZE);
+        for _ in 0..chunks.div_ceil(EXECUTABLE_HASH_CHUNKS_PER_TRANSACTION as usize) {
+            let hash_ix = HashExecutable {
+                hash_state,
+                pda: *metadata_address,
+                program_id,
+                program_data,
+            }.instruction(HashExecutableInstructionArgs {
+                chunks: EXECUTABLE_HASH_CHUNKS_PER_TRANSACTION,
+            });
+            self.send_instruction(hash_ix, signer)?;
+        }
+
+        let finish_ix = FinishExecutableHash {
+            hash_state,
+            pda: *metadata_address,
+            payer: signer.pubkey(),
+            program_id,
+            program_data,
+        }.instruction();
+        let signature = self.send_instruction(finish_ix, signer)?;
+
+        // What finish_executable_hash recorded, not what the client expects it to be
+        let attested = self.fetch_metadata(metadata_address)?;
+        let executable_hash = attested.executable_hash
+            .ok_or_else(|| Error::InvalidAccount(format!("{} has no executable hash after attesting", metadata_address)))?;
+        Ok(Attestation {
+            version: attested.version,
+            executable_hash,
+            signature,
+        })
+    }
+
+    // Transactions
+
+    /// Sends a single instruction signed by the signer and waits for confirmation.
+    pub fn send_instruction(&self, instruction: Instruction, signer: &Keypair) -> Result<Signature> {
+        self.send_transaction(vec![instruction], &[signer])
+    }
+
+    /// Sends the instructions in one transaction and waits for confirmation. The first signer pays.
+    pub fn send_transaction(&self, instructions: Vec<Instruction>, signers: &[&Keypair]) -> Result<Signature> {
+        let recent_blockhash = self.get_latest_blockhash()?;
+        let transaction = self.simulate_and_create_transaction(instructions, signers, recent_blockhash)?;
+        self.rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(Error::rpc("send transaction"))
+    }
+
+    // Sends the instructions signed by the signer and, for a multisig authority, by its co-signers
+    fn send_as_authority(
+        &self,
+        instructions: Vec<Instruction>,
+        signer: &Keypair,
+        multisig: Option<&MultisigAuthority>,
+    ) -> Result<Signature> {
+        let mut signers = vec![signer];
+        if let Some(multisig) = multisig {
+            signers.extend(multisig.co_signers);
+        }
+        self.send_transaction(instructions, &signers)
+    }
+
+    /// Simulates the instructions and signs a transaction that requests the compute units the
+    /// simulation consumed, with the priority fee of the client. The first signer pays.
+    pub fn simulate_and_create_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        signers: &[&Keypair],
+        recent_blockhash: Hash,
+    ) -> Result<Transaction> {
+        let message = self.simulate_and_create_message(instructions, &signers[0].pubkey(), recent_blockhash)?;
+        Ok(Transaction::new(signers, message, recent_blockhash))
+    }
+
+    /// Like [`Self::simulate_and_create_transaction`], but returns the message unsigned. The
+    /// simulation does not verify signatures, so the signers do not have to be available.
+    pub fn simulate_and_create_message(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Result<Message> {
+        self.create_message(None, instructions, payer, recent_blockhash)
+    }
+
+    // Advancing a durable nonce has to be the first instruction, also in the simulation, or the
+    // nonce blockhash is rejected as expired
+    fn create_message(
+        &self,
+        advance_nonce_ix: Option<Instruction>,
+        instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Result<Message> {
+        let advance_nonce: Vec<Instruction> = advance_nonce_ix.into_iter().collect();
+        let mut simulation_instructions = advance_nonce.clone();
+        simulation_instructions.extend([
+            ComputeBudgetInstruction::set_compute_unit_price(self.priority_fees_per_cu),
+            ComputeBudgetInstruction::set_compute_unit_limit(SIMULATION_COMPUTE_UNIT_LIMIT),
+        ]);
+        simulation_instructions.extend(instructions.clone());
+
+        let transaction_to_simulate = Transaction::new_unsigned(Message::new_with_blockhash(
+            &simulation_instructions,
+            Some(payer),
+            &recent_blockhash,
+        ));
+
+        let simulation = self.rpc_client.simulate_transaction(&transaction_to_simulate)
+            .map_err(Error::rpc("simulate transaction"))?;
+
+        // Errors of the metadata program will fail the real transaction as well
+        if let Some(TransactionError::InstructionError(index, InstructionError::Custom(code))) = &simulation.value.err {
+            let failed_program = simulation_instructions.get(*index as usize).map(|ix| ix.program_id);
+            if failed_program == Some(METADATA_PROGRAM_ID) {
+                return Err(Error::Program(*code));
+            }
+        }
+
+        let units_consumed = simulation.value.units_consumed.unwrap_or(0) as u32;
+        let mut final_instructions = advance_nonce;
+        final_instructions.extend([
+            ComputeBudgetInstruction::set_compute_unit_price(self.priority_fees_per_cu),
+            ComputeBudgetInstruction::set_compute_unit_limit(units_consumed + 200),
+        ]);
+        final_instructions.extend(instructions);
+
+        Ok(Message::new_with_blockhash(&final_instructions, Some(payer), &recent_blockhash))
+    }
+
+    // Upload steps
+
+    // Creates the metadata account if it does not exist yet
+    fn initialize(
+        &self,
+        signer: &Keypair,
+        metadata_address: &Pubkey,
+        program_id: &Pubkey,
+        seed: &str,
+        stored_data: &StoredData,
+        options: &UploadOptions,
+    ) -> Result<Option<Signature>> {
+        if self.rpc_client.get_account(metadata_address).is_ok() {
+            return Ok(None);
+        }
+
+        let initialize_ix = self.initialize_instruction(&signer.pubkey(), metadata_address, program_id, seed, stored_data, options)?;
+        let mut signers = vec![signer];
+        if let (Some(program_keypair), None) = (options.program_keypair, options.seed_signer) {
+            signers.push(program_keypair);
+        }
+        self.send_transaction(vec![initialize_ix], &signers).map(Some)
+    }
+
+    fn initialize_instruction(
+        &self,
+        signer: &Pubkey,
+        metadata_address: &Pubkey,
+        program_id: &Pubkey,
+        seed: &str,
+        stored_data: &StoredData,
+        options: &UploadOptions,
+    ) -> Result<Instruction> {
+        let program_account = self.get_account(program_id, "get program account")?;
+        let program_data = get_program_data_address(program_id, &program_account.owner);
+        if let Some(seed_signer) = options.seed_signer {
+            // Anyone can create a signer-seeded account, the program only has to exist
+            if seed_signer != *signer {
+                return Err(Error::NotSeedSigner {
+                    metadata: *metadata_address,
+                    seed_signer,
+                });
+            }
+            return Ok(InitializeWithSignerSeed {
+                pda: *metadata_address,
+                signer: *signer,
+                system_program: solana_sdk::system_program::ID,
+                program_id: *program_id,
+                program_data,
+            }.instruction(InitializeWithSignerSeedInstructionArgs {
+                seed: seed.to_string(),
+                encoding: stored_data.encoding,
+                compression: stored_data.compression,
+                format: stored_data.format,
+                data_source: stored_data.data_source,
+            }));
+        }
+
+        // Programs of the old loaders are immutable and have no authority. Only the deployer
+        // can claim them by signing with the program keypair.
+        let has_authority = program_account.owner != solana_sdk::bpf_loader::ID
+            && program_account.owner != solana_sdk::bpf_loader_deprecated::ID;
+        if !has_authority && options.program_keypair.is_none() {
+            return Err(Error::NoProgramAuthority(*program_id));
+        }
+        // Upgradeable programs with a revoked authority and finalized loader-v4 programs were made
+        // immutable on purpose, so nobody can claim their canonical account
+        if has_authority && self.fetch_program_authority(program_id)?.is_none() {
+            return Err(Error::ImmutableProgram(*program_id));
+        }
+
+        let mut initialize_ix = Initialize {
+            pda: *metadata_address,
+            signer: *signer,
+            system_program: solana_sdk::system_program::ID,
+            program_id: *program_id,
+            program_data,
+        }.instruction(InitializeInstructionArgs {
+            seed: seed.to_string(),
+            encoding: stored_data.encoding,
+            compression: stored_data.compression,
+            format: stored_data.format,
+            data_source: stored_data.data_source,
+        });
+        if options.program_keypair.is_some() {
+            // The program account is not a signer in the IDL, it only signs as attestation
+            initialize_ix.accounts[3].is_signer = true;
+        }
+        Ok(initialize_ix)
+    }
+
+    // Reads the metadata account before the buffer is paid for. None means the account is
+    // created in the same transaction as the buffer is set.
+    fn upload_target(
+        &self,
+        authority: &Pubkey,
+        metadata_address: &Pubkey,
+        program_id: &Pubkey,
+        account: Option<Account>,
+        options: &UploadOptions,
+    ) -> Result<UploadTarget> {
+        let program_owner = self.get_account(program_id, "get program account")?.owner;
+        let (account_size, version, data_len, delegate_record) = match account {
+            Some(account) => {
+                let metadata = MetadataAccount::from_account_data(&account.data)?;
+                if metadata.frozen {
+                    return Err(Error::Frozen(*metadata_address));
+                }
+                // Signers of a multisig authority do not need a delegate record
+                let delegate_record = match options.multisig {
+                    Some(_) => None,
+                    None => self.get_delegate_record(metadata_address, &metadata, authority)?,
+                };
+                let data_len = metadata.data.map_or(0, |stored_data| stored_data.data.len());
+                (account.data.len(), metadata.version, data_len, delegate_record)
+            }
+            None => (METADATA_OFFSET, 0, 0, None),
+        };
+        Ok(UploadTarget {
+            metadata_address: *metadata_address,
+            program_id: *program_id,
+            program_data: get_program_data_address(program_id, &program_owner),
+            account_size,
+            version,
+            data_len,
+            delegate_record,
+        })
+    }
+
+    // Resizes the metadata account to the size of the data and sets the buffer, archiving the
+    // replaced version if requested. Returns the instructions and the archive.
+    fn set_buffer_instructions(
+        &self,
+        authority: &Pubkey,
+        target: &UploadTarget,
+        buffer: &Pubkey,
+        data_len: usize,
+        options: &UploadOptions,
+    ) -> Result<(Vec<Instruction>, Option<Pubkey>)> {
+        let archive = (options.archive && target.version > 0)
+            .then(|| get_archive_address(&target.metadata_address, target.version));
+
+        let target_size = data_len + METADATA_OFFSET;
+        let (multisig, remaining_accounts) = multisig_accounts(options.multisig.as_ref());
+        let resize = Resize {
+            pda: target.metadata_address,
+            signer: *authority,
+            system_program: solana_sdk::system_program::ID,
+            program_id: target.program_id,
+            delegate_record: target.delegate_record,
+            multisig,
+        };
+        let mut instructions = match archive {
+            Some(archive) => write_archive_instructions(authority, target, &archive),
+            None => vec![],
+        };
+        if target_size > target.account_size {
+            instructions.extend(self.resize_instructions(&resize, &remaining_accounts, options.seed_signer, target.account_size, target_size)?);
+        }
+        instructions.push(SetBuffer {
+            buffer: *buffer,
+            pda: target.metadata_address,
+            authority: *authority,
+            program_id: target.program_id,
+            program_data: target.program_data,
+            archive,
+            payer: archive.map(|_| *authority),
+            system_program: archive.map(|_| solana_sdk::system_program::ID),
+            delegate_record: target.delegate_record,
+            multisig,
+        }.instruction_with_remaining_accounts(&remaining_accounts));
+        // Only the authority can shrink the account, a delegate leaves the unused space to it
+        if target_size < target.account_size && target.delegate_record.is_none() {
+            instructions.extend(self.resize_instructions(&resize, &remaining_accounts, options.seed_signer, target.account_size, target_size)?);
+        }
+        Ok((instructions, archive))
+    }
+
+    fn create_buffer(&self, signer: &Keypair, program_id: &Pubkey, seed: &str, stored_data: &StoredData) -> Result<Keypair> {
+        let buffer_size = stored_data.data.len() + BUFFER_OFFSET;
+        let buffer_keypair = Keypair::new();
+        let rent = self.rpc_client.get_minimum_balance_for_rent_exemption(buffer_size)
+            .map_err(Error::rpc("get rent"))?;
+        let create_account_ix = solana_sdk::system_instruction::create_account(
+            &signer.pubkey(),
+            &buffer_keypair.pubkey(),
+            rent,
+            buffer_size as u64,
+            &METADATA_PROGRAM_ID,
+        );
+        let create_buffer_ix = CreateBufferBuilder::new()
+            .buffer(buffer_keypair.pubkey())
+            .authority(signer.pubkey())
+            .program_id(*program_id)
+            .seed(seed.to_string())
+            .encoding(stored_data.encoding)
+            .compression(stored_data.compression)
+            .format(stored_data.format)
+            .data_source(stored_data.data_source)
+            .data_hash(stored_data.data_hash)
+            .content_hash(stored_data.content_hash)
+            .instruction();
+        self.send_transaction(vec![create_account_ix, create_buffer_ix], &[signer, &buffer_keypair])?;
+        Ok(buffer_keypair)
+    }
+
+    fn write_buffer(&self, signer: &Keypair, buffer: &Pubkey, data: &[u8]) -> Result<()> {
+        // Every chunk carries its own offset, so chunks can be sent without waiting for each other
+        // and a chunk whose first send actually landed can be resent without duplicating data.
+        let mut pending_offsets: Vec<usize> = (0..data.len()).step_by(CHUNK_SIZE).collect();
+        let mut attempts = 0;
+
+        while !pending_offsets.is_empty() {
+            attempts += 1;
+            if attempts > MAX_WRITE_ATTEMPTS {
+                return Err(Error::WriteFailed {
+                    chunks: pending_offsets.len(),
+                    attempts: MAX_WRITE_ATTEMPTS,
+                });
+            }
+
+            let recent_blockhash = self.get_latest_blockhash()?;
+            let mut sent_chunks = Vec::with_capacity(pending_offsets.len());
+            let mut failed_offsets = Vec::new();
+            for offset in pending_offsets {
+                let chunk_end = std::cmp::min(offset + CHUNK_SIZE, data.len());
+                let write_ix = WriteBufferAt {
+                    buffer: *buffer,
+                    signer: signer.pubkey(),
+                    system_program: solana_sdk::system_program::ID,
+                }.instruction(WriteBufferAtInstructionArgs {
+                    offset: offset as u32,
+                    expected_data_len: None,
+                    data: data[offset..chunk_end].to_vec(),
+                });
+                let transaction = self.simulate_and_create_transaction(vec![write_ix], &[signer], recent_blockhash)?;
+
+                match self.rpc_client.send_transaction(&transaction) {
+                    Ok(signature) => sent_chunks.push((offset, signature)),
+                    Err(_) => failed_offsets.push(offset),
+                }
+            }
+
+            for (offset, signature) in sent_chunks {
+                if self.rpc_client
+                    .poll_for_signature_with_commitment(&signature, self.rpc_client.commitment())
+                    .is_err()
+                {
+                    failed_offsets.push(offset);
+                }
+            }
+
+            pending_offsets = failed_offsets;
+        }
+        Ok(())
+    }
+
+    // Grows the metadata account in steps the runtime allows, or shrinks it in one step. The account
+    // grows before the buffer is set and shrinks afterwards, so the previous version is still intact
+    // when set_buffer archives it. seed_signer is the key a signer-seeded account was derived with.
+    fn resize_instructions(
+        &self,
+        accounts: &Resize,
+        remaining_accounts: &[AccountMeta],
+        seed_signer: Option<Pubkey>,
+        from_size: usize,
+        to_size: usize,
+    ) -> Result<Vec<Instruction>> {
+        let mut sizes = vec![];
+        let mut current_size = from_size;
+        while current_size < to_size {
+            current_size = current_size.saturating_add(MAX_RESIZE_STEP).min(to_size);
+            sizes.push(current_size);
+        }
+        if to_size < from_size {
+            sizes.push(to_size);
+        }
+
+        sizes
+            .into_iter()
+            .map(|size| {
+                let len = u32::try_from(size)
+                    .map_err(|_| Error::InvalidAccount(format!("Account size {} does not fit in a resize instruction", size)))?;
+                Ok(accounts.instruction_with_remaining_accounts(ResizeInstructionArgs { len, seed_signer }, remaining_accounts))
+            })
+            .collect()
+    }
+
+    // Signers that are not the authority of the metadata account write through their delegate record
+    fn get_delegate_record(&self, metadata_address: &Pubkey, metadata: &MetadataAccount, signer: &Pubkey) -> Result<Option<Pubkey>> {
+        if metadata.authority == *signer {
+            return Ok(None);
+        }
+        let delegate_record = get_delegate_address(metadata_address, signer);
+        Ok(self.rpc_client.get_account(&delegate_record).ok().map(|_| delegate_record))
+    }
+
+    // Resolving
+
+    // visited holds the accounts on the way, starting with the metadata account, to stop cycles
+    fn resolve_stored_data(&self, visited: &mut Vec<Pubkey>, stored_data: &StoredData) -> Result<Vec<u8>> {
+        match stored_data.data_source {
+            DataSource::Direct => decode_stored_data(stored_data),
+            DataSource::Url => {
+                let url = String::from_utf8(decompress_stored_data(stored_data)?)
+                    .map_err(|_| Error::Decode("the stored URL is not valid UTF-8".to_string()))?;
+                let data = fetch_data_from_url(&url)?;
+                if stored_data.content_hash != [0u8; 32] && hash(&data).to_bytes() != stored_data.content_hash {
+                    return Err(Error::ContentHashMismatch(format!("The content at {}", url)));
+                }
+                Ok(data)
+            }
+            DataSource::Account => {
+                let data = self.resolve_account_pointer(visited, &stored_data.data)?;
+                if stored_data.content_hash != [0u8; 32] && hash(&data).to_bytes() != stored_data.content_hash {
+                    return Err(Error::ContentHashMismatch(format!("The data in {}", visited.last().unwrap())));
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    fn resolve_account_pointer(&self, visited: &mut Vec<Pubkey>, pointer: &[u8]) -> Result<Vec<u8>> {
+        let address = pointer
+            .get(..32)
+            .and_then(|address| Pubkey::try_from(address).ok())
+            .ok_or_else(|| Error::InvalidAccountPointer(format!("{} bytes", pointer.len())))?;
+        let range = match pointer.len() {
+            32 => None,
+            40 => Some((
+                u32::from_le_bytes(pointer[32..36].try_into().unwrap()) as usize,
+                u32::from_le_bytes(pointer[36..40].try_into().unwrap()) as usize,
+            )),
+            _ => return Err(Error::InvalidAccountPointer(format!("{} bytes", pointer.len()))),
+        };
+
+        if visited.contains(&address) {
+            let path: Vec<String> = visited.iter().map(|address| address.to_string()).collect();
+            return Err(Error::AccountPointerCycle(format!("{} -> {}", path.join(" -> "), address)));
+        }
+        if visited.len() > MAX_POINTER_DEPTH {
+            return Err(Error::TooManyAccountPointers(MAX_POINTER_DEPTH));
+        }
+        visited.push(address);
+
+        let account = self.get_account(&address, "get pointed to account")?;
+        match range {
+            Some((offset, length)) => account.data
+                .get(offset..offset + length)
+                .map(|data| data.to_vec())
+                .ok_or_else(|| Error::InvalidAccountPointer(format!("{} is shorter than the range {}:{}", address, offset, length))),
+            // A metadata account is resolved like the account that points to it
+            None if account.owner == METADATA_PROGRAM_ID
+                && account.data.starts_with(&account_discriminator("MetadataAccount3")) =>
+            {
+                let stored_data = MetadataAccount::from_account_data(&account.data)?
+                    .data
+                    .ok_or_else(|| Error::InvalidAccountPointer(format!("{} has no data", address)))?;
+                self.resolve_stored_data(visited, &stored_data)
+            }
+            None => Ok(account.data),
+        }
+    }
+
+    // RPC helpers
+
+    fn get_account(&self, address: &Pubkey, action: &'static str) -> Result<Account> {
+        self.rpc_client.get_account(address).map_err(Error::rpc(action))
+    }
+
+    fn get_account_with_retry(&self, address: &Pubkey) -> Result<Account> {
+        let mut attempts = 0;
+        loop {
+            match self.rpc_client.get_account(address) {
+                Ok(account) => return Ok(account),
+                Err(e) => {
+                    attempts += 1;
+                    if attempts >= ACCOUNT_RETRY_ATTEMPTS {
+                        return Err(Error::rpc("get account data")(e));
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(ACCOUNT_RETRY_DELAY_SECS));
+                }
+            }
+        }
+    }
+
+    fn get_program_data(&self, program_id: &Pubkey, program_account: &Account) -> Result<Account> {
+        let program_data_address = get_program_data_address(program_id, &program_account.owner)
+            .ok_or(Error::NotAProgram(*program_id))?;
+        self.get_account(&program_data_address, "get program data account")
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        self.rpc_client.get_latest_blockhash().map_err(Error::rpc("get recent blockhash"))
+    }
+
+    // Returns the blockhash and the authority of a durable nonce
+    fn get_nonce(&self, nonce: &DurableNonce) -> Result<(Hash, Pubkey)> {
+        let account = self.get_account(&nonce.account, "get nonce account")?;
+        let data = data_from_account(&account)
+            .map_err(|e| Error::InvalidAccount(format!("{} is not a nonce account: {}", nonce.account, e)))?;
+        if let Some(authority) = nonce.authority.filter(|authority| *authority != data.authority) {
+            return Err(Error::InvalidAccount(format!(
+                "The authority of nonce account {} is {}, not {}",
+                nonce.account, data.authority, authority
+            )));
+        }
+        Ok((data.blockhash(), data.authority))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codama_sdk::accounts::MetadataAccount3;
+    use crate::codama_sdk::types::{Compression, Encoding, Format};
+    use crate::data::{account_pointer_data, encode_data, DataFormat};
+    use crate::codama_sdk::accounts::MetadataArchive;
+    use crate::state::VERSION_OFFSET;
+    use borsh::BorshDeserialize;
+    use solana_account_decoder::{encode_ui_account, UiAccount};
+    use solana_client::client_error::Result as ClientResult;
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_response::{Response, RpcResponseContext};
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    // Answers getAccountInfo with the given accounts and every other request like the mock client
+    struct AccountsSender {
+        accounts: HashMap<Pubkey, Account>,
+        mock: solana_client::nonblocking::rpc_client::RpcClient,
+    }
+
+    #[async_trait::async_trait]
+    impl RpcSender for AccountsSender {
+        async fn send(&self, request: RpcRequest, params: serde_json::Value) -> ClientResult<serde_json::Value> {
+            let context = RpcResponseContext { slot: 1, api_version: None };
+            let response = match request {
+                RpcRequest::GetAccountInfo => serde_json::to_value(Response {
+                    context,
+                    value: self.encode_account(&params[0]),
+                }),
+                RpcRequest::GetMultipleAccounts => serde_json::to_value(Response {
+                    context,
+                    value: params[0].as_array().unwrap().iter().map(|address| self.encode_account(address)).collect::<Vec<_>>(),
+                }),
+                _ => return self.mock.send(request, params).await,
+            };
+            Ok(response.unwrap())
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "accounts".to_string()
+        }
+    }
+
+    impl AccountsSender {
+        fn encode_account(&self, address: &serde_json::Value) -> Option<UiAccount> {
+            let address = Pubkey::from_str(address.as_str().unwrap()).unwrap();
+            self.accounts.get(&address)
+                .map(|account| encode_ui_account(&address, account, UiAccountEncoding::Base64, None, None))
+        }
+    }
+
+    fn client_with_accounts(accounts: Vec<(Pubkey, Account)>) -> MetadataClient {
+        let sender = AccountsSender {
+            accounts: accounts.into_iter().collect(),
+            mock: solana_client::nonblocking::rpc_client::RpcClient::new_mock("succeeds".to_string()),
+        };
+        MetadataClient::new(RpcClient::new_sender(sender, RpcClientConfig::default()))
+    }
+
+    fn account(owner: &Pubkey, data: Vec<u8>) -> Account {
+        Account { lamports: 1, data, owner: *owner, executable: false, rent_epoch: 0 }
+    }
+
+    fn client_with_account(address: &Pubkey, owner: &Pubkey, data: Vec<u8>) -> MetadataClient {
+        client_with_accounts(vec![(*address, account(owner, data))])
+    }
+
+    fn metadata_account_data(stored_data: &StoredData) -> Vec<u8> {
+        let header = MetadataAccount3 {
+            discriminator: account_discriminator("MetadataAccount3"),
+            authority: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            data_type: [0; 16],
+            encoding: stored_data.encoding,
+            compression: stored_data.compression,
+            format: stored_data.format,
+            data_source: stored_data.data_source,
+            data_len: stored_data.data.len() as u32,
+            data_hash: stored_data.data_hash,
+            content_hash: stored_data.content_hash,
+            version: 1,
+            frozen: false,
+            pending_authority: Pubkey::default(),
+            program_data_slot: 0,
+            executable_hash: [0; 32],
+        };
+        let mut account_data = borsh::to_vec(&header).unwrap();
+        account_data.extend_from_slice(&stored_data.data);
+        account_data
+    }
+
+    fn archive_account_data(metadata: &Pubkey, version: u64, stored_data: &StoredData) -> Vec<u8> {
+        let header = MetadataArchive {
+            discriminator: account_discriminator("MetadataArchive"),
+            metadata: *metadata,
+            version,
+            encoding: stored_data.encoding,
+            compression: stored_data.compression,
+            format: stored_data.format,
+            data_source: stored_data.data_source,
+            data_len: stored_data.data.len() as u32,
+            data_hash: stored_data.data_hash,
+            content_hash: stored_data.content_hash,
+        };
+        let mut account_data = borsh::to_vec(&header).unwrap();
+        account_data.extend_from_slice(&stored_data.data);
+        account_data
+    }
+
+    #[test]
+    fn pointer_resolves_to_the_whole_account() {
+        let (metadata, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let client = client_with_account(&target, &Pubkey::new_unique(), b"0123456789".to_vec());
+        let stored_data = account_pointer_data(&target, None).unwrap();
+        assert_eq!(client.resolve_data(&metadata, &stored_data).unwrap(), b"0123456789");
+    }
+
+    #[test]
+    fn pointer_resolves_to_its_range() {
+        let (metadata, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let client = client_with_account(&target, &Pubkey::new_unique(), b"0123456789".to_vec());
+        let stored_data = account_pointer_data(&target, Some((2, 3))).unwrap();
+        assert_eq!(client.resolve_data(&metadata, &stored_data).unwrap(), b"234");
+
+        let client = client_with_account(&target, &Pubkey::new_unique(), b"0123".to_vec());
+        assert!(matches!(
+            client.resolve_data(&metadata, &stored_data),
+            Err(Error::InvalidAccountPointer(_))
+        ));
+    }
+
+    #[test]
+    fn pointer_to_a_metadata_account_resolves_its_data() {
+        let (metadata, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data_format = DataFormat { encoding: Encoding::Base64, compression: Some(Compression::Zstd), format: Format::Json };
+        let target_data = encode_data(b"{\"name\":\"target\"}", &data_format).unwrap();
+        let client = client_with_account(&target, &METADATA_PROGRAM_ID, metadata_account_data(&target_data));
+
+        let stored_data = account_pointer_data(&target, None).unwrap();
+        assert_eq!(client.resolve_data(&metadata, &stored_data).unwrap(), b"{\"name\":\"target\"}");
+    }
+
+    #[test]
+    fn pointer_cycles_are_rejected() {
+        let (metadata, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // The target points back to the metadata account
+        let back_pointer = account_pointer_data(&metadata, None).unwrap();
+        let client = client_with_account(&target, &METADATA_PROGRAM_ID, metadata_account_data(&back_pointer));
+
+        let stored_data = account_pointer_data(&target, None).unwrap();
+        assert!(matches!(
+            client.resolve_data(&metadata, &stored_data),
+            Err(Error::AccountPointerCycle(_))
+        ));
+    }
+
+    #[test]
+    fn pinned_pointer_rejects_changed_data() {
+        let (metadata, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let client = client_with_account(&target, &Pubkey::new_unique(), b"changed".to_vec());
+        let mut stored_data = account_pointer_data(&target, None).unwrap();
+        stored_data.content_hash = hash(b"original").to_bytes();
+        assert!(matches!(
+            client.resolve_data(&metadata, &stored_data),
+            Err(Error::ContentHashMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn exported_upload_is_only_signed_by_the_authority() {
+        let stored_data = encode_data(b"{\"name\":\"test\"}", &DataFormat::default()).unwrap();
+        let account_data = metadata_account_data(&stored_data);
+        let authority = MetadataAccount::from_account_data(&account_data).unwrap().authority;
+        // A program of the non-upgradeable loader, so there is no program data account to look up
+        let program_id = Pubkey::new_unique();
+        let client = client_with_accounts(vec![
+            (get_metadata_address("idl", &program_id), account(&METADATA_PROGRAM_ID, account_data)),
+            (program_id, account(&solana_sdk::bpf_loader::ID, vec![])),
+        ]);
+
+        let options = UploadOptions::default();
+        let exported = client
+            .export_upload(&Keypair::new(), None, &program_id, "idl", &stored_data, &options, None)
+            .unwrap();
+        assert_eq!(exported.authority, authority);
+        assert_eq!(exported.signers, vec![authority]);
+    }
+
+    #[test]
+    fn large_archives_are_written_in_steps() {
+        let client = client_with_accounts(vec![]);
+        let (authority, buffer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut target = UploadTarget {
+            metadata_address: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            program_data: None,
+            account_size: METADATA_OFFSET + 25_000,
+            version: 1,
+            data_len: 25_000,
+            delegate_record: None,
+        };
+        let options = UploadOptions { archive: true, ..Default::default() };
+        let archive = get_archive_address(&target.metadata_address, 1);
+        let write_archive_ix = WriteArchive {
+            pda: target.metadata_address,
+            archive,
+            payer: authority,
+            system_program: solana_sdk::system_program::ID,
+        }.instruction();
+
+        // 25 KiB of data and the header take three steps, set_buffer writes the last one
+        let (instructions, archived) = client.set_buffer_instructions(&authority, &target, &buffer, 100, &options).unwrap();
+        assert_eq!(archived, Some(archive));
+        assert_eq!(instructions[..2], [write_archive_ix.clone(), write_archive_ix.clone()]);
+        assert!(!instructions[2..].contains(&write_archive_ix));
+
+        // The archive only copies the data, not the unused space of the account
+        target.data_len = 100;
+        let (instructions, _) = client.set_buffer_instructions(&authority, &target, &buffer, 100, &options).unwrap();
+        assert!(!instructions.contains(&write_archive_ix));
+    }
+
+    #[test]
+    fn archived_versions_are_listed_and_loaded() {
+        let metadata = Pubkey::new_unique();
+        let current = encode_data(b"{\"version\":4}", &DataFormat::default()).unwrap();
+        let version_1 = encode_data(b"{\"version\":1}", &DataFormat::default()).unwrap();
+        let version_3 = encode_data(b"{\"version\":3}", &DataFormat::default()).unwrap();
+        let mut metadata_data = metadata_account_data(&current);
+        metadata_data[VERSION_OFFSET..VERSION_OFFSET + 8].copy_from_slice(&4u64.to_le_bytes());
+        // Version 2 was overwritten without archiving it
+        let client = client_with_accounts(vec![
+            (metadata, account(&METADATA_PROGRAM_ID, metadata_data)),
+            (get_archive_address(&metadata, 1), account(&METADATA_PROGRAM_ID, archive_account_data(&metadata, 1, &version_1))),
+            (get_archive_address(&metadata, 3), account(&METADATA_PROGRAM_ID, archive_account_data(&metadata, 3, &version_3))),
+        ]);
+
+        let versions: Vec<u64> = client.fetch_archives(&metadata, 4).unwrap().into_iter().map(|(version, _)| version).collect();
+        assert_eq!(versions, [1, 3]);
+
+        // A rollback uploads the data of the archive again
+        assert_eq!(client.download(&metadata, Some(1)).unwrap(), b"{\"version\":1}");
+        assert_eq!(client.download(&metadata, None).unwrap(), b"{\"version\":4}");
+        assert!(matches!(
+            client.download(&metadata, Some(2)),
+            Err(Error::VersionNotArchived { version: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn delegates_do_not_shrink_the_account() {
+        let client = client_with_accounts(vec![]);
+        let (delegate, buffer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut target = UploadTarget {
+            metadata_address: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            program_data: None,
+            account_size: METADATA_OFFSET + 1000,
+            version: 1,
+            data_len: 1000,
+            delegate_record: None,
+        };
+        let options = UploadOptions::default();
+        let (instructions, _) = client.set_buffer_instructions(&delegate, &target, &buffer, 100, &options).unwrap();
+        assert_eq!(instructions.len(), 2, "set_buffer and the shrinking resize");
+
+        target.delegate_record = Some(get_delegate_address(&target.metadata_address, &delegate));
+        let (instructions, _) = client.set_buffer_instructions(&delegate, &target, &buffer, 100, &options).unwrap();
+        assert_eq!(instructions.len(), 1, "only set_buffer");
+    }
+
+    #[test]
+    fn uploads_above_64_kib_are_resized_in_steps() {
+        let client = client_with_accounts(vec![]);
+        let (authority, buffer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let target = UploadTarget {
+            metadata_address: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            program_data: None,
+            account_size: METADATA_OFFSET,
+            version: 0,
+            data_len: 0,
+            delegate_record: None,
+        };
+        let (instructions, _) = client
+            .set_buffer_instructions(&authority, &target, &buffer, 70_000, &UploadOptions::default())
+            .unwrap();
+
+        // Seven resize steps of at most 10 KiB in front of set_buffer
+        let lens: Vec<u32> = instructions[..instructions.len() - 1]
+            .iter()
+            .map(|ix| ResizeInstructionArgs::try_from_slice(&ix.data[8..]).unwrap().len)
+            .collect();
+        assert_eq!(lens.len(), 7);
+        assert_eq!(*lens.last().unwrap() as usize, METADATA_OFFSET + 70_000);
+        let mut size = METADATA_OFFSET as u32;
+        for len in lens {
+            assert!(len - size <= MAX_RESIZE_STEP as u32);
+            size = len;
+        }
+    }
+
+    #[test]
+    fn legacy_layouts_are_migrated_with_their_content_hash() {
+        let (metadata, program_id, authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let content = br#"{"legacy":true}"#;
+        let gzip_data = crate::data::compress(content, Compression::Gzip).unwrap();
+        let mut data_type = [0u8; 16];
+        data_type[..3].copy_from_slice(b"idl");
+
+        let mut metadata_account = account_discriminator("MetadataAccount").to_vec();
+        metadata_account.extend_from_slice(authority.as_ref());
+        metadata_account.extend_from_slice(&data_type);
+        metadata_account.extend_from_slice(&(gzip_data.len() as u32).to_le_bytes());
+        metadata_account.extend_from_slice(&gzip_data);
+
+        let mut metadata_account_2 = account_discriminator("MetadataAccount2").to_vec();
+        metadata_account_2.extend_from_slice(authority.as_ref());
+        metadata_account_2.extend_from_slice(&data_type);
+        metadata_account_2.extend_from_slice(program_id.as_ref());
+        metadata_account_2.extend_from_slice(&(gzip_data.len() as u32).to_le_bytes());
+        metadata_account_2.extend_from_slice(&gzip_data);
+
+        // The old MetadataAccount3 header stores how its data is encoded: utf8, no compression, json, direct
+        let mut metadata_account_3 = account_discriminator("MetadataAccount3").to_vec();
+        metadata_account_3.extend_from_slice(authority.as_ref());
+        metadata_account_3.extend_from_slice(program_id.as_ref());
+        metadata_account_3.extend_from_slice(&data_type);
+        metadata_account_3.extend_from_slice(&[0, 0, 1, 2]);
+        metadata_account_3.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        metadata_account_3.extend_from_slice(content);
+
+        let layouts = [
+            (metadata_account, "MetadataAccount"),
+            (metadata_account_2, "MetadataAccount2"),
+            (metadata_account_3, "MetadataAccount3 with the old 96 byte header"),
+        ];
+        for (account_data, expected_layout) in layouts {
+            let client = client_with_account(&metadata, &METADATA_PROGRAM_ID, account_data);
+            let (layout, ix) = client.migrate_instruction(&authority, &metadata, &program_id).unwrap().unwrap();
+            assert_eq!(layout, expected_layout);
+            let args = MigrateInstructionArgs::try_from_slice(&ix.data[8..]).unwrap();
+            assert_eq!(args.content_hash, hash(content).to_bytes(), "{}", expected_layout);
+        }
+
+        // Current accounts are left alone
+        let current = encode_data(content, &DataFormat::default()).unwrap();
+        let client = client_with_account(&metadata, &METADATA_PROGRAM_ID, metadata_account_data(&current));
+        assert!(client.migrate_instruction(&authority, &metadata, &program_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn attest_does_not_close_an_attestation_started_by_another_payer() {
+        let (metadata, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let stored_data = encode_data(b"{}", &DataFormat::default()).unwrap();
+        let hash_state = MetadataExecutableHash {
+            discriminator: account_discriminator("MetadataExecutableHash"),
+            payer,
+            version: 1,
+            program_data_slot: 1,
+            executable_len: 1024,
+            offset: 0,
+            digest: [0; 32],
+        };
+        let client = client_with_accounts(vec![
+            (metadata, account(&METADATA_PROGRAM_ID, metadata_account_data(&stored_data))),
+            (get_executable_hash_address(&metadata), account(&METADATA_PROGRAM_ID, borsh::to_vec(&hash_state).unwrap())),
+        ]);
+        let result = client.attest(&Keypair::new(), &metadata, None);
+        assert!(matches!(
+            result,
+            Err(Error::AttestationInProgress { metadata: m, payer: p }) if m == metadata && p == payer
+        ));
+    }
+}
@@ -12,16 +12,17 @@ use borsh::BorshDeserialize;
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct IdlAccount {
+pub struct MetadataAccount {
 pub discriminator: [u8; 8],
 #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
 pub authority: Pubkey,
+pub data_type: [u8; 16],
 pub data_len: u32,
 }
 
 
-impl IdlAccount {
-      pub const LEN: usize = 44;
+impl MetadataAccount {
+      pub const LEN: usize = 60;
   
   
   
@@ -32,7 +33,7 @@ impl IdlAccount {
   }
 }
 
-impl<'a> TryFrom<&solana_program::account_info::AccountInfo<'a>> for IdlAccount {
+impl<'a> TryFrom<&solana_program::account_info::AccountInfo<'a>> for MetadataAccount {
   type Error = std::io::Error;
 
   fn try_from(account_info: &solana_program::account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
@@ -42,28 +43,28 @@ impl<'a> TryFrom<&solana_program::account_info::AccountInfo<'a>> for IdlAccount
 }
 
   #[cfg(feature = "anchor")]
-  impl anchor_lang::AccountDeserialize for IdlAccount {
+  impl anchor_lang::AccountDeserialize for MetadataAccount {
       fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
         Ok(Self::deserialize(buf)?)
       }
   }
 
   #[cfg(feature = "anchor")]
-  impl anchor_lang::AccountSerialize for IdlAccount {}
+  impl anchor_lang::AccountSerialize for MetadataAccount {}
 
   #[cfg(feature = "anchor")]
-  impl anchor_lang::Owner for IdlAccount {
+  impl anchor_lang::Owner for MetadataAccount {
       fn owner() -> Pubkey {
         crate::METADATA_PROGRAM_ID
       }
   }
 
   #[cfg(feature = "anchor-idl-build")]
-  impl anchor_lang::IdlBuild for IdlAccount {}
+  impl anchor_lang::IdlBuild for MetadataAccount {}
 
   
   #[cfg(feature = "anchor-idl-build")]
-  impl anchor_lang::Discriminator for IdlAccount {
-    const DISCRIMINATOR: [u8; 8] = [0; 8];
+  impl anchor_lang::Discriminator for MetadataAccount {
+    const DISCRIMINATOR: [u8; 8] = [32, 224, 226, 224, 77, 64, 109, 234];
   }
 
@@ -9,32 +9,47 @@ use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 
 /// Accounts.
-pub struct CreateBuffer {
+pub struct Freeze {
       
               
-          pub buffer: solana_program::pubkey::Pubkey,
+          pub pda: solana_program::pubkey::Pubkey,
           
               
           pub authority: solana_program::pubkey::Pubkey,
+          
+              
+    
+          pub multisig: Option<solana_program::pubkey::Pubkey>,
       }
 
-impl CreateBuffer {
+impl Freeze {
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
     self.instruction_with_remaining_accounts(&[])
   }
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
-    let mut accounts = Vec::with_capacity(2+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(3+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            self.buffer,
+            self.pda,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.authority,
             true
           ));
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                multisig,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       accounts.extend_from_slice(remaining_accounts);
-    let data = CreateBufferInstructionData::new().try_to_vec().unwrap();
+    let data = FreezeInstructionData::new().try_to_vec().unwrap();
     
     solana_program::instruction::Instruction {
       program_id: crate::METADATA_PROGRAM_ID,
@@ -45,19 +60,19 @@ impl CreateBuffer {
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
-pub struct CreateBufferInstructionData {
+pub struct FreezeInstructionData {
             discriminator: [u8; 8],
       }
 
-impl CreateBufferInstructionData {
+impl FreezeInstructionData {
   pub fn new() -> Self {
     Self {
-                        discriminator: [175, 76, 101, 74, 224, 249, 104, 170],
+                        discriminator: [255, 91, 207, 84, 251, 194, 254, 63],
                   }
   }
 }
 
-impl Default for CreateBufferInstructionData {
+impl Default for FreezeInstructionData {
   fn default() -> Self {
     Self::new()
   }
@@ -65,32 +80,40 @@ impl Default for CreateBufferInstructionData {
 
 
 
-/// Instruction builder for `CreateBuffer`.
+/// Instruction builder for `Freeze`.
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` buffer
+                ///   0. `[writable]` pda
                 ///   1. `[signer]` authority
+                ///   2. `[optional]` multisig
 #[derive(Clone, Debug, Default)]
-pub struct CreateBufferBuilder {
-            buffer: Option<solana_program::pubkey::Pubkey>,
+pub struct FreezeBuilder {
+            pda: Option<solana_program::pubkey::Pubkey>,
                 authority: Option<solana_program::pubkey::Pubkey>,
+                multisig: Option<solana_program::pubkey::Pubkey>,
                 __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
-impl CreateBufferBuilder {
+impl FreezeBuilder {
   pub fn new() -> Self {
     Self::default()
   }
             #[inline(always)]
-    pub fn buffer(&mut self, buffer: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.buffer = Some(buffer);
+    pub fn pda(&mut self, pda: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.pda = Some(pda);
                     self
     }
             #[inline(always)]
     pub fn authority(&mut self, authority: solana_program::pubkey::Pubkey) -> &mut Self {
                         self.authority = Some(authority);
                     self
+    }
+            /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+                        self.multisig = multisig;
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -106,46 +129,54 @@ impl CreateBufferBuilder {
   }
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
-    let accounts = CreateBuffer {
-                              buffer: self.buffer.expect("buffer is not set"),
+    let accounts = Freeze {
+                              pda: self.pda.expect("pda is not set"),
                                         authority: self.authority.expect("authority is not set"),
+                                        multisig: self.multisig,
                       };
     
     accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
   }
 }
 
-  /// `create_buffer` CPI accounts.
-  pub struct CreateBufferCpiAccounts<'a, 'b> {
+  /// `freeze` CPI accounts.
+  pub struct FreezeCpiAccounts<'a, 'b> {
           
                     
-              pub buffer: &'b solana_program::account_info::AccountInfo<'a>,
+              pub pda: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub authority: &'b solana_program::account_info::AccountInfo<'a>,
+                
+                    
+              pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             }
 
-/// `create_buffer` CPI instruction.
-pub struct CreateBufferCpi<'a, 'b> {
+/// `freeze` CPI instruction.
+pub struct FreezeCpi<'a, 'b> {
   /// The program to invoke.
   pub __program: &'b solana_program::account_info::AccountInfo<'a>,
       
               
-          pub buffer: &'b solana_program::account_info::AccountInfo<'a>,
+          pub pda: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub authority: &'b solana_program::account_info::AccountInfo<'a>,
+          
+              
+          pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
         }
 
-impl<'a, 'b> CreateBufferCpi<'a, 'b> {
+impl<'a, 'b> FreezeCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_program::account_info::AccountInfo<'a>,
-          accounts: CreateBufferCpiAccounts<'a, 'b>,
+          accounts: FreezeCpiAccounts<'a, 'b>,
           ) -> Self {
     Self {
       __program: program,
-              buffer: accounts.buffer,
+              pda: accounts.pda,
               authority: accounts.authority,
+              multisig: accounts.multisig,
                 }
   }
   #[inline(always)]
@@ -167,15 +198,26 @@ impl<'a, 'b> CreateBufferCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program::entrypoint::ProgramResult {
-    let mut accounts = Vec::with_capacity(2+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(3+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.buffer.key,
+            *self.pda.key,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.authority.key,
             true
           ));
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *multisig.key,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_program::instruction::AccountMeta {
           pubkey: *remaining_account.0.key,
@@ -183,17 +225,20 @@ impl<'a, 'b> CreateBufferCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let data = CreateBufferInstructionData::new().try_to_vec().unwrap();
+    let data = FreezeInstructionData::new().try_to_vec().unwrap();
     
     let instruction = solana_program::instruction::Instruction {
       program_id: crate::METADATA_PROGRAM_ID,
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(3 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(4 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
-                  account_infos.push(self.buffer.clone());
+                  account_infos.push(self.pda.clone());
                         account_infos.push(self.authority.clone());
+                        if let Some(multisig) = self.multisig {
+          account_infos.push(multisig.clone());
+        }
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -204,36 +249,44 @@ impl<'a, 'b> CreateBufferCpi<'a, 'b> {
   }
 }
 
-/// Instruction builder for `CreateBuffer` via CPI.
+/// Instruction builder for `Freeze` via CPI.
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` buffer
+                ///   0. `[writable]` pda
                 ///   1. `[signer]` authority
+                ///   2. `[optional]` multisig
 #[derive(Clone, Debug)]
-pub struct CreateBufferCpiBuilder<'a, 'b> {
-  instruction: Box<CreateBufferCpiBuilderInstruction<'a, 'b>>,
+pub struct FreezeCpiBuilder<'a, 'b> {
+  instruction: Box<FreezeCpiBuilderInstruction<'a, 'b>>,
 }
 
-impl<'a, 'b> CreateBufferCpiBuilder<'a, 'b> {
+impl<'a, 'b> FreezeCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
-    let instruction = Box::new(CreateBufferCpiBuilderInstruction {
+    let instruction = Box::new(FreezeCpiBuilderInstruction {
       __program: program,
-              buffer: None,
+              pda: None,
               authority: None,
+              multisig: None,
                                 __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
       #[inline(always)]
-    pub fn buffer(&mut self, buffer: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.buffer = Some(buffer);
+    pub fn pda(&mut self, pda: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.pda = Some(pda);
                     self
     }
       #[inline(always)]
     pub fn authority(&mut self, authority: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.authority = Some(authority);
                     self
+    }
+      /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>) -> &mut Self {
+                        self.instruction.multisig = multisig;
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -257,22 +310,25 @@ impl<'a, 'b> CreateBufferCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program::entrypoint::ProgramResult {
-        let instruction = CreateBufferCpi {
+        let instruction = FreezeCpi {
         __program: self.instruction.__program,
                   
-          buffer: self.instruction.buffer.expect("buffer is not set"),
+          pda: self.instruction.pda.expect("pda is not set"),
                   
           authority: self.instruction.authority.expect("authority is not set"),
+                  
+          multisig: self.instruction.multisig,
                     };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
   }
 }
 
 #[derive(Clone, Debug)]
-struct CreateBufferCpiBuilderInstruction<'a, 'b> {
+struct FreezeCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_program::account_info::AccountInfo<'a>,
-            buffer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+            pda: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -7,12 +7,16 @@
 
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
+use crate::codama_sdk::types::Encoding;
+use crate::codama_sdk::types::Compression;
+use crate::codama_sdk::types::Format;
+use crate::codama_sdk::types::DataSource;
 
 /// Accounts.
 pub struct Initialize {
       
               
-          pub idl: solana_program::pubkey::Pubkey,
+          pub pda: solana_program::pubkey::Pubkey,
           
               
           pub signer: solana_program::pubkey::Pubkey,
@@ -24,7 +28,8 @@ pub struct Initialize {
           pub program_id: solana_program::pubkey::Pubkey,
           
               
-          pub program_data: solana_program::pubkey::Pubkey,
+    
+          pub program_data: Option<solana_program::pubkey::Pubkey>,
       }
 
 impl Initialize {
@@ -35,7 +40,7 @@ impl Initialize {
   pub fn instruction_with_remaining_accounts(&self, args: InitializeInstructionArgs, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
     let mut accounts = Vec::with_capacity(5+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            self.idl,
+            self.pda,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
@@ -50,10 +55,17 @@ impl Initialize {
             self.program_id,
             false
           ));
-                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
-            self.program_data,
-            false
-          ));
+                                          if let Some(program_data) = self.program_data {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                program_data,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       accounts.extend_from_slice(remaining_accounts);
     let mut data = InitializeInstructionData::new().try_to_vec().unwrap();
           let mut args = args.try_to_vec().unwrap();
@@ -70,13 +82,13 @@ impl Initialize {
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct InitializeInstructionData {
             discriminator: [u8; 8],
-            }
+                                    }
 
 impl InitializeInstructionData {
   pub fn new() -> Self {
     Self {
                         discriminator: [175, 175, 109, 31, 13, 152, 155, 237],
-                                }
+                                                                                        }
   }
 }
 
@@ -90,6 +102,10 @@ impl Default for InitializeInstructionData {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InitializeInstructionArgs {
                   pub seed: String,
+                pub encoding: Encoding,
+                pub compression: Compression,
+                pub format: Format,
+                pub data_source: DataSource,
       }
 
 
@@ -97,19 +113,23 @@ pub struct InitializeInstructionArgs {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                       ///   1. `[writable, signer]` signer
                 ///   2. `[optional]` system_program (default to `11111111111111111111111111111111`)
           ///   3. `[]` program_id
-          ///   4. `[]` program_data
+                ///   4. `[optional]` program_data
 #[derive(Clone, Debug, Default)]
 pub struct InitializeBuilder {
-            idl: Option<solana_program::pubkey::Pubkey>,
+            pda: Option<solana_program::pubkey::Pubkey>,
                 signer: Option<solana_program::pubkey::Pubkey>,
                 system_program: Option<solana_program::pubkey::Pubkey>,
                 program_id: Option<solana_program::pubkey::Pubkey>,
                 program_data: Option<solana_program::pubkey::Pubkey>,
                         seed: Option<String>,
+                encoding: Option<Encoding>,
+                compression: Option<Compression>,
+                format: Option<Format>,
+                data_source: Option<DataSource>,
         __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -118,8 +138,8 @@ impl InitializeBuilder {
     Self::default()
   }
             #[inline(always)]
-    pub fn idl(&mut self, idl: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.idl = Some(idl);
+    pub fn pda(&mut self, pda: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.pda = Some(pda);
                     self
     }
             #[inline(always)]
@@ -138,15 +158,36 @@ impl InitializeBuilder {
                         self.program_id = Some(program_id);
                     self
     }
-            #[inline(always)]
-    pub fn program_data(&mut self, program_data: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.program_data = Some(program_data);
+            /// `[optional account]`
+#[inline(always)]
+    pub fn program_data(&mut self, program_data: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+                        self.program_data = program_data;
                     self
     }
                     #[inline(always)]
       pub fn seed(&mut self, seed: String) -> &mut Self {
         self.seed = Some(seed);
         self
+      }
+                #[inline(always)]
+      pub fn encoding(&mut self, encoding: Encoding) -> &mut Self {
+        self.encoding = Some(encoding);
+        self
+      }
+                #[inline(always)]
+      pub fn compression(&mut self, compression: Compression) -> &mut Self {
+        self.compression = Some(compression);
+        self
+      }
+                #[inline(always)]
+      pub fn format(&mut self, format: Format) -> &mut Self {
+        self.format = Some(format);
+        self
+      }
+                #[inline(always)]
+      pub fn data_source(&mut self, data_source: DataSource) -> &mut Self {
+        self.data_source = Some(data_source);
+        self
       }
         /// Add an additional account to the instruction.
   #[inline(always)]
@@ -163,14 +204,18 @@ impl InitializeBuilder {
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
     let accounts = Initialize {
-                              idl: self.idl.expect("idl is not set"),
+                              pda: self.pda.expect("pda is not set"),
                                         signer: self.signer.expect("signer is not set"),
                                         system_program: self.system_program.unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
                                         program_id: self.program_id.expect("program_id is not set"),
-                                        program_data: self.program_data.expect("program_data is not set"),
+                                        program_data: self.program_data,
                       };
           let args = InitializeInstructionArgs {
                                                               seed: self.seed.clone().expect("seed is not set"),
+                                                                  encoding: self.encoding.clone().expect("encoding is not set"),
+                                                                  compression: self.compression.clone().expect("compression is not set"),
+                                                                  format: self.format.clone().expect("format is not set"),
+                                                                  data_source: self.data_source.clone().expect("data_source is not set"),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -181,7 +226,7 @@ impl InitializeBuilder {
   pub struct InitializeCpiAccounts<'a, 'b> {
           
                     
-              pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+              pub pda: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub signer: &'b solana_program::account_info::AccountInfo<'a>,
@@ -193,7 +238,7 @@ impl InitializeBuilder {
               pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
-              pub program_data: &'b solana_program::account_info::AccountInfo<'a>,
+              pub program_data: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             }
 
 /// `initialize` CPI instruction.
@@ -202,7 +247,7 @@ pub struct InitializeCpi<'a, 'b> {
   pub __program: &'b solana_program::account_info::AccountInfo<'a>,
       
               
-          pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+          pub pda: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub signer: &'b solana_program::account_info::AccountInfo<'a>,
@@ -214,7 +259,7 @@ pub struct InitializeCpi<'a, 'b> {
           pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
           
               
-          pub program_data: &'b solana_program::account_info::AccountInfo<'a>,
+          pub program_data: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             /// The arguments for the instruction.
     pub __args: InitializeInstructionArgs,
   }
@@ -227,7 +272,7 @@ impl<'a, 'b> InitializeCpi<'a, 'b> {
       ) -> Self {
     Self {
       __program: program,
-              idl: accounts.idl,
+              pda: accounts.pda,
               signer: accounts.signer,
               system_program: accounts.system_program,
               program_id: accounts.program_id,
@@ -256,7 +301,7 @@ impl<'a, 'b> InitializeCpi<'a, 'b> {
   ) -> solana_program::entrypoint::ProgramResult {
     let mut accounts = Vec::with_capacity(5+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.idl.key,
+            *self.pda.key,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
@@ -271,10 +316,17 @@ impl<'a, 'b> InitializeCpi<'a, 'b> {
             *self.program_id.key,
             false
           ));
-                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
-            *self.program_data.key,
-            false
-          ));
+                                          if let Some(program_data) = self.program_data {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *program_data.key,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_program::instruction::AccountMeta {
           pubkey: *remaining_account.0.key,
@@ -293,11 +345,13 @@ impl<'a, 'b> InitializeCpi<'a, 'b> {
     };
     let mut account_infos = Vec::with_capacity(6 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
-                  account_infos.push(self.idl.clone());
+                  account_infos.push(self.pda.clone());
                         account_infos.push(self.signer.clone());
                         account_infos.push(self.system_program.clone());
                         account_infos.push(self.program_id.clone());
-                        account_infos.push(self.program_data.clone());
+                        if let Some(program_data) = self.program_data {
+          account_infos.push(program_data.clone());
+        }
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -312,11 +366,11 @@ impl<'a, 'b> InitializeCpi<'a, 'b> {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                       ///   1. `[writable, signer]` signer
           ///   2. `[]` system_program
           ///   3. `[]` program_id
-          ///   4. `[]` program_data
+                ///   4. `[optional]` program_data
 #[derive(Clone, Debug)]
 pub struct InitializeCpiBuilder<'a, 'b> {
   instruction: Box<InitializeCpiBuilderInstruction<'a, 'b>>,
@@ -326,19 +380,23 @@ impl<'a, 'b> InitializeCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
     let instruction = Box::new(InitializeCpiBuilderInstruction {
       __program: program,
-              idl: None,
+              pda: None,
               signer: None,
               system_program: None,
               program_id: None,
               program_data: None,
                                             seed: None,
+                                encoding: None,
+                                compression: None,
+                                format: None,
+                                data_source: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
       #[inline(always)]
-    pub fn idl(&mut self, idl: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.idl = Some(idl);
+    pub fn pda(&mut self, pda: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.pda = Some(pda);
                     self
     }
       #[inline(always)]
@@ -356,15 +414,36 @@ impl<'a, 'b> InitializeCpiBuilder<'a, 'b> {
                         self.instruction.program_id = Some(program_id);
                     self
     }
-      #[inline(always)]
-    pub fn program_data(&mut self, program_data: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.program_data = Some(program_data);
+      /// `[optional account]`
+#[inline(always)]
+    pub fn program_data(&mut self, program_data: Option<&'b solana_program::account_info::AccountInfo<'a>>) -> &mut Self {
+                        self.instruction.program_data = program_data;
                     self
     }
                     #[inline(always)]
       pub fn seed(&mut self, seed: String) -> &mut Self {
         self.instruction.seed = Some(seed);
         self
+      }
+                #[inline(always)]
+      pub fn encoding(&mut self, encoding: Encoding) -> &mut Self {
+        self.instruction.encoding = Some(encoding);
+        self
+      }
+                #[inline(always)]
+      pub fn compression(&mut self, compression: Compression) -> &mut Self {
+        self.instruction.compression = Some(compression);
+        self
+      }
+                #[inline(always)]
+      pub fn format(&mut self, format: Format) -> &mut Self {
+        self.instruction.format = Some(format);
+        self
+      }
+                #[inline(always)]
+      pub fn data_source(&mut self, data_source: DataSource) -> &mut Self {
+        self.instruction.data_source = Some(data_source);
+        self
       }
         /// Add an additional account to the instruction.
   #[inline(always)]
@@ -390,11 +469,15 @@ impl<'a, 'b> InitializeCpiBuilder<'a, 'b> {
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program::entrypoint::ProgramResult {
           let args = InitializeInstructionArgs {
                                                               seed: self.instruction.seed.clone().expect("seed is not set"),
+                                                                  encoding: self.instruction.encoding.clone().expect("encoding is not set"),
+                                                                  compression: self.instruction.compression.clone().expect("compression is not set"),
+                                                                  format: self.instruction.format.clone().expect("format is not set"),
+                                                                  data_source: self.instruction.data_source.clone().expect("data_source is not set"),
                                     };
         let instruction = InitializeCpi {
         __program: self.instruction.__program,
                   
-          idl: self.instruction.idl.expect("idl is not set"),
+          pda: self.instruction.pda.expect("pda is not set"),
                   
           signer: self.instruction.signer.expect("signer is not set"),
                   
@@ -402,7 +485,7 @@ impl<'a, 'b> InitializeCpiBuilder<'a, 'b> {
                   
           program_id: self.instruction.program_id.expect("program_id is not set"),
                   
-          program_data: self.instruction.program_data.expect("program_data is not set"),
+          program_data: self.instruction.program_data,
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -412,12 +495,16 @@ impl<'a, 'b> InitializeCpiBuilder<'a, 'b> {
 #[derive(Clone, Debug)]
 struct InitializeCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_program::account_info::AccountInfo<'a>,
-            idl: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+            pda: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 signer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 program_id: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 program_data: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                         seed: Option<String>,
+                encoding: Option<Encoding>,
+                compression: Option<Compression>,
+                format: Option<Format>,
+                data_source: Option<DataSource>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -9,46 +9,46 @@ use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 
 /// Accounts.
-pub struct SetBuffer {
+pub struct Migrate {
       
               
-          pub buffer: solana_program::pubkey::Pubkey,
-          
-              
-          pub idl: solana_program::pubkey::Pubkey,
+          pub pda: solana_program::pubkey::Pubkey,
           
               
           pub authority: solana_program::pubkey::Pubkey,
           
               
           pub program_id: solana_program::pubkey::Pubkey,
+          
+              
+          pub system_program: solana_program::pubkey::Pubkey,
       }
 
-impl SetBuffer {
-  pub fn instruction(&self, args: SetBufferInstructionArgs) -> solana_program::instruction::Instruction {
+impl Migrate {
+  pub fn instruction(&self, args: MigrateInstructionArgs) -> solana_program::instruction::Instruction {
     self.instruction_with_remaining_accounts(args, &[])
   }
   #[allow(clippy::vec_init_then_push)]
-  pub fn instruction_with_remaining_accounts(&self, args: SetBufferInstructionArgs, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
+  pub fn instruction_with_remaining_accounts(&self, args: MigrateInstructionArgs, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
     let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            self.buffer,
+            self.pda,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
-            self.idl,
-            false
-          ));
-                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.authority,
             true
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.program_id,
             false
+          ));
+                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.system_program,
+            false
           ));
                       accounts.extend_from_slice(remaining_accounts);
-    let mut data = SetBufferInstructionData::new().try_to_vec().unwrap();
+    let mut data = MigrateInstructionData::new().try_to_vec().unwrap();
           let mut args = args.try_to_vec().unwrap();
       data.append(&mut args);
     
@@ -61,19 +61,19 @@ impl SetBuffer {
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
-pub struct SetBufferInstructionData {
+pub struct MigrateInstructionData {
             discriminator: [u8; 8],
             }
 
-impl SetBufferInstructionData {
+impl MigrateInstructionData {
   pub fn new() -> Self {
     Self {
-                        discriminator: [13, 212, 241, 0, 78, 93, 17, 51],
+                        discriminator: [155, 234, 231, 146, 236, 158, 162, 30],
                                 }
   }
 }
 
-impl Default for SetBufferInstructionData {
+impl Default for MigrateInstructionData {
   fn default() -> Self {
     Self::new()
   }
@@ -81,41 +81,36 @@ impl Default for SetBufferInstructionData {
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct SetBufferInstructionArgs {
-                  pub seed: String,
+pub struct MigrateInstructionArgs {
+                  pub content_hash: [u8; 32],
       }
 
 
-/// Instruction builder for `SetBuffer`.
+/// Instruction builder for `Migrate`.
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` buffer
-                ///   1. `[writable]` idl
-                ///   2. `[signer]` authority
-          ///   3. `[]` program_id
+                ///   0. `[writable]` pda
+                      ///   1. `[writable, signer]` authority
+          ///   2. `[]` program_id
+                ///   3. `[optional]` system_program (default to `11111111111111111111111111111111`)
 #[derive(Clone, Debug, Default)]
-pub struct SetBufferBuilder {
-            buffer: Option<solana_program::pubkey::Pubkey>,
-                idl: Option<solana_program::pubkey::Pubkey>,
+pub struct MigrateBuilder {
+            pda: Option<solana_program::pubkey::Pubkey>,
                 authority: Option<solana_program::pubkey::Pubkey>,
                 program_id: Option<solana_program::pubkey::Pubkey>,
-                        seed: Option<String>,
+                system_program: Option<solana_program::pubkey::Pubkey>,
+                        content_hash: Option<[u8; 32]>,
         __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
-impl SetBufferBuilder {
+impl MigrateBuilder {
   pub fn new() -> Self {
     Self::default()
   }
             #[inline(always)]
-    pub fn buffer(&mut self, buffer: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.buffer = Some(buffer);
-                    self
-    }
-            #[inline(always)]
-    pub fn idl(&mut self, idl: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.idl = Some(idl);
+    pub fn pda(&mut self, pda: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.pda = Some(pda);
                     self
     }
             #[inline(always)]
@@ -127,10 +122,16 @@ impl SetBufferBuilder {
     pub fn program_id(&mut self, program_id: solana_program::pubkey::Pubkey) -> &mut Self {
                         self.program_id = Some(program_id);
                     self
+    }
+            /// `[optional account, default to '11111111111111111111111111111111']`
+#[inline(always)]
+    pub fn system_program(&mut self, system_program: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.system_program = Some(system_program);
+                    self
     }
                     #[inline(always)]
-      pub fn seed(&mut self, seed: String) -> &mut Self {
-        self.seed = Some(seed);
+      pub fn content_hash(&mut self, content_hash: [u8; 32]) -> &mut Self {
+        self.content_hash = Some(content_hash);
         self
       }
         /// Add an additional account to the instruction.
@@ -147,68 +148,68 @@ impl SetBufferBuilder {
   }
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
-    let accounts = SetBuffer {
-                              buffer: self.buffer.expect("buffer is not set"),
-                                        idl: self.idl.expect("idl is not set"),
+    let accounts = Migrate {
+                              pda: self.pda.expect("pda is not set"),
                                         authority: self.authority.expect("authority is not set"),
                                         program_id: self.program_id.expect("program_id is not set"),
+                                        system_program: self.system_program.unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
                       };
-          let args = SetBufferInstructionArgs {
-                                                              seed: self.seed.clone().expect("seed is not set"),
+          let args = MigrateInstructionArgs {
+                                                              content_hash: self.content_hash.clone().expect("content_hash is not set"),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
   }
 }
 
-  /// `set_buffer` CPI accounts.
-  pub struct SetBufferCpiAccounts<'a, 'b> {
+  /// `migrate` CPI accounts.
+  pub struct MigrateCpiAccounts<'a, 'b> {
           
                     
-              pub buffer: &'b solana_program::account_info::AccountInfo<'a>,
-                
-                    
-              pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+              pub pda: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub authority: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
+                
+                    
+              pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
             }
 
-/// `set_buffer` CPI instruction.
-pub struct SetBufferCpi<'a, 'b> {
+/// `migrate` CPI instruction.
+pub struct MigrateCpi<'a, 'b> {
   /// The program to invoke.
   pub __program: &'b solana_program::account_info::AccountInfo<'a>,
       
               
-          pub buffer: &'b solana_program::account_info::AccountInfo<'a>,
-          
-              
-          pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+          pub pda: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub authority: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
+          
+              
+          pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
             /// The arguments for the instruction.
-    pub __args: SetBufferInstructionArgs,
+    pub __args: MigrateInstructionArgs,
   }
 
-impl<'a, 'b> SetBufferCpi<'a, 'b> {
+impl<'a, 'b> MigrateCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_program::account_info::AccountInfo<'a>,
-          accounts: SetBufferCpiAccounts<'a, 'b>,
-              args: SetBufferInstructionArgs,
+          accounts: MigrateCpiAccounts<'a, 'b>,
+              args: MigrateInstructionArgs,
       ) -> Self {
     Self {
       __program: program,
-              buffer: accounts.buffer,
-              idl: accounts.idl,
+              pda: accounts.pda,
               authority: accounts.authority,
               program_id: accounts.program_id,
+              system_program: accounts.system_program,
                     __args: args,
           }
   }
@@ -233,20 +234,20 @@ impl<'a, 'b> SetBufferCpi<'a, 'b> {
   ) -> solana_program::entrypoint::ProgramResult {
     let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.buffer.key,
+            *self.pda.key,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.idl.key,
-            false
-          ));
-                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.authority.key,
             true
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.program_id.key,
             false
+          ));
+                                          accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.system_program.key,
+            false
           ));
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_program::instruction::AccountMeta {
@@ -255,7 +256,7 @@ impl<'a, 'b> SetBufferCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let mut data = SetBufferInstructionData::new().try_to_vec().unwrap();
+    let mut data = MigrateInstructionData::new().try_to_vec().unwrap();
           let mut args = self.__args.try_to_vec().unwrap();
       data.append(&mut args);
     
@@ -266,10 +267,10 @@ impl<'a, 'b> SetBufferCpi<'a, 'b> {
     };
     let mut account_infos = Vec::with_capacity(5 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
-                  account_infos.push(self.buffer.clone());
-                        account_infos.push(self.idl.clone());
+                  account_infos.push(self.pda.clone());
                         account_infos.push(self.authority.clone());
                         account_infos.push(self.program_id.clone());
+                        account_infos.push(self.system_program.clone());
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -280,40 +281,35 @@ impl<'a, 'b> SetBufferCpi<'a, 'b> {
   }
 }
 
-/// Instruction builder for `SetBuffer` via CPI.
+/// Instruction builder for `Migrate` via CPI.
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` buffer
-                ///   1. `[writable]` idl
-                ///   2. `[signer]` authority
-          ///   3. `[]` program_id
+                ///   0. `[writable]` pda
+                      ///   1. `[writable, signer]` authority
+          ///   2. `[]` program_id
+          ///   3. `[]` system_program
 #[derive(Clone, Debug)]
-pub struct SetBufferCpiBuilder<'a, 'b> {
-  instruction: Box<SetBufferCpiBuilderInstruction<'a, 'b>>,
+pub struct MigrateCpiBuilder<'a, 'b> {
+  instruction: Box<MigrateCpiBuilderInstruction<'a, 'b>>,
 }
 
-impl<'a, 'b> SetBufferCpiBuilder<'a, 'b> {
+impl<'a, 'b> MigrateCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
-    let instruction = Box::new(SetBufferCpiBuilderInstruction {
+    let instruction = Box::new(MigrateCpiBuilderInstruction {
       __program: program,
-              buffer: None,
-              idl: None,
+              pda: None,
               authority: None,
               program_id: None,
-                                            seed: None,
+              system_program: None,
+                                            content_hash: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
       #[inline(always)]
-    pub fn buffer(&mut self, buffer: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.buffer = Some(buffer);
-                    self
-    }
-      #[inline(always)]
-    pub fn idl(&mut self, idl: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.idl = Some(idl);
+    pub fn pda(&mut self, pda: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.pda = Some(pda);
                     self
     }
       #[inline(always)]
@@ -325,10 +321,15 @@ impl<'a, 'b> SetBufferCpiBuilder<'a, 'b> {
     pub fn program_id(&mut self, program_id: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.program_id = Some(program_id);
                     self
+    }
+      #[inline(always)]
+    pub fn system_program(&mut self, system_program: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.system_program = Some(system_program);
+                    self
     }
                     #[inline(always)]
-      pub fn seed(&mut self, seed: String) -> &mut Self {
-        self.instruction.seed = Some(seed);
+      pub fn content_hash(&mut self, content_hash: [u8; 32]) -> &mut Self {
+        self.instruction.content_hash = Some(content_hash);
         self
       }
         /// Add an additional account to the instruction.
@@ -353,19 +354,19 @@ impl<'a, 'b> SetBufferCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program::entrypoint::ProgramResult {
-          let args = SetBufferInstructionArgs {
-                                                              seed: self.instruction.seed.clone().expect("seed is not set"),
+          let args = MigrateInstructionArgs {
+                                                              content_hash: self.instruction.content_hash.clone().expect("content_hash is not set"),
                                     };
-        let instruction = SetBufferCpi {
+        let instruction = MigrateCpi {
         __program: self.instruction.__program,
                   
-          buffer: self.instruction.buffer.expect("buffer is not set"),
-                  
-          idl: self.instruction.idl.expect("idl is not set"),
+          pda: self.instruction.pda.expect("pda is not set"),
                   
           authority: self.instruction.authority.expect("authority is not set"),
                   
           program_id: self.instruction.program_id.expect("program_id is not set"),
+                  
+          system_program: self.instruction.system_program.expect("system_program is not set"),
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -373,13 +374,13 @@ impl<'a, 'b> SetBufferCpiBuilder<'a, 'b> {
 }
 
 #[derive(Clone, Debug)]
-struct SetBufferCpiBuilderInstruction<'a, 'b> {
+struct MigrateCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_program::account_info::AccountInfo<'a>,
-            buffer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-                idl: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+            pda: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 program_id: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-                        seed: Option<String>,
+                system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                        content_hash: Option<[u8; 32]>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
 }
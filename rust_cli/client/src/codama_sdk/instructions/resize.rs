@@ -7,12 +7,13 @@
 
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
 
 /// Accounts.
 pub struct Resize {
       
               
-          pub idl: solana_program::pubkey::Pubkey,
+          pub pda: solana_program::pubkey::Pubkey,
           
               
           pub signer: solana_program::pubkey::Pubkey,
@@ -22,6 +23,14 @@ pub struct Resize {
           
               
           pub program_id: solana_program::pubkey::Pubkey,
+          
+              
+    
+          pub delegate_record: Option<solana_program::pubkey::Pubkey>,
+          
+              
+    
+          pub multisig: Option<solana_program::pubkey::Pubkey>,
       }
 
 impl Resize {
@@ -30,9 +39,9 @@ impl Resize {
   }
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, args: ResizeInstructionArgs, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
-    let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            self.idl,
+            self.pda,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
@@ -47,6 +56,28 @@ impl Resize {
             self.program_id,
             false
           ));
+                                          if let Some(delegate_record) = self.delegate_record {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                delegate_record,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                multisig,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       accounts.extend_from_slice(remaining_accounts);
     let mut data = ResizeInstructionData::new().try_to_vec().unwrap();
           let mut args = args.try_to_vec().unwrap();
@@ -82,8 +113,8 @@ impl Default for ResizeInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResizeInstructionArgs {
-                  pub len: u16,
-                pub seed: String,
+                  pub len: u32,
+                pub seed_signer: Option<Pubkey>,
       }
 
 
@@ -91,18 +122,22 @@ pub struct ResizeInstructionArgs {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                       ///   1. `[writable, signer]` signer
                 ///   2. `[optional]` system_program (default to `11111111111111111111111111111111`)
           ///   3. `[]` program_id
+                ///   4. `[optional]` delegate_record
+                ///   5. `[optional]` multisig
 #[derive(Clone, Debug, Default)]
 pub struct ResizeBuilder {
-            idl: Option<solana_program::pubkey::Pubkey>,
+            pda: Option<solana_program::pubkey::Pubkey>,
                 signer: Option<solana_program::pubkey::Pubkey>,
                 system_program: Option<solana_program::pubkey::Pubkey>,
                 program_id: Option<solana_program::pubkey::Pubkey>,
-                        len: Option<u16>,
-                seed: Option<String>,
+                delegate_record: Option<solana_program::pubkey::Pubkey>,
+                multisig: Option<solana_program::pubkey::Pubkey>,
+                        len: Option<u32>,
+                seed_signer: Option<Pubkey>,
         __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -111,8 +146,8 @@ impl ResizeBuilder {
     Self::default()
   }
             #[inline(always)]
-    pub fn idl(&mut self, idl: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.idl = Some(idl);
+    pub fn pda(&mut self, pda: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.pda = Some(pda);
                     self
     }
             #[inline(always)]
@@ -130,15 +165,28 @@ impl ResizeBuilder {
     pub fn program_id(&mut self, program_id: solana_program::pubkey::Pubkey) -> &mut Self {
                         self.program_id = Some(program_id);
                     self
+    }
+            /// `[optional account]`
+#[inline(always)]
+    pub fn delegate_record(&mut self, delegate_record: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+                        self.delegate_record = delegate_record;
+                    self
+    }
+            /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+                        self.multisig = multisig;
+                    self
     }
                     #[inline(always)]
-      pub fn len(&mut self, len: u16) -> &mut Self {
+      pub fn len(&mut self, len: u32) -> &mut Self {
         self.len = Some(len);
         self
       }
-                #[inline(always)]
-      pub fn seed(&mut self, seed: String) -> &mut Self {
-        self.seed = Some(seed);
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn seed_signer(&mut self, seed_signer: Pubkey) -> &mut Self {
+        self.seed_signer = Some(seed_signer);
         self
       }
         /// Add an additional account to the instruction.
@@ -156,14 +204,16 @@ impl ResizeBuilder {
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
     let accounts = Resize {
-                              idl: self.idl.expect("idl is not set"),
+                              pda: self.pda.expect("pda is not set"),
                                         signer: self.signer.expect("signer is not set"),
                                         system_program: self.system_program.unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
                                         program_id: self.program_id.expect("program_id is not set"),
+                                        delegate_record: self.delegate_record,
+                                        multisig: self.multisig,
                       };
           let args = ResizeInstructionArgs {
                                                               len: self.len.clone().expect("len is not set"),
-                                                                  seed: self.seed.clone().expect("seed is not set"),
+                                                                  seed_signer: self.seed_signer.clone(),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -174,7 +224,7 @@ impl ResizeBuilder {
   pub struct ResizeCpiAccounts<'a, 'b> {
           
                     
-              pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+              pub pda: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub signer: &'b solana_program::account_info::AccountInfo<'a>,
@@ -184,6 +234,12 @@ impl ResizeBuilder {
                 
                     
               pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
+                
+                    
+              pub delegate_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                
+                    
+              pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             }
 
 /// `resize` CPI instruction.
@@ -192,7 +248,7 @@ pub struct ResizeCpi<'a, 'b> {
   pub __program: &'b solana_program::account_info::AccountInfo<'a>,
       
               
-          pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+          pub pda: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub signer: &'b solana_program::account_info::AccountInfo<'a>,
@@ -202,6 +258,12 @@ pub struct ResizeCpi<'a, 'b> {
           
               
           pub program_id: &'b solana_program::account_info::AccountInfo<'a>,
+          
+              
+          pub delegate_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+          
+              
+          pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             /// The arguments for the instruction.
     pub __args: ResizeInstructionArgs,
   }
@@ -214,10 +276,12 @@ impl<'a, 'b> ResizeCpi<'a, 'b> {
       ) -> Self {
     Self {
       __program: program,
-              idl: accounts.idl,
+              pda: accounts.pda,
               signer: accounts.signer,
               system_program: accounts.system_program,
               program_id: accounts.program_id,
+              delegate_record: accounts.delegate_record,
+              multisig: accounts.multisig,
                     __args: args,
           }
   }
@@ -240,9 +304,9 @@ impl<'a, 'b> ResizeCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program::entrypoint::ProgramResult {
-    let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.idl.key,
+            *self.pda.key,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new(
@@ -257,6 +321,28 @@ impl<'a, 'b> ResizeCpi<'a, 'b> {
             *self.program_id.key,
             false
           ));
+                                          if let Some(delegate_record) = self.delegate_record {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *delegate_record.key,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *multisig.key,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_program::instruction::AccountMeta {
           pubkey: *remaining_account.0.key,
@@ -273,12 +359,18 @@ impl<'a, 'b> ResizeCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(5 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(7 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
-                  account_infos.push(self.idl.clone());
+                  account_infos.push(self.pda.clone());
                         account_infos.push(self.signer.clone());
                         account_infos.push(self.system_program.clone());
                         account_infos.push(self.program_id.clone());
+                        if let Some(delegate_record) = self.delegate_record {
+          account_infos.push(delegate_record.clone());
+        }
+                        if let Some(multisig) = self.multisig {
+          account_infos.push(multisig.clone());
+        }
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -293,10 +385,12 @@ impl<'a, 'b> ResizeCpi<'a, 'b> {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                       ///   1. `[writable, signer]` signer
           ///   2. `[]` system_program
           ///   3. `[]` program_id
+                ///   4. `[optional]` delegate_record
+                ///   5. `[optional]` multisig
 #[derive(Clone, Debug)]
 pub struct ResizeCpiBuilder<'a, 'b> {
   instruction: Box<ResizeCpiBuilderInstruction<'a, 'b>>,
@@ -306,19 +400,21 @@ impl<'a, 'b> ResizeCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
     let instruction = Box::new(ResizeCpiBuilderInstruction {
       __program: program,
-              idl: None,
+              pda: None,
               signer: None,
               system_program: None,
               program_id: None,
+              delegate_record: None,
+              multisig: None,
                                             len: None,
-                                seed: None,
+                                seed_signer: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
       #[inline(always)]
-    pub fn idl(&mut self, idl: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.idl = Some(idl);
+    pub fn pda(&mut self, pda: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.pda = Some(pda);
                     self
     }
       #[inline(always)]
@@ -335,15 +431,28 @@ impl<'a, 'b> ResizeCpiBuilder<'a, 'b> {
     pub fn program_id(&mut self, program_id: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.program_id = Some(program_id);
                     self
+    }
+      /// `[optional account]`
+#[inline(always)]
+    pub fn delegate_record(&mut self, delegate_record: Option<&'b solana_program::account_info::AccountInfo<'a>>) -> &mut Self {
+                        self.instruction.delegate_record = delegate_record;
+                    self
+    }
+      /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>) -> &mut Self {
+                        self.instruction.multisig = multisig;
+                    self
     }
                     #[inline(always)]
-      pub fn len(&mut self, len: u16) -> &mut Self {
+      pub fn len(&mut self, len: u32) -> &mut Self {
         self.instruction.len = Some(len);
         self
       }
-                #[inline(always)]
-      pub fn seed(&mut self, seed: String) -> &mut Self {
-        self.instruction.seed = Some(seed);
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn seed_signer(&mut self, seed_signer: Pubkey) -> &mut Self {
+        self.instruction.seed_signer = Some(seed_signer);
         self
       }
         /// Add an additional account to the instruction.
@@ -370,18 +479,22 @@ impl<'a, 'b> ResizeCpiBuilder<'a, 'b> {
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program::entrypoint::ProgramResult {
           let args = ResizeInstructionArgs {
                                                               len: self.instruction.len.clone().expect("len is not set"),
-                                                                  seed: self.instruction.seed.clone().expect("seed is not set"),
+                                                                  seed_signer: self.instruction.seed_signer.clone(),
                                     };
         let instruction = ResizeCpi {
         __program: self.instruction.__program,
                   
-          idl: self.instruction.idl.expect("idl is not set"),
+          pda: self.instruction.pda.expect("pda is not set"),
                   
           signer: self.instruction.signer.expect("signer is not set"),
                   
           system_program: self.instruction.system_program.expect("system_program is not set"),
                   
           program_id: self.instruction.program_id.expect("program_id is not set"),
+                  
+          delegate_record: self.instruction.delegate_record,
+                  
+          multisig: self.instruction.multisig,
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -391,12 +504,14 @@ impl<'a, 'b> ResizeCpiBuilder<'a, 'b> {
 #[derive(Clone, Debug)]
 struct ResizeCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_program::account_info::AccountInfo<'a>,
-            idl: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+            pda: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 signer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 program_id: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-                        len: Option<u16>,
-                seed: Option<String>,
+                delegate_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                        len: Option<u32>,
+                seed_signer: Option<Pubkey>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -13,10 +13,14 @@ use solana_program::pubkey::Pubkey;
 pub struct SetAuthority {
       
               
-          pub idl: solana_program::pubkey::Pubkey,
+          pub pda: solana_program::pubkey::Pubkey,
           
               
           pub authority: solana_program::pubkey::Pubkey,
+          
+              
+    
+          pub multisig: Option<solana_program::pubkey::Pubkey>,
       }
 
 impl SetAuthority {
@@ -25,15 +29,26 @@ impl SetAuthority {
   }
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, args: SetAuthorityInstructionArgs, remaining_accounts: &[solana_program::instruction::AccountMeta]) -> solana_program::instruction::Instruction {
-    let mut accounts = Vec::with_capacity(2+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(3+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            self.idl,
+            self.pda,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.authority,
             true
           ));
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                multisig,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       accounts.extend_from_slice(remaining_accounts);
     let mut data = SetAuthorityInstructionData::new().try_to_vec().unwrap();
           let mut args = args.try_to_vec().unwrap();
@@ -77,12 +92,14 @@ pub struct SetAuthorityInstructionArgs {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                 ///   1. `[signer]` authority
+                ///   2. `[optional]` multisig
 #[derive(Clone, Debug, Default)]
 pub struct SetAuthorityBuilder {
-            idl: Option<solana_program::pubkey::Pubkey>,
+            pda: Option<solana_program::pubkey::Pubkey>,
                 authority: Option<solana_program::pubkey::Pubkey>,
+                multisig: Option<solana_program::pubkey::Pubkey>,
                         new_authority: Option<Pubkey>,
         __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
@@ -92,14 +109,20 @@ impl SetAuthorityBuilder {
     Self::default()
   }
             #[inline(always)]
-    pub fn idl(&mut self, idl: solana_program::pubkey::Pubkey) -> &mut Self {
-                        self.idl = Some(idl);
+    pub fn pda(&mut self, pda: solana_program::pubkey::Pubkey) -> &mut Self {
+                        self.pda = Some(pda);
                     self
     }
             #[inline(always)]
     pub fn authority(&mut self, authority: solana_program::pubkey::Pubkey) -> &mut Self {
                         self.authority = Some(authority);
                     self
+    }
+            /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+                        self.multisig = multisig;
+                    self
     }
                     #[inline(always)]
       pub fn new_authority(&mut self, new_authority: Pubkey) -> &mut Self {
@@ -121,8 +144,9 @@ impl SetAuthorityBuilder {
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_program::instruction::Instruction {
     let accounts = SetAuthority {
-                              idl: self.idl.expect("idl is not set"),
+                              pda: self.pda.expect("pda is not set"),
                                         authority: self.authority.expect("authority is not set"),
+                                        multisig: self.multisig,
                       };
           let args = SetAuthorityInstructionArgs {
                                                               new_authority: self.new_authority.clone().expect("new_authority is not set"),
@@ -136,10 +160,13 @@ impl SetAuthorityBuilder {
   pub struct SetAuthorityCpiAccounts<'a, 'b> {
           
                     
-              pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+              pub pda: &'b solana_program::account_info::AccountInfo<'a>,
                 
                     
               pub authority: &'b solana_program::account_info::AccountInfo<'a>,
+                
+                    
+              pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             }
 
 /// `set_authority` CPI instruction.
@@ -148,10 +175,13 @@ pub struct SetAuthorityCpi<'a, 'b> {
   pub __program: &'b solana_program::account_info::AccountInfo<'a>,
       
               
-          pub idl: &'b solana_program::account_info::AccountInfo<'a>,
+          pub pda: &'b solana_program::account_info::AccountInfo<'a>,
           
               
           pub authority: &'b solana_program::account_info::AccountInfo<'a>,
+          
+              
+          pub multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
             /// The arguments for the instruction.
     pub __args: SetAuthorityInstructionArgs,
   }
@@ -164,8 +194,9 @@ impl<'a, 'b> SetAuthorityCpi<'a, 'b> {
       ) -> Self {
     Self {
       __program: program,
-              idl: accounts.idl,
+              pda: accounts.pda,
               authority: accounts.authority,
+              multisig: accounts.multisig,
                     __args: args,
           }
   }
@@ -188,15 +219,26 @@ impl<'a, 'b> SetAuthorityCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program::entrypoint::ProgramResult {
-    let mut accounts = Vec::with_capacity(2+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(3+ remaining_accounts.len());
                             accounts.push(solana_program::instruction::AccountMeta::new(
-            *self.idl.key,
+            *self.pda.key,
             false
           ));
                                           accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.authority.key,
             true
           ));
+                                          if let Some(multisig) = self.multisig {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *multisig.key,
+                false,
+              ));
+            } else {
+              accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::METADATA_PROGRAM_ID,
+                false,
+              ));
+            }
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_program::instruction::AccountMeta {
           pubkey: *remaining_account.0.key,
@@ -213,10 +255,13 @@ impl<'a, 'b> SetAuthorityCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(3 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(4 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
-                  account_infos.push(self.idl.clone());
+                  account_infos.push(self.pda.clone());
                         account_infos.push(self.authority.clone());
+                        if let Some(multisig) = self.multisig {
+          account_infos.push(multisig.clone());
+        }
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -231,8 +276,9 @@ impl<'a, 'b> SetAuthorityCpi<'a, 'b> {
 ///
 /// ### Accounts:
 ///
-                ///   0. `[writable]` idl
+                ///   0. `[writable]` pda
                 ///   1. `[signer]` authority
+                ///   2. `[optional]` multisig
 #[derive(Clone, Debug)]
 pub struct SetAuthorityCpiBuilder<'a, 'b> {
   instruction: Box<SetAuthorityCpiBuilderInstruction<'a, 'b>>,
@@ -242,22 +288,29 @@ impl<'a, 'b> SetAuthorityCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
     let instruction = Box::new(SetAuthorityCpiBuilderInstruction {
       __program: program,
-              idl: None,
+              pda: None,
               authority: None,
+              multisig: None,
                                             new_authority: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
       #[inline(always)]
-    pub fn idl(&mut self, idl: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
-                        self.instruction.idl = Some(idl);
+    pub fn pda(&mut self, pda: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.pda = Some(pda);
                     self
     }
       #[inline(always)]
     pub fn authority(&mut self, authority: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.authority = Some(authority);
                     self
+    }
+      /// `[optional account]`
+#[inline(always)]
+    pub fn multisig(&mut self, multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>) -> &mut Self {
+                        self.instruction.multisig = multisig;
+                    self
     }
                     #[inline(always)]
       pub fn new_authority(&mut self, new_authority: Pubkey) -> &mut Self {
@@ -292,9 +345,11 @@ impl<'a, 'b> SetAuthorityCpiBuilder<'a, 'b> {
         let instruction = SetAuthorityCpi {
         __program: self.instruction.__program,
                   
-          idl: self.instruction.idl.expect("idl is not set"),
+          pda: self.instruction.pda.expect("pda is not set"),
                   
           authority: self.instruction.authority.expect("authority is not set"),
+                  
+          multisig: self.instruction.multisig,
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -304,8 +359,9 @@ impl<'a, 'b> SetAuthorityCpiBuilder<'a, 'b> {
 #[derive(Clone, Debug)]
 struct SetAuthorityCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_program::account_info::AccountInfo<'a>,
-            idl: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+            pda: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+                multisig: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                         new_authority: Option<Pubkey>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
@@ -75,7 +75,7 @@ impl Default for WriteBufferInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WriteBufferInstructionArgs {
-                  pub idl_data: Vec<u8>,
+                  pub data: Vec<u8>,
       }
 
 
@@ -91,7 +91,7 @@ pub struct WriteBufferBuilder {
             buffer: Option<solana_program::pubkey::Pubkey>,
                 signer: Option<solana_program::pubkey::Pubkey>,
                 system_program: Option<solana_program::pubkey::Pubkey>,
-                        idl_data: Option<Vec<u8>>,
+                        data: Option<Vec<u8>>,
         __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -116,8 +116,8 @@ impl WriteBufferBuilder {
                     self
     }
                     #[inline(always)]
-      pub fn idl_data(&mut self, idl_data: Vec<u8>) -> &mut Self {
-        self.idl_data = Some(idl_data);
+      pub fn data(&mut self, data: Vec<u8>) -> &mut Self {
+        self.data = Some(data);
         self
       }
         /// Add an additional account to the instruction.
@@ -140,7 +140,7 @@ impl WriteBufferBuilder {
                                         system_program: self.system_program.unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
                       };
           let args = WriteBufferInstructionArgs {
-                                                              idl_data: self.idl_data.clone().expect("idl_data is not set"),
+                                                              data: self.data.clone().expect("data is not set"),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -273,7 +273,7 @@ impl<'a, 'b> WriteBufferCpiBuilder<'a, 'b> {
               buffer: None,
               signer: None,
               system_program: None,
-                                            idl_data: None,
+                                            data: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
@@ -294,8 +294,8 @@ impl<'a, 'b> WriteBufferCpiBuilder<'a, 'b> {
                     self
     }
                     #[inline(always)]
-      pub fn idl_data(&mut self, idl_data: Vec<u8>) -> &mut Self {
-        self.instruction.idl_data = Some(idl_data);
+      pub fn data(&mut self, data: Vec<u8>) -> &mut Self {
+        self.instruction.data = Some(data);
         self
       }
         /// Add an additional account to the instruction.
@@ -321,7 +321,7 @@ impl<'a, 'b> WriteBufferCpiBuilder<'a, 'b> {
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program::entrypoint::ProgramResult {
           let args = WriteBufferInstructionArgs {
-                                                              idl_data: self.instruction.idl_data.clone().expect("idl_data is not set"),
+                                                              data: self.instruction.data.clone().expect("data is not set"),
                                     };
         let instruction = WriteBufferCpi {
         __program: self.instruction.__program,
@@ -343,7 +343,7 @@ struct WriteBufferCpiBuilderInstruction<'a, 'b> {
             buffer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 signer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-                        idl_data: Option<Vec<u8>>,
+                        data: Option<Vec<u8>>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_program::account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -9,5 +9,6 @@
         pub mod errors;
         pub mod instructions;
         pub mod programs;
+        pub mod types;
     
   pub(crate) use programs::*;
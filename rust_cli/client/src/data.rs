@@ -0,0 +1,281 @@
+//! Encoding of uploaded data and decoding of stored data.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use solana_sdk::{hash::hash, pubkey::Pubkey};
+use std::io::{Read, Write};
+
+use crate::codama_sdk::types::{Compression, DataSource, Encoding, Format};
+use crate::error::{Error, Result};
+use crate::state::StoredData;
+
+// High levels are slow but uploads are rare and every byte saved is rent
+const ZSTD_LEVEL: i32 = 19;
+
+/// How an upload stores a file, written into the encoding, compression and format fields of the account.
+#[derive(Clone, Copy, Debug)]
+pub struct DataFormat {
+    pub encoding: Encoding,
+    /// None tries gzip and zstd and keeps the smaller result.
+    pub compression: Option<Compression>,
+    pub format: Format,
+}
+
+impl Default for DataFormat {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::Utf8,
+            compression: Some(Compression::Gzip),
+            format: Format::Json,
+        }
+    }
+}
+
+impl DataFormat {
+    /// Parses the names used on the command line, e.g. `utf8`, `auto` and `json`.
+    pub fn parse(encoding: &str, compression: &str, format: &str) -> Result<Self> {
+        let encoding = match encoding {
+            "utf8" => Encoding::Utf8,
+            "base58" => Encoding::Base58,
+            "base64" => Encoding::Base64,
+            _ => return Err(Error::InvalidDataFormat(format!("Invalid encoding {}, expected utf8, base58 or base64", encoding))),
+        };
+        let compression = match compression {
+            "none" => Some(Compression::None),
+            "gzip" => Some(Compression::Gzip),
+            "zstd" => Some(Compression::Zstd),
+            "auto" => None,
+            _ => return Err(Error::InvalidDataFormat(format!("Invalid compression {}, expected none, gzip, zstd or auto", compression))),
+        };
+        let format = match format {
+            "json" => Format::Json,
+            "yaml" => Format::Yaml,
+            "toml" => Format::Toml,
+            "text" => Format::Text,
+            "binary" => Format::Binary,
+            _ => return Err(Error::InvalidDataFormat(format!("Invalid format {}, expected json, yaml, toml, text or binary", format))),
+        };
+        Ok(Self { encoding, compression, format })
+    }
+}
+
+/// Compresses the data and then encodes the compressed bytes. Utf8 stores them as they are.
+pub fn encode_data(upload_data: &[u8], data_format: &DataFormat) -> Result<StoredData> {
+    let content_hash = hash(upload_data).to_bytes();
+    let (compression, compressed_data) = match data_format.compression {
+        Some(compression) => (compression, compress(upload_data, compression)?),
+        None => {
+            let gzip_data = compress(upload_data, Compression::Gzip)?;
+            let zstd_data = compress(upload_data, Compression::Zstd)?;
+            if zstd_data.len() < gzip_data.len() {
+                (Compression::Zstd, zstd_data)
+            } else {
+                (Compression::Gzip, gzip_data)
+            }
+        }
+    };
+    let encoded_data = match data_format.encoding {
+        Encoding::Utf8 => compressed_data,
+        Encoding::Base58 => bs58::encode(compressed_data).into_vec(),
+        Encoding::Base64 => STANDARD.encode(compressed_data).into_bytes(),
+    };
+    Ok(StoredData {
+        encoding: data_format.encoding,
+        compression,
+        format: data_format.format,
+        data_source: DataSource::Direct,
+        data_hash: hash(&encoded_data).to_bytes(),
+        content_hash,
+        data: encoded_data,
+    })
+}
+
+/// Stores the URL itself. With pin the content behind the URL is fetched once and its hash is recorded
+/// as content hash, so downloads reject content that changed since. Otherwise the content hash is zero.
+pub fn url_data(url: &str, pin: bool) -> Result<StoredData> {
+    reqwest::Url::parse(url).map_err(|e| Error::InvalidUrl { url: url.to_string(), reason: e.to_string() })?;
+    let content_hash = if pin {
+        hash(&fetch_data_from_url(url)?).to_bytes()
+    } else {
+        [0u8; 32]
+    };
+    let data = url.as_bytes().to_vec();
+    Ok(StoredData {
+        encoding: Encoding::Utf8,
+        compression: Compression::None,
+        format: Format::Json,
+        data_source: DataSource::Url,
+        data_hash: hash(&data).to_bytes(),
+        content_hash,
+        data,
+    })
+}
+
+/// Stores a pointer to another account, optionally to the `(offset, length)` range of its data.
+/// The content hash is zero, see [`crate::MetadataClient::pin_account_pointer`].
+pub fn account_pointer_data(address: &Pubkey, range: Option<(u32, u32)>) -> Result<StoredData> {
+    let mut data = address.to_bytes().to_vec();
+    if let Some((offset, length)) = range {
+        if length == 0 || offset.checked_add(length).is_none() {
+            return Err(Error::InvalidAccountPointer(format!("invalid range {}:{}", offset, length)));
+        }
+        data.extend_from_slice(&offset.to_le_bytes());
+        data.extend_from_slice(&length.to_le_bytes());
+    }
+    Ok(StoredData {
+        encoding: Encoding::Utf8,
+        compression: Compression::None,
+        format: Format::Json,
+        data_source: DataSource::Account,
+        data_hash: hash(&data).to_bytes(),
+        content_hash: [0u8; 32],
+        data,
+    })
+}
+
+/// Decodes and decompresses direct data and checks it against the content hash. A zero content hash
+/// means the uploader did not declare one and is not checked.
+pub fn decode_stored_data(stored_data: &StoredData) -> Result<Vec<u8>> {
+    let decoded_data = decompress_stored_data(stored_data)?;
+
+    // Verify the decompressed data against the content hash declared by the uploader
+    if stored_data.content_hash != [0u8; 32] && hash(&decoded_data).to_bytes() != stored_data.content_hash {
+        return Err(Error::ContentHashMismatch("Downloaded data".to_string()));
+    }
+    Ok(decoded_data)
+}
+
+/// Reverses [`encode_data`] purely from the header, so data written by other tools round-trips as well.
+pub fn decompress_stored_data(stored_data: &StoredData) -> Result<Vec<u8>> {
+    let compressed_data = match stored_data.encoding {
+        Encoding::Utf8 => stored_data.data.clone(),
+        Encoding::Base58 => bs58::decode(&stored_data.data)
+            .into_vec()
+            .map_err(|e| Error::Decode(format!("invalid base58 data: {}", e)))?,
+        Encoding::Base64 => STANDARD.decode(&stored_data.data)
+            .map_err(|e| Error::Decode(format!("invalid base64 data: {}", e)))?,
+    };
+    let decoded_data = match stored_data.compression {
+        Compression::None => compressed_data,
+        Compression::Gzip => {
+            let mut decoder = GzDecoder::new(&compressed_data[..]);
+            let mut decoded_data = Vec::new();
+            decoder.read_to_end(&mut decoded_data)
+                .map_err(|e| Error::Decode(format!("invalid gzip data: {}", e)))?;
+            decoded_data
+        }
+        Compression::Zstd => zstd::decode_all(&compressed_data[..])
+            .map_err(|e| Error::Decode(format!("invalid zstd data: {}", e)))?,
+    };
+    Ok(decoded_data)
+}
+
+pub fn compress(data: &[u8], compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(data.to_vec()),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?)
+        }
+        Compression::Zstd => Ok(zstd::encode_all(data, ZSTD_LEVEL)?),
+    }
+}
+
+pub fn fetch_data_from_url(url: &str) -> Result<Vec<u8>> {
+    let fetch_error = |reason: String| Error::Fetch { url: url.to_string(), reason };
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .send()
+        .map_err(|e| fetch_error(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(fetch_error(format!("HTTP {}", response.status())));
+    }
+
+    response.bytes()
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| fetch_error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_checks_the_content_hash() {
+        let mut stored_data = encode_data(b"{\"name\":\"test\"}", &DataFormat::default()).unwrap();
+        assert_eq!(decode_stored_data(&stored_data).unwrap(), b"{\"name\":\"test\"}");
+
+        stored_data.content_hash[0] ^= 1;
+        assert!(matches!(decode_stored_data(&stored_data), Err(Error::ContentHashMismatch(_))));
+    }
+
+    // A JSON file that compresses well, like an IDL
+    fn sample_json() -> Vec<u8> {
+        let instructions: Vec<String> = (0..200)
+            .map(|i| format!("{{\"name\":\"instruction_{}\",\"accounts\":[{{\"name\":\"authority\",\"signer\":true}}]}}", i))
+            .collect();
+        format!("{{\"instructions\":[{}]}}", instructions.join(",")).into_bytes()
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let content = sample_json();
+        for encoding in ["utf8", "base58", "base64"] {
+            for compression in ["none", "gzip", "zstd", "auto"] {
+                let data_format = DataFormat::parse(encoding, compression, "json").unwrap();
+                let stored_data = encode_data(&content, &data_format).unwrap();
+                assert_eq!(
+                    decode_stored_data(&stored_data).unwrap(),
+                    content,
+                    "{} {} does not round trip",
+                    encoding,
+                    compression
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn auto_keeps_the_smaller_compression() {
+        let content = sample_json();
+        let gzip_len = compress(&content, Compression::Gzip).unwrap().len();
+        let zstd_len = compress(&content, Compression::Zstd).unwrap().len();
+
+        let stored_data = encode_data(&content, &DataFormat::parse("utf8", "auto", "json").unwrap()).unwrap();
+        assert_eq!(stored_data.data.len(), gzip_len.min(zstd_len));
+        let expected = if zstd_len < gzip_len { Compression::Zstd } else { Compression::Gzip };
+        assert_eq!(stored_data.compression, expected);
+    }
+
+    #[test]
+    fn zstd_data_is_decoded_from_the_header_alone() {
+        let content = sample_json();
+        let mut stored_data = encode_data(&content, &DataFormat::parse("base64", "none", "json").unwrap()).unwrap();
+        // As written by another tool: zstd compressed, base64 encoded
+        stored_data.compression = Compression::Zstd;
+        stored_data.data = STANDARD.encode(zstd::encode_all(&content[..], 3).unwrap()).into_bytes();
+        assert_eq!(decode_stored_data(&stored_data).unwrap(), content);
+    }
+
+    #[test]
+    fn gzip_data_of_the_js_sdk_is_decoded() {
+        // pako.gzip of the content, as the JS SDK uploads it. It only differs from our own output
+        // in the OS byte of the gzip header.
+        let js_data = STANDARD.decode("H4sIAAAAAAAAA6tWykvMTVWyUkouyi8u1i1OyVaqBQD+H+s1FAAAAA==").unwrap();
+        let mut stored_data = encode_data(b"{\"name\":\"cross-sdk\"}", &DataFormat::default()).unwrap();
+        assert_eq!(stored_data.compression, Compression::Gzip);
+        assert_eq!(stored_data.data[..9], js_data[..9]);
+        stored_data.data = js_data;
+        assert_eq!(decode_stored_data(&stored_data).unwrap(), b"{\"name\":\"cross-sdk\"}");
+    }
+
+    #[test]
+    fn decode_skips_a_zero_content_hash() {
+        let mut stored_data = encode_data(b"{}", &DataFormat::default()).unwrap();
+        stored_data.content_hash = [0u8; 32];
+        assert_eq!(decode_stored_data(&stored_data).unwrap(), b"{}");
+    }
+}
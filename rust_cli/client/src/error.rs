@@ -0,0 +1,130 @@
+use num_traits::FromPrimitive;
+use solana_client::client_error::ClientError;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::codama_sdk::errors::MetadataProgramError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors returned by the client.
+#[derive(Error, Debug)]
+pub enum Error {
+    /// An RPC request failed. `action` describes what the client tried to do.
+    #[error("Failed to {action}: {source}")]
+    Rpc {
+        action: &'static str,
+        source: Box<ClientError>,
+    },
+    /// Simulating a transaction failed with an error of the metadata program.
+    #[error("Transaction simulation failed: {}", describe_program_error(*.0))]
+    Program(u32),
+    /// An account does not have the layout the client expected.
+    #[error("{0}")]
+    InvalidAccount(String),
+    /// The stored bytes do not match the data hash of the account.
+    #[error("Stored data does not match the data hash of the account")]
+    DataHashMismatch,
+    /// Downloaded content does not match the content hash recorded by the uploader.
+    #[error("{0} does not match the content hash of the account")]
+    ContentHashMismatch(String),
+    #[error("No data has been uploaded to {0} yet")]
+    NoData(Pubkey),
+    #[error("Version {version} does not exist, the current version is {current_version}")]
+    VersionNotFound { version: u64, current_version: u64 },
+    #[error("Version {version} was not archived ({archive} not found)")]
+    VersionNotArchived { version: u64, archive: Pubkey },
+    #[error("{0} is frozen and can not be changed anymore")]
+    Frozen(Pubkey),
+    /// Programs of the old loaders have no authority and can only be claimed with the program keypair.
+    #[error("Program {0} has no authority, the program keypair has to sign to attest that you deployed it")]
+    NoProgramAuthority(Pubkey),
+    /// Upgradeable programs with a revoked authority and finalized loader-v4 programs can not be claimed.
+    #[error("Program {0} is immutable, its canonical metadata account can not be created anymore")]
+    ImmutableProgram(Pubkey),
+    /// Signer-seeded metadata accounts can only be created by the key they are derived from.
+    #[error("{metadata} does not exist yet and can only be created by {seed_signer}")]
+    NotSeedSigner { metadata: Pubkey, seed_signer: Pubkey },
+    #[error("{signer} is not the pending authority of {metadata}")]
+    NotPendingAuthority { signer: Pubkey, metadata: Pubkey },
+    /// The hash state of an attestation can only be closed by the key that started it and paid its rent.
+    #[error("An attestation of {metadata} started by {payer} is in progress, {payer} has to finish or close it first")]
+    AttestationInProgress { metadata: Pubkey, payer: Pubkey },
+    #[error("{0} is not a program")]
+    NotAProgram(Pubkey),
+    #[error("Failed to write {chunks} buffer chunks after {attempts} attempts")]
+    WriteFailed { chunks: usize, attempts: u32 },
+    #[error("Invalid account pointer: {0}")]
+    InvalidAccountPointer(String),
+    #[error("Account pointers form a cycle: {0}")]
+    AccountPointerCycle(String),
+    #[error("Followed more than {0} account pointers")]
+    TooManyAccountPointers(usize),
+    #[error("Invalid URL {url}: {reason}")]
+    InvalidUrl { url: String, reason: String },
+    #[error("Failed to fetch {url}: {reason}")]
+    Fetch { url: String, reason: String },
+    #[error("{0}")]
+    InvalidDataFormat(String),
+    /// The stored data could not be decoded or decompressed as its header describes.
+    #[error("Failed to decode the stored data: {0}")]
+    Decode(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl Error {
+    /// The metadata program error that made a transaction fail, if there was one.
+    pub fn program_error(&self) -> Option<MetadataProgramError> {
+        match self {
+            Self::Program(code) => MetadataProgramError::from_u32(*code),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn rpc(action: &'static str) -> impl FnOnce(ClientError) -> Self {
+        move |source| Self::Rpc { action, source: Box::new(source) }
+    }
+}
+
+/// Explains an error of the metadata program and what to do about it.
+pub fn describe_program_error(code: u32) -> String {
+    let Some(error) = MetadataProgramError::from_u32(code) else {
+        return format!("custom program error {:#x}", code);
+    };
+    let hint = match error {
+        MetadataProgramError::InvalidMetadataAddress => {
+            "The account is not the metadata account derived from the program id and data type. \
+             Signer-seeded accounts have to be resized with the key they were created with."
+        }
+        MetadataProgramError::WrongProgramId => {
+            "The metadata account or buffer belongs to a different program. Check the program id you passed."
+        }
+        MetadataProgramError::DataTypeMismatch => {
+            "The buffer was created for a different data type, for example metadata instead of idl."
+        }
+        MetadataProgramError::InvalidAuthority | MetadataProgramError::WrongAuthority => {
+            "The signer is not the authority of the metadata account. Sign with the authority keypair."
+        }
+        MetadataProgramError::ResizeTooLarge => {
+            "Resize the account in steps of at most 10240 bytes."
+        }
+        MetadataProgramError::ResizeTooSmall => {
+            "The account can not be shrunk below the size of the data it currently stores."
+        }
+        MetadataProgramError::AccountFrozen => {
+            "Frozen metadata accounts can not be changed anymore."
+        }
+        MetadataProgramError::InvalidDelegate => {
+            "The delegate record was added by a previous authority or for another account. Ask the authority to add the delegate again."
+        }
+        MetadataProgramError::DelegateExpired => {
+            "Ask the authority to revoke the expired delegate record and add it again."
+        }
+        MetadataProgramError::ExecutableHashOutdated => {
+            "Attest again, which closes the unfinished hash and starts over."
+        }
+        _ => return error.to_string(),
+    };
+    format!("{}. {}", error, hint)
+}
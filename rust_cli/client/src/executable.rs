@@ -0,0 +1,48 @@
+//! Hashing of program executables for attestations.
+
+use solana_sdk::hash::hashv;
+
+/// The executable is hashed in chunks of this size, see the hash_executable instruction.
+pub const EXECUTABLE_HASH_CHUNK_SIZE: usize = 65536;
+// Size of the header of the upgradeable loader's ProgramData and of the loader-v4 program account
+pub(crate) const PROGRAM_DATA_METADATA_SIZE: usize = 45;
+pub(crate) const LOADER_V4_STATE_SIZE: usize = 48;
+// LoaderV4Status::Finalized, the program can not be changed anymore
+pub(crate) const LOADER_V4_STATUS_FINALIZED: u64 = 2;
+
+/// sha256 chained over chunks of [`EXECUTABLE_HASH_CHUNK_SIZE`] bytes, starting with 32 zero bytes.
+/// This is the digest the program records when a version is attested.
+pub fn executable_hash(executable: &[u8]) -> [u8; 32] {
+    executable
+        .chunks(EXECUTABLE_HASH_CHUNK_SIZE)
+        .fold([0u8; 32], |digest, chunk| hashv(&[&digest, chunk]).to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_executable_hashes_to_zero() {
+        assert_eq!(executable_hash(&[]), [0u8; 32]);
+    }
+
+    #[test]
+    fn chunks_are_chained() {
+        let executable: Vec<u8> = (0..EXECUTABLE_HASH_CHUNK_SIZE * 2 + 1).map(|i| i as u8).collect();
+
+        let first = hashv(&[&[0u8; 32], &executable[..EXECUTABLE_HASH_CHUNK_SIZE]]).to_bytes();
+        assert_eq!(executable_hash(&executable[..EXECUTABLE_HASH_CHUNK_SIZE]), first);
+
+        let second = hashv(&[&first, &executable[EXECUTABLE_HASH_CHUNK_SIZE..EXECUTABLE_HASH_CHUNK_SIZE * 2]]).to_bytes();
+        let last = hashv(&[&second, &executable[EXECUTABLE_HASH_CHUNK_SIZE * 2..]]).to_bytes();
+        assert_eq!(executable_hash(&executable), last);
+    }
+
+    #[test]
+    fn one_more_byte_adds_a_chunk() {
+        let executable = vec![7u8; EXECUTABLE_HASH_CHUNK_SIZE + 1];
+        let full_chunk = executable_hash(&executable[..EXECUTABLE_HASH_CHUNK_SIZE]);
+        assert_eq!(executable_hash(&executable), hashv(&[&full_chunk, &[7u8]]).to_bytes());
+    }
+}
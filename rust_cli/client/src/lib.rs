@@ -0,0 +1,47 @@
+//! Client for the program metadata program, which stores the IDL and other metadata of a
+//! Solana program in an account derived from the program id.
+//!
+//! [`MetadataClient`] wraps an [`RpcClient`](solana_client::rpc_client::RpcClient) and returns
+//! typed results and [`Error`]s. It never prints.
+//!
+//! ```no_run
+//! use program_metadata_client::{encode_data, get_metadata_address, DataFormat, MetadataClient, UploadOptions, IDL_SEED};
+//! use solana_client::rpc_client::RpcClient;
+//! use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair};
+//!
+//! # fn main() -> program_metadata_client::Result<()> {
+//! let client = MetadataClient::new(RpcClient::new_with_commitment(
+//!     "https://api.devnet.solana.com".to_string(),
+//!     CommitmentConfig::confirmed(),
+//! ));
+//! let authority = Keypair::new();
+//! let program_id = Pubkey::new_unique();
+//!
+//! let stored_data = encode_data(br#"{"name": "my_program"}"#, &DataFormat::default())?;
+//! let upload = client.upload(&authority, &program_id, IDL_SEED, &stored_data, &UploadOptions::default())?;
+//!
+//! let idl = client.download(&get_metadata_address(IDL_SEED, &program_id), None)?;
+//! assert_eq!(idl, br#"{"name": "my_program"}"#);
+//! assert_eq!(client.fetch_metadata(&upload.metadata_address)?.version, upload.version);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod address;
+mod client;
+// The generated code re-exports more than it uses
+#[allow(unused_imports)]
+pub mod codama_sdk;
+pub mod data;
+mod error;
+pub mod executable;
+pub mod state;
+
+pub use address::*;
+pub use client::{
+    Attestation, DurableNonce, ExportedUpload, MetadataClient, Migration, MultisigAuthority, UploadOptions, UploadResult,
+};
+pub use codama_sdk::programs::METADATA_PROGRAM_ID;
+pub use data::{account_pointer_data, encode_data, url_data, DataFormat};
+pub use error::{describe_program_error, Error, Result};
+pub use state::{DelegateRecord, MetadataAccount, StoredData};
@@ -0,0 +1,250 @@
+//! Decoding of the accounts of the metadata program.
+
+use borsh::BorshDeserialize;
+use solana_sdk::{hash::hash, pubkey::Pubkey};
+
+use crate::codama_sdk::accounts::{
+    MetadataAccount as MetadataAccount1, MetadataAccount2, MetadataAccount3, MetadataArchive, MetadataBuffer,
+    MetadataDelegate,
+};
+use crate::codama_sdk::types::{Compression, DataSource, Encoding, Format};
+use crate::error::{Error, Result};
+
+// Layout of the MetadataAccount3 header. The trailing data starts at METADATA_OFFSET.
+pub const AUTHORITY_OFFSET: usize = 8;
+pub const PROGRAM_ID_OFFSET: usize = 40;
+pub const DATA_TYPE_OFFSET: usize = 72;
+pub const DATA_HEADER_OFFSET: usize = 88;
+pub const VERSION_OFFSET: usize = 160;
+pub const FROZEN_OFFSET: usize = 168;
+pub const PENDING_AUTHORITY_OFFSET: usize = 169;
+pub const PROGRAM_DATA_SLOT_OFFSET: usize = 201;
+pub const EXECUTABLE_HASH_OFFSET: usize = 209;
+pub const METADATA_OFFSET: usize = 241;
+// MetadataBuffer has the same data header but no version.
+pub const BUFFER_OFFSET: usize = 160;
+// Layout of the MetadataArchive header. The trailing data starts at ARCHIVE_OFFSET.
+pub const ARCHIVE_DATA_HEADER_OFFSET: usize = 48;
+pub const ARCHIVE_OFFSET: usize = 120;
+// Layouts of the legacy MetadataAccount and MetadataAccount2 headers
+pub const LEGACY_DATA_LEN_OFFSET: usize = 56;
+pub const LEGACY_OFFSET: usize = 60;
+pub const LEGACY_2_DATA_LEN_OFFSET: usize = 88;
+pub const LEGACY_2_OFFSET: usize = 92;
+// Layout of the old MetadataAccount3 header, from before the hashes and the version were added
+pub const LEGACY_3_DATA_HEADER_OFFSET: usize = 88;
+pub const LEGACY_3_DATA_LEN_OFFSET: usize = 92;
+pub const LEGACY_3_OFFSET: usize = 96;
+// Layout of a MetadataDelegate record
+pub const DELEGATE_METADATA_OFFSET: usize = 8;
+pub const DELEGATE_DELEGATE_OFFSET: usize = 40;
+pub const DELEGATE_AUTHORITY_OFFSET: usize = 72;
+pub const DELEGATE_EXPIRY_SLOT_OFFSET: usize = 104;
+pub const DELEGATE_SIZE: usize = 112;
+// Offsets inside the data header: encoding, compression, format, data_source, data_len, data_hash, content_hash
+const DATA_LEN_OFFSET: usize = 4;
+const DATA_HASH_OFFSET: usize = 8;
+const CONTENT_HASH_OFFSET: usize = 40;
+
+// The generated accounts are checked against the program by build.rs, so a layout change
+// of the program fails the build here instead of misreading accounts.
+const _: () = {
+    assert!(METADATA_OFFSET == MetadataAccount3::LEN);
+    assert!(BUFFER_OFFSET == MetadataBuffer::LEN);
+    assert!(ARCHIVE_OFFSET == MetadataArchive::LEN);
+    assert!(DELEGATE_SIZE == MetadataDelegate::LEN);
+    assert!(LEGACY_OFFSET == MetadataAccount1::LEN);
+    assert!(LEGACY_2_OFFSET == MetadataAccount2::LEN);
+};
+
+/// Anchor discriminator of the account type `account_name`.
+pub fn account_discriminator(account_name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("account:{}", account_name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Whether a MetadataAccount3 still has the old 96 byte header and has to be migrated first.
+/// Mirrors `is_legacy_metadata_account_3` of the program.
+pub fn is_legacy_metadata_account_3(account_data: &[u8]) -> bool {
+    if account_data.len() < LEGACY_3_OFFSET {
+        return false;
+    }
+    let data_length = u32::from_le_bytes(
+        account_data[LEGACY_3_DATA_LEN_OFFSET..LEGACY_3_OFFSET].try_into().unwrap(),
+    ) as usize;
+    match account_data.get(METADATA_OFFSET..METADATA_OFFSET.saturating_add(data_length)) {
+        Some(data) => {
+            let data_hash_offset = DATA_HEADER_OFFSET + DATA_HASH_OFFSET;
+            data_length > 0 && hash(data).to_bytes()[..] != account_data[data_hash_offset..data_hash_offset + 32]
+        }
+        None => true,
+    }
+}
+
+/// Data header and trailing data of a metadata account, buffer or archive.
+#[derive(Clone, Debug)]
+pub struct StoredData {
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub format: Format,
+    pub data_source: DataSource,
+    /// SHA-256 of `data`, checked by the program when the data is set.
+    pub data_hash: [u8; 32],
+    /// SHA-256 of the content before compression and encoding. Zero for unpinned URLs and account pointers.
+    pub content_hash: [u8; 32],
+    /// The stored bytes, still compressed and encoded.
+    pub data: Vec<u8>,
+}
+
+impl StoredData {
+    /// Reads the stored data of a MetadataArchive.
+    pub fn from_archive_data(account_data: &[u8]) -> Result<Self> {
+        read_stored_data(account_data, ARCHIVE_DATA_HEADER_OFFSET, ARCHIVE_OFFSET)
+    }
+}
+
+/// A decoded MetadataAccount3.
+#[derive(Clone, Debug)]
+pub struct MetadataAccount {
+    /// `Pubkey::default()` once the authority was erased.
+    pub authority: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    /// Incremented by every upload. 0 means nothing was uploaded yet.
+    pub version: u64,
+    pub frozen: bool,
+    pub pending_authority: Option<Pubkey>,
+    /// Deployment slot of the program when the current version was set.
+    pub program_data_slot: u64,
+    /// Digest of the executable the current version was attested against.
+    pub executable_hash: Option<[u8; 32]>,
+    /// None until the first upload.
+    pub data: Option<StoredData>,
+}
+
+impl MetadataAccount {
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self> {
+        if !account_data.starts_with(&account_discriminator("MetadataAccount3")) {
+            return Err(Error::InvalidAccount("Account is not a MetadataAccount3".to_string()));
+        }
+        if is_legacy_metadata_account_3(account_data) {
+            return Err(Error::InvalidAccount(
+                "Account has the old MetadataAccount3 layout, run migrate first".to_string(),
+            ));
+        }
+        let header = account_data
+            .get(..METADATA_OFFSET)
+            .ok_or_else(|| Error::InvalidAccount("Account data is shorter than its header".to_string()))?;
+
+        let data_type = &header[DATA_TYPE_OFFSET..DATA_HEADER_OFFSET];
+        let data_type_len = data_type.iter().position(|byte| *byte == 0).unwrap_or(data_type.len());
+        let version = read_u64(header, VERSION_OFFSET);
+        let pending_authority = read_pubkey(header, PENDING_AUTHORITY_OFFSET);
+        let executable_hash: [u8; 32] = header[EXECUTABLE_HASH_OFFSET..EXECUTABLE_HASH_OFFSET + 32]
+            .try_into()
+            .unwrap();
+
+        Ok(Self {
+            authority: read_pubkey(header, AUTHORITY_OFFSET),
+            program_id: read_pubkey(header, PROGRAM_ID_OFFSET),
+            data_type: String::from_utf8_lossy(&data_type[..data_type_len]).into_owned(),
+            version,
+            frozen: header[FROZEN_OFFSET] != 0,
+            pending_authority: Some(pending_authority).filter(|pending_authority| *pending_authority != Pubkey::default()),
+            program_data_slot: read_u64(header, PROGRAM_DATA_SLOT_OFFSET),
+            executable_hash: Some(executable_hash).filter(|executable_hash| *executable_hash != [0u8; 32]),
+            data: if version == 0 {
+                None
+            } else {
+                Some(read_stored_data(account_data, DATA_HEADER_OFFSET, METADATA_OFFSET)?)
+            },
+        })
+    }
+}
+
+/// A decoded MetadataDelegate record.
+#[derive(Clone, Debug)]
+pub struct DelegateRecord {
+    pub metadata: Pubkey,
+    pub delegate: Pubkey,
+    /// The authority that added the delegate. The record is ignored once the authority changes.
+    pub authority: Pubkey,
+    /// Last slot in which the delegate can upload. 0 means no expiry.
+    pub expiry_slot: u64,
+}
+
+impl DelegateRecord {
+    pub fn from_account_data(account_data: &[u8]) -> Result<Self> {
+        if account_data.len() != DELEGATE_SIZE || !account_data.starts_with(&account_discriminator("MetadataDelegate")) {
+            return Err(Error::InvalidAccount("Account is not a MetadataDelegate".to_string()));
+        }
+        Ok(Self {
+            metadata: read_pubkey(account_data, DELEGATE_METADATA_OFFSET),
+            delegate: read_pubkey(account_data, DELEGATE_DELEGATE_OFFSET),
+            authority: read_pubkey(account_data, DELEGATE_AUTHORITY_OFFSET),
+            expiry_slot: read_u64(account_data, DELEGATE_EXPIRY_SLOT_OFFSET),
+        })
+    }
+
+    /// Whether the record still lets the delegate upload: added by the current authority and not expired.
+    pub fn is_active(&self, authority: &Pubkey, current_slot: u64) -> bool {
+        self.authority == *authority && (self.expiry_slot == 0 || current_slot <= self.expiry_slot)
+    }
+}
+
+pub(crate) fn read_stored_data(account_data: &[u8], header_offset: usize, data_offset: usize) -> Result<StoredData> {
+    let header = account_data
+        .get(header_offset..header_offset + CONTENT_HASH_OFFSET + 32)
+        .ok_or_else(|| Error::InvalidAccount("Account data is shorter than its header".to_string()))?;
+
+    let (encoding, compression, format, data_source) = read_data_header(header)?;
+
+    let data_length = u32::from_le_bytes(header[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 4].try_into().unwrap()) as usize;
+    let mut data_hash = [0u8; 32];
+    data_hash.copy_from_slice(&header[DATA_HASH_OFFSET..DATA_HASH_OFFSET + 32]);
+    let mut content_hash = [0u8; 32];
+    content_hash.copy_from_slice(&header[CONTENT_HASH_OFFSET..CONTENT_HASH_OFFSET + 32]);
+
+    let data = account_data
+        .get(data_offset..data_offset + data_length)
+        .ok_or_else(|| Error::InvalidAccount("Account data is shorter than its data length".to_string()))?
+        .to_vec();
+
+    // Verify the stored bytes against the hash that set_buffer checked on chain
+    if hash(&data).to_bytes() != data_hash {
+        return Err(Error::DataHashMismatch);
+    }
+
+    Ok(StoredData {
+        encoding,
+        compression,
+        format,
+        data_source,
+        data_hash,
+        content_hash,
+        data,
+    })
+}
+
+// Reads the encoding, compression, format and data source bytes that start every data header
+pub(crate) fn read_data_header(header: &[u8]) -> Result<(Encoding, Compression, Format, DataSource)> {
+    let encoding = Encoding::try_from_slice(&header[0..1])
+        .map_err(|e| Error::InvalidAccount(format!("Invalid encoding: {}", e)))?;
+    let compression = Compression::try_from_slice(&header[1..2])
+        .map_err(|e| Error::InvalidAccount(format!("Invalid compression: {}", e)))?;
+    let format = Format::try_from_slice(&header[2..3])
+        .map_err(|e| Error::InvalidAccount(format!("Invalid format: {}", e)))?;
+    let data_source = DataSource::try_from_slice(&header[3..4])
+        .map_err(|e| Error::InvalidAccount(format!("Invalid data source: {}", e)))?;
+    Ok((encoding, compression, format, data_source))
+}
+
+// Callers check the length of the data first
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::try_from(&data[offset..offset + 32]).unwrap()
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
@@ -1,207 +1,429 @@
 use clap::{Command, Arg, ArgAction};
+use program_metadata_client::{IDL_SEED, METADATA_SEED};
 
 pub fn get_clap_app() -> Command {
     Command::new("program-metadata")
         .about("This CLI is used to interact with the metadata program. You can upload IDLs and metadata to Solana programs so that everyone can access them by just knowing the program id. It supports uploading from local files or URLs. You can also download the IDL and metadata from a program to a file on your system.")
         .version("0.1.0")
+        .subcommand(data_command(IDL_SEED, "IDL", "idl-path"))
+        .subcommand(data_command(METADATA_SEED, "Metadata", "metadata-path"))
         .subcommand(
-            Command::new("idl")
-                .about("IDL management commands")
-                .subcommand(
-                    Command::new("upload")
-                        .about("Upload IDL from a file")
-                        .arg(
-                            Arg::new("idl-path")
-                                .help("Path to the IDL file")
-                                .required(true)
-                                .index(1)
-                        )
-                        .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
-                                .required(true)
-                                .index(2)
-                        )
-                        .arg(
-                            Arg::new("keypair")
-                                .help("Path to keypair file (optional, defaults to config)")
-                                .short('k')
-                                .long("keypair")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
-                        .arg(
-                            Arg::new("priority-fees-per-cu")
-                                .help("Priority fees per compute unit")
-                                .short('p')
-                                .long("priority-fees")
-                                .action(ArgAction::Set)
-                                .default_value("0")
-                        )
-                        .arg(
-                            Arg::new("rpc-url")
-                                .help("RPC URL (optional, defaults to config)")
-                                .short('u')
-                                .long("rpc-url")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
+            Command::new("watch")
+                .about("Print the events of the metadata program live as they happen")
+                .arg(
+                    Arg::new("program-id")
+                        .help("Only show events for this program (optional, defaults to all programs)")
+                        .long("program-id")
+                        .action(ArgAction::Set)
+                        .required(false)
+                )
+                .arg(
+                    rpc_url_arg()
+                        .help("RPC URL (optional, defaults to config). The websocket URL is derived from it")
                 )
+        )
+        .subcommand(
+            Command::new("multisig")
+                .about("Threshold authorities for metadata accounts")
                 .subcommand(
-                    Command::new("upload-url")
-                        .about("Upload IDL from URL")
+                    Command::new("create")
+                        .about("Create a multisig that can be set as the authority of a metadata account")
                         .arg(
-                            Arg::new("url")
-                                .help("URL of the IDL file")
+                            Arg::new("threshold")
+                                .help("Number of signers needed to approve a change")
                                 .required(true)
                                 .index(1)
                         )
                         .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
+                            Arg::new("signers")
+                                .help("Public keys of the signers")
                                 .required(true)
+                                .num_args(1..)
                                 .index(2)
                         )
-                        .arg(
-                            Arg::new("keypair")
-                                .help("Path to keypair file (optional, defaults to config)")
-                                .short('k')
-                                .long("keypair")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
-                        .arg(
-                            Arg::new("priority-fees-per-cu")
-                                .help("Priority fees per compute unit")
-                                .short('p')
-                                .long("priority-fees")
-                                .action(ArgAction::Set)
-                                .default_value("0")
-                        )
-                        .arg(
-                            Arg::new("rpc-url")
-                                .help("RPC URL (optional, defaults to config)")
-                                .short('u')
-                                .long("rpc-url")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
+                        .arg(keypair_arg().help("Path to keypair file that pays for the account (optional, defaults to config)"))
+                        .arg(priority_fees_arg())
+                        .arg(rpc_url_arg())
                 )
                 .subcommand(
-                    Command::new("download")
-                        .about("Download IDL to file")
+                    Command::new("show")
+                        .about("Show the threshold and signers of a multisig")
                         .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
+                            Arg::new("address")
+                                .help("Address of the multisig account")
                                 .required(true)
                                 .index(1)
                         )
-                        .arg(
-                            Arg::new("output")
-                                .help("Output file path (optional, defaults to idl.json)")
-                                .required(false)
-                                .index(2)
-                        )
+                        .arg(rpc_url_arg())
                 )
         )
+}
+
+// The idl and metadata subcommands are the same apart from the seed of the accounts they work on.
+// The label names the data in the help texts and path_arg is the id of the file argument of upload.
+fn data_command(seed: &'static str, label: &str, path_arg: &'static str) -> Command {
+    Command::new(seed)
+        .about(format!("{} management commands", label))
         .subcommand(
-            Command::new("metadata")
-                .about("Metadata management commands")
-                .subcommand(
-                    Command::new("upload")
-                        .about("Upload Metadata from file")
-                        .arg(
-                            Arg::new("metadata-path")
-                                .help("Path to the Metadata file")
-                                .required(true)
-                                .index(1)
-                        )
-                        .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
-                                .required(true)
-                                .index(2)
-                        )
-                        .arg(
-                            Arg::new("keypair")
-                                .help("Path to keypair file (optional, defaults to config)")
-                                .short('k')
-                                .long("keypair")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
-                        .arg(
-                            Arg::new("priority-fees-per-cu")
-                                .help("Priority fees per compute unit")
-                                .short('p')
-                                .long("priority-fees")
-                                .action(ArgAction::Set)
-                                .default_value("0")
-                        )
-                        .arg(
-                            Arg::new("rpc-url")
-                                .help("RPC URL (optional, defaults to config)")
-                                .short('u')
-                                .long("rpc-url")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
+            Command::new("upload")
+                .about(format!("Upload {} from a file", label))
+                .arg(
+                    Arg::new(path_arg)
+                        .help(format!("Path to the {} file", label))
+                        .required(true)
+                        .index(1)
                 )
-                .subcommand(
-                    Command::new("upload-url")
-                        .about("Upload Metadata from URL")
-                        .arg(
-                            Arg::new("url")
-                                .help("URL of the Metadata file")
-                                .required(true)
-                                .index(1)
-                        )
-                        .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
-                                .required(true)
-                                .index(2)
-                        )
-                        .arg(
-                            Arg::new("keypair")
-                                .help("Path to keypair file (optional, defaults to config)")
-                                .short('k')
-                                .long("keypair")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
-                        .arg(
-                            Arg::new("priority-fees-per-cu")
-                                .help("Priority fees per compute unit")
-                                .short('p')
-                                .long("priority-fees")
-                                .action(ArgAction::Set)
-                                .default_value("0")
-                        )
-                        .arg(
-                            Arg::new("rpc-url")
-                                .help("RPC URL (optional, defaults to config)")
-                                .short('u')
-                                .long("rpc-url")
-                                .action(ArgAction::Set)
-                                .required(false)
-                        )
+                .arg(upload_program_id_arg())
+                .args(transaction_args())
+                .args(upload_args())
+                .arg(
+                    Arg::new("encoding")
+                        .help("Encoding of the stored bytes")
+                        .long("encoding")
+                        .value_parser(["utf8", "base58", "base64"])
+                        .default_value("utf8")
                 )
-                .subcommand(
-                    Command::new("download")
-                        .about("Download Metadata to file")
-                        .arg(
-                            Arg::new("program-id")
-                                .help("Program ID")
-                                .required(true)
-                                .index(1)
-                        )
-                        .arg(
-                            Arg::new("output")
-                                .help("Output file path (optional, defaults to metadata.json)")
-                                .required(false)
-                                .index(2)
-                        )
+                .arg(
+                    Arg::new("compression")
+                        .help("Compression of the stored bytes, auto keeps the smaller of gzip and zstd")
+                        .long("compression")
+                        .value_parser(["none", "gzip", "zstd", "auto"])
+                        .default_value("gzip")
+                )
+                .arg(
+                    Arg::new("format")
+                        .help("Format of the file")
+                        .long("format")
+                        .value_parser(["json", "yaml", "toml", "text", "binary"])
+                        .default_value("json")
+                )
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("upload-url")
+                .about(format!("Store the URL of the {} file, download fetches it from there", label))
+                .arg(
+                    Arg::new("url")
+                        .help(format!("URL of the {} file", label))
+                        .required(true)
+                        .index(1)
+                )
+                .arg(upload_program_id_arg())
+                .args(transaction_args())
+                .args(upload_args())
+                .arg(pin_arg().help("Record the hash of the current content at the URL so download rejects changed content"))
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("upload-account")
+                .about(format!(
+                    "Point to another account that holds the {}, for example the {} account of another program",
+                    label, label
+                ))
+                .arg(
+                    Arg::new("account")
+                        .help("Address of the account, optionally followed by :<OFFSET>:<LENGTH> of the data in it")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(upload_program_id_arg())
+                .args(transaction_args())
+                .args(upload_args())
+                .arg(pin_arg().help("Record the hash of the data the account holds now so download rejects changed data"))
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("download")
+                .about(format!("Download {} to file", label))
+                .arg(program_id_arg())
+                .arg(
+                    Arg::new("output")
+                        .help(format!("Output file path (optional, defaults to {}.json)", seed))
+                        .required(false)
+                        .index(2)
+                )
+                .arg(
+                    Arg::new("version")
+                        .help("Version to download (optional, defaults to the current version)")
+                        .short('v')
+                        .long("version")
+                        .action(ArgAction::Set)
+                        .required(false)
+                )
+                .arg(
+                    Arg::new("signer")
+                        .help("Download the third party data uploaded by this signer with --add-signer-seed")
+                        .short('s')
+                        .long("signer")
+                        .action(ArgAction::Set)
+                        .required(false)
+                )
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("versions")
+                .about(format!("List the current and archived versions of the {}", label))
+                .arg(program_id_arg())
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("status")
+                .about(format!("Show the {} account and warn if it predates the last program upgrade", label))
+                .arg(program_id_arg())
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("attest")
+                .about(format!("Hash the deployed executable on chain and bind the current {} version to it", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("verify")
+                .about(format!("Check that the {} was attested against the deployed program or a local .so file", label))
+                .arg(program_id_arg())
+                .arg(
+                    Arg::new("so")
+                        .help("Path to the .so file to compare with (optional, defaults to the deployed program)")
+                        .long("so")
+                        .action(ArgAction::Set)
+                        .required(false)
+                )
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("rollback")
+                .about(format!("Upload an archived version of the {} again", label))
+                .arg(program_id_arg())
+                .arg(
+                    Arg::new("version")
+                        .help("Version to roll back to")
+                        .required(true)
+                        .index(2)
+                )
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about(format!("Migrate a legacy {} account to the current account layout", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+        )
+        .subcommand(
+            Command::new("freeze")
+                .about(format!("Permanently freeze the {} account. This can not be undone", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+                .arg(
+                    Arg::new("yes")
+                        .help("Skip the confirmation prompt")
+                        .short('y')
+                        .long("yes")
+                        .action(ArgAction::SetTrue)
+                )
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("set-authority")
+                .about(format!("Propose a new authority for the {} account. The new authority has to accept it", label))
+                .arg(program_id_arg())
+                .arg(
+                    Arg::new("new-authority")
+                        .help("Public key of the new authority")
+                        .required(true)
+                        .index(2)
+                )
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("accept-authority")
+                .about(format!("Accept the authority of the {} account. Must be signed by the proposed authority", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+                .arg(multisig_arg(label).help("Multisig that was proposed as the new authority, accept with threshold of its signers"))
+                .arg(multisig_signer_arg())
+        )
+        .subcommand(
+            Command::new("cancel-authority")
+                .about(format!("Cancel a pending authority transfer of the {} account", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("close")
+                .about(format!("Close the {} account and reclaim its rent", label))
+                .arg(program_id_arg())
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("close-buffer")
+                .about("Close a buffer, e.g. after an exported transaction set it, and reclaim its rent")
+                .arg(
+                    Arg::new("buffer")
+                        .help("Buffer address")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(keypair_arg().help("Path to keypair file of the buffer authority (optional, defaults to config)"))
+                .arg(priority_fees_arg())
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("add-delegate")
+                .about(format!("Allow a delegate to upload new versions of the {} without being its authority", label))
+                .arg(program_id_arg())
+                .arg(delegate_arg())
+                .arg(
+                    Arg::new("expiry-slot")
+                        .help("Last slot in which the delegate can upload (optional, defaults to no expiry)")
+                        .long("expiry-slot")
+                        .action(ArgAction::Set)
+                        .required(false)
                 )
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+        .subcommand(
+            Command::new("list-delegates")
+                .about(format!("List the delegates of the {} account", label))
+                .arg(program_id_arg())
+                .arg(rpc_url_arg())
         )
-} 
\ No newline at end of file
+        .subcommand(
+            Command::new("list-third-party")
+                .about(format!("List the third party {} accounts of the program, uploaded with --add-signer-seed", label))
+                .arg(program_id_arg())
+                .arg(rpc_url_arg())
+        )
+        .subcommand(
+            Command::new("revoke-delegate")
+                .about(format!("Revoke a delegate of the {} account", label))
+                .arg(program_id_arg())
+                .arg(delegate_arg())
+                .args(transaction_args())
+                .args(multisig_args(label))
+        )
+}
+
+fn program_id_arg() -> Arg {
+    Arg::new("program-id")
+        .help("Program ID")
+        .required(true)
+        .index(1)
+}
+
+// Uploads can claim programs without an authority with the program keypair
+fn upload_program_id_arg() -> Arg {
+    program_id_arg()
+        .help("Program ID, or the path to the program keypair for programs without an authority")
+        .index(2)
+}
+
+fn delegate_arg() -> Arg {
+    Arg::new("delegate")
+        .help("Public key of the delegate")
+        .required(true)
+        .index(2)
+}
+
+fn keypair_arg() -> Arg {
+    Arg::new("keypair")
+        .help("Path to keypair file (optional, defaults to config)")
+        .short('k')
+        .long("keypair")
+        .action(ArgAction::Set)
+        .required(false)
+}
+
+fn priority_fees_arg() -> Arg {
+    Arg::new("priority-fees-per-cu")
+        .help("Priority fees per compute unit")
+        .short('p')
+        .long("priority-fees")
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(u64))
+        .default_value("0")
+}
+
+fn rpc_url_arg() -> Arg {
+    Arg::new("rpc-url")
+        .help("RPC URL (optional, defaults to config)")
+        .short('u')
+        .long("rpc-url")
+        .action(ArgAction::Set)
+        .required(false)
+}
+
+// Arguments of every subcommand that sends transactions
+fn transaction_args() -> [Arg; 3] {
+    [keypair_arg(), priority_fees_arg(), rpc_url_arg()]
+}
+
+fn multisig_arg(label: &str) -> Arg {
+    Arg::new("multisig")
+        .help(format!("Multisig that is the authority of the {} account, sign with threshold of its signers", label))
+        .long("multisig")
+        .action(ArgAction::Set)
+        .required(false)
+}
+
+fn multisig_signer_arg() -> Arg {
+    Arg::new("multisig-signer")
+        .help("Keypair file of another signer of the multisig, can be repeated")
+        .long("multisig-signer")
+        .action(ArgAction::Append)
+        .requires("multisig")
+}
+
+// Arguments to sign as a multisig authority
+fn multisig_args(label: &str) -> [Arg; 2] {
+    [multisig_arg(label), multisig_signer_arg()]
+}
+
+// Arguments shared by upload, upload-url and upload-account
+fn upload_args() -> [Arg; 6] {
+    [
+        Arg::new("archive")
+            .help("Keep the current version in an archive account before overwriting it")
+            .long("archive")
+            .action(ArgAction::SetTrue),
+        Arg::new("add-signer-seed")
+            .help("Add the signer as seed of the account. Anyone can upload such third party data, the signer does not have to be the program authority")
+            .short('a')
+            .long("add-signer-seed")
+            .action(ArgAction::SetTrue),
+        Arg::new("export-transaction")
+            .help("Only write the buffer with the keypair and print the transaction that sets it, to sign with a multisig or offline")
+            .long("export-transaction")
+            .value_name("ENCODING")
+            .value_parser(["base58", "base64"])
+            .num_args(0..=1)
+            .default_missing_value("base58"),
+        Arg::new("authority")
+            .help("Signer of the exported transaction (optional, defaults to the authority of the account or program)")
+            .long("authority")
+            .action(ArgAction::Set)
+            .requires("export-transaction"),
+        Arg::new("nonce")
+            .help("Durable nonce account whose blockhash the exported transaction uses, so it does not expire")
+            .long("nonce")
+            .action(ArgAction::Set)
+            .requires("export-transaction"),
+        Arg::new("nonce-authority")
+            .help("Authority of the nonce account (optional, defaults to the authority stored in the nonce account)")
+            .long("nonce-authority")
+            .action(ArgAction::Set)
+            .requires("nonce"),
+    ]
+}
+
+fn pin_arg() -> Arg {
+    Arg::new("pin")
+        .long("pin")
+        .action(ArgAction::SetTrue)
+}
@@ -1,11 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-  pub(crate) mod r#idl_account;
-
-  pub use self::r#idl_account::*;
-
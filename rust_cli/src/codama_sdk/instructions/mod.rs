@@ -1,23 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-  pub(crate) mod r#close_buffer;
-  pub(crate) mod r#create_buffer;
-  pub(crate) mod r#initialize;
-  pub(crate) mod r#resize;
-  pub(crate) mod r#set_authority;
-  pub(crate) mod r#set_buffer;
-  pub(crate) mod r#write_buffer;
-
-  pub use self::r#close_buffer::*;
-  pub use self::r#create_buffer::*;
-  pub use self::r#initialize::*;
-  pub use self::r#resize::*;
-  pub use self::r#set_authority::*;
-  pub use self::r#set_buffer::*;
-  pub use self::r#write_buffer::*;
-
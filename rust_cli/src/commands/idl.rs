@@ -1,548 +1,773 @@
-use anyhow::{Result, anyhow};
-use flate2::write::GzEncoder;
-use flate2::Compression;
-use solana_sdk::commitment_config::CommitmentConfig;
+use anyhow::{Result, anyhow, bail};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use solana_sdk::{
+    hash::Hash,
     pubkey::Pubkey,
     signature::Keypair,
-    transaction::Transaction,
     signer::Signer,
-    compute_budget::ComputeBudgetInstruction,
 };
 use std::io::Write;
-use std::io::Read;
 use std::{str::FromStr, fs};
-use crate::codama_sdk::instructions::{Resize, ResizeInstructionArgs};
-use crate::config::get_user_config;
-use crate::codama_sdk::{
-    instructions::{
-        initialize::{Initialize, InitializeInstructionArgs},
-        create_buffer::CreateBufferBuilder,
-        write_buffer::{WriteBuffer, WriteBufferInstructionArgs},
-        set_buffer::{SetBuffer, SetBufferInstructionArgs},
-        close_buffer::CloseBuffer,
-    },
-    programs::METADATA_PROGRAM_ID,
+use program_metadata_client::{
+    account_pointer_data, encode_data, executable::executable_hash, get_metadata_address,
+    get_signer_seeded_metadata_address, url_data, DataFormat, DurableNonce, Error, ExportedUpload, MetadataClient,
+    MultisigAuthority, StoredData, UploadOptions,
 };
-use reqwest::blocking::Client;
-use flate2::read::GzDecoder;
+use crate::config::get_user_config;
 
-const IDL_SEED: &str = "idl";
-const METADATA_SEED: &str = "metadata";
-const METADATA_OFFSET: usize = 44;
-const CHUNK_SIZE: u16 = 900;
-const MAX_RESIZE_STEP: u16 = 10240;
+// Flags shared by the upload commands
+pub struct UploadFlags {
+    pub archive: bool,
+    // Derive the metadata account from the signer as well, so it does not have to be the program authority
+    pub add_signer_seed: bool,
+    // Encoding of the exported set_buffer message, base58 or base64. The local keypair only writes the buffer.
+    pub export_transaction: Option<String>,
+    // Signer of the exported message, defaults to the authority of the metadata account or program
+    pub authority: Option<String>,
+    // Durable nonce account of the exported message, and its authority if it is not the stored one
+    pub nonce: Option<String>,
+    pub nonce_authority: Option<String>,
+    pub multisig: MultisigFlags,
+}
 
+// Signs as one of the signers of a multisig authority
+pub struct MultisigFlags {
+    // The multisig that is the authority, or the proposed authority for accept-authority
+    pub multisig: Option<String>,
+    // Keypair files of the other signers, that together with the keypair reach the threshold
+    pub signers: Vec<String>,
+}
 
 //Upload functions
-pub fn upload_idl_by_file_path(
+#[allow(clippy::too_many_arguments)]
+pub fn upload_by_file_path(
+    seed: &str,
     file_path: &str,
     program_id: &str,
     keypair_path: Option<&str>,
     priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    flags: &UploadFlags,
+    data_format: &DataFormat,
 ) -> Result<()> {
-    upload_data_by_file_path(file_path, program_id, keypair_path, priority_fees_per_cu, IDL_SEED, rpc_url)
+    let upload_data = fs::read(file_path)
+        .map_err(|e| anyhow!("Failed to read file: {}", e))?;
+    let stored_data = encode_data(&upload_data, data_format)?;
+    upload_new_data(&stored_data, program_id, keypair_path, priority_fees_per_cu, seed, rpc_url, flags)
 }
 
-pub fn upload_metadata_by_file_path(
-    metadata_path: &str,
+#[allow(clippy::too_many_arguments)]
+pub fn upload_by_url(
+    seed: &str,
+    url: &str,
     program_id: &str,
     keypair_path: Option<&str>,
     priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    flags: &UploadFlags,
+    pin: bool,
 ) -> Result<()> {
-    upload_data_by_file_path(metadata_path, program_id, keypair_path, priority_fees_per_cu, METADATA_SEED, rpc_url)
+    let stored_data = url_data(url, pin)?;
+    upload_new_data(&stored_data, program_id, keypair_path, priority_fees_per_cu, seed, rpc_url, flags)
 }
 
-pub fn upload_idl_by_url(
-    url: &str,
+#[allow(clippy::too_many_arguments)]
+pub fn upload_by_account(
+    seed: &str,
+    pointer: &str,
     program_id: &str,
     keypair_path: Option<&str>,
     priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    flags: &UploadFlags,
+    pin: bool,
 ) -> Result<()> {
-    let upload_data = fetch_data_from_url(url)?;
-    upload_data_from_bytes(upload_data, program_id, keypair_path, priority_fees_per_cu, IDL_SEED, rpc_url)
+    let stored_data = parse_account_pointer(pointer, program_id, seed, rpc_url, pin)?;
+    upload_new_data(&stored_data, program_id, keypair_path, priority_fees_per_cu, seed, rpc_url, flags)
 }
 
-pub fn upload_metadata_by_url(
-    url: &str,
+fn upload_new_data(
+    stored_data: &StoredData,
     program_id: &str,
     keypair_path: Option<&str>,
     priority_fees_per_cu: u64,
+    seed: &str,
     rpc_url: Option<&str>,
+    flags: &UploadFlags,
 ) -> Result<()> {
-    let upload_data = fetch_data_from_url(url)?;
-    upload_data_from_bytes(upload_data, program_id, keypair_path, priority_fees_per_cu, METADATA_SEED, rpc_url)
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+
+    // Parse program ID. Programs without an authority pass their keypair instead.
+    let (program_pubkey, program_keypair) = parse_program_id(program_id)?;
+
+    println!("Signer: {}", signer.pubkey());
+    println!("Program: {}", program_pubkey);
+
+    let multisig = load_multisig(&flags.multisig)?;
+    let options = UploadOptions {
+        program_keypair: program_keypair.as_ref(),
+        archive: flags.archive,
+        seed_signer: flags.add_signer_seed.then(|| signer.pubkey()),
+        multisig: as_multisig_authority(&multisig),
+    };
+    if let Some(encoding) = &flags.export_transaction {
+        if program_keypair.is_some() {
+            bail!("--export-transaction can not be combined with a program keypair");
+        }
+        let authority = flags.authority.as_deref()
+            .map(|authority| Pubkey::from_str(authority).map_err(|e| anyhow!("Invalid authority: {}", e)))
+            .transpose()?;
+        let nonce = flags.nonce.as_deref()
+            .map(|nonce| -> Result<DurableNonce> {
+                Ok(DurableNonce {
+                    account: Pubkey::from_str(nonce).map_err(|e| anyhow!("Invalid nonce account: {}", e))?,
+                    authority: flags.nonce_authority.as_deref()
+                        .map(|authority| Pubkey::from_str(authority).map_err(|e| anyhow!("Invalid nonce authority: {}", e)))
+                        .transpose()?,
+                })
+            })
+            .transpose()?;
+        let exported = client.export_upload(&signer, authority, &program_pubkey, seed, stored_data, &options, nonce.as_ref())?;
+        return print_exported_upload(&exported, seed, encoding);
+    }
+    upload(&client, &signer, &program_pubkey, seed, stored_data, &options)
 }
 
-fn upload_data_by_file_path(
-    file_path: &str,
-    program_id: &str,
-    keypair_path: Option<&str>,
-    priority_fees_per_cu: u64,
+fn print_exported_upload(exported: &ExportedUpload, seed: &str, encoding: &str) -> Result<()> {
+    let message = exported.message.serialize();
+    let encoded = match encoding {
+        "base58" => bs58::encode(message).into_string(),
+        "base64" => STANDARD.encode(message),
+        _ => bail!("Invalid encoding: {}, expected base58 or base64", encoding),
+    };
+
+    println!("Buffer {} written", exported.buffer);
+    if let Some(delegate_record) = exported.delegate_record {
+        println!("Sets the buffer as delegate with record {}", delegate_record);
+    }
+    if let Some(archive) = exported.archive {
+        println!("Archives version {} to {}", exported.version - 1, archive);
+    }
+    println!(
+        "Send this {} message with fee payer {} to set version {} of {}:",
+        encoding, exported.authority, exported.version, exported.metadata_address
+    );
+    println!("{}", encoded);
+    println!("Required signers:");
+    for signer in &exported.signers {
+        println!("  {}", signer);
+    }
+    println!(
+        "Once the transaction landed, reclaim the rent of the buffer with: program-metadata {} close-buffer {}",
+        seed, exported.buffer
+    );
+    Ok(())
+}
+
+fn upload(
+    client: &MetadataClient,
+    signer: &Keypair,
+    program_pubkey: &Pubkey,
     seed: &str,
-    rpc_url: Option<&str>,
+    stored_data: &StoredData,
+    options: &UploadOptions,
 ) -> Result<()> {
-    let upload_data = fs::read(file_path)
-        .map_err(|e| anyhow!("Failed to read file: {}", e))?;
-    upload_data_from_bytes(upload_data, program_id, keypair_path, priority_fees_per_cu, seed, rpc_url)
+    let result = client.upload(signer, program_pubkey, seed, stored_data, options)
+        .map_err(|e| match e {
+            Error::NoProgramAuthority(program) => anyhow!(
+                "Program {} has no authority. Pass the path of the program keypair as program id to attest that you deployed it",
+                program
+            ),
+            Error::ImmutableProgram(program) => anyhow!(
+                "Program {} is immutable, so its canonical metadata can not be created anymore. Use --add-signer-seed to publish it as third party metadata",
+                program
+            ),
+            e => e.into(),
+        })?;
+
+    if let Some(signature) = result.initialize_signature {
+        println!("Initialized {} with seed {}. Signature: {}", result.metadata_address, seed, signature);
+    }
+    if let Some(delegate_record) = result.delegate_record {
+        println!("Wrote as delegate with record {}", delegate_record);
+    }
+    if let Some(archive) = result.archive {
+        println!("Archived version {} to {}", result.version - 1, archive);
+    }
+    println!(
+        "Buffer {} set and closed successfully! Version: {}, Signature: {}",
+        result.buffer, result.version, result.signature
+    );
+    Ok(())
 }
 
-// New core function that handles the actual upload
-fn upload_data_from_bytes(
-    upload_data: Vec<u8>,
+// Parses <ADDRESS> or <ADDRESS>:<OFFSET>:<LENGTH>. With pin the data the pointer resolves to now
+// is hashed as content hash.
+fn parse_account_pointer(
+    pointer: &str,
     program_id: &str,
-    keypair_path: Option<&str>,
-    priority_fees_per_cu: u64,
     seed: &str,
     rpc_url: Option<&str>,
-) -> Result<()> {
-    // Get signer and RPC client
-    let (signer, rpc_client) = if let Some(path) = keypair_path {
-        let keypair = solana_sdk::signature::read_keypair_file(path)
-            .map_err(|e| anyhow!("Failed to read keypair file: {}", e))?;
-        let rpc_client = get_user_config(rpc_url)
-            .map(|(_, client)| client)
-            .map_err(|e| anyhow!("Failed to get RPC client: {}", e))?;
-        (keypair, rpc_client)
-    } else {
-        get_user_config(rpc_url)
-            .map_err(|e| anyhow!("Failed to get user config: {}", e))?
+    pin: bool,
+) -> Result<StoredData> {
+    let parts: Vec<&str> = pointer.split(':').collect();
+    let address = Pubkey::from_str(parts[0])
+        .map_err(|e| anyhow!("Invalid account {}: {}", parts[0], e))?;
+    let range = match parts[1..] {
+        [] => None,
+        [offset, length] => Some((
+            offset.parse::<u32>().map_err(|_| anyhow!("Invalid offset {}", offset))?,
+            length.parse::<u32>().map_err(|_| anyhow!("Invalid length {}", length))?,
+        )),
+        _ => return Err(anyhow!("Invalid account pointer {}, expected <ADDRESS> or <ADDRESS>:<OFFSET>:<LENGTH>", pointer)),
     };
 
-    // Parse program ID
-    let program_pubkey = Pubkey::from_str(program_id)
-        .map_err(|e| anyhow!("Invalid program ID: {}", e))?;
-
-    println!("Signer: {}", signer.pubkey());
-    println!("&program_pubkey: {}", &program_pubkey);
-
-    // Get account address
-    let metadata_address = get_metadata_address(seed, &program_pubkey);
+    let mut stored_data = account_pointer_data(&address, range)?;
+    if pin {
+        let client = get_client(rpc_url)?;
+        let (program_pubkey, _) = parse_program_id(program_id)?;
+        client.pin_account_pointer(&get_metadata_address(seed, &program_pubkey), &mut stored_data)?;
+    }
+    Ok(stored_data)
+}
 
-    // Initialize account
-    initialize(&program_pubkey, &signer, priority_fees_per_cu, seed, &rpc_client)?;
+//Download functions
+pub fn download_data_to_file(
+    seed: &str,
+    program_id: &str,
+    output_path: &str,
+    rpc_url: Option<&str>,
+    version: Option<u64>,
+    signer: Option<&str>,
+) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    // Signer-seeded accounts are derived from the key that uploaded them
+    let metadata_address = match signer {
+        Some(signer) => {
+            let signer = Pubkey::from_str(signer)
+                .map_err(|e| anyhow!("Invalid signer: {}", e))?;
+            get_signer_seeded_metadata_address(seed, &program_pubkey, &signer)
+        }
+        None => get_metadata_address(seed, &program_pubkey),
+    };
 
-    // Create buffer
-    let (compressed_data, buffer_keypair) = create_buffer(upload_data, &rpc_client, &signer, priority_fees_per_cu)?;
+    // Decoded data, or the data behind the URL or account the account points to
+    let download_data = client.download(&metadata_address, version)?;
 
-    // Write buffer
-    write_buffer(compressed_data, &buffer_keypair, &signer, &rpc_client, priority_fees_per_cu)?;
+    fs::write(output_path, download_data)
+        .map_err(|e| anyhow!("Failed to write to file: {}", e))?;
 
-    // Set and close buffer
-    set_and_close_buffer(rpc_client, metadata_address, &buffer_keypair, priority_fees_per_cu, &signer, program_pubkey, seed)?;
-    
+    println!("Successfully downloaded and saved data to {}", output_path);
     Ok(())
 }
 
-//Sub-transactions
-fn initialize(
-    program_pubkey: &Pubkey,
-    signer: &Keypair,
-    priority_fees_per_cu: u64,
+//Version functions
+pub fn list_versions(
     seed: &str,
-    rpc_client: &solana_client::rpc_client::RpcClient,
+    program_id: &str,
+    rpc_url: Option<&str>,
 ) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata_address = get_metadata_address(seed, &program_pubkey);
+
+    let metadata = client.fetch_metadata(&metadata_address)?;
+    let Some(stored_data) = &metadata.data else {
+        println!("No data has been uploaded to {} yet", metadata_address);
+        return Ok(());
+    };
 
-    let metadata_address = get_metadata_address(seed, program_pubkey);
+    println!("Versions of {} for program {}:", seed, program_pubkey);
+    for (version, archived_data) in client.fetch_archives(&metadata_address, metadata.version)? {
+        print_version(version, &archived_data, "archived");
+    }
+    let state = if metadata.frozen { "current, frozen" } else { "current" };
+    print_version(metadata.version, stored_data, state);
+    Ok(())
+}
 
-    // Check if account already exists
-    if let Ok(_) = rpc_client.get_account(&metadata_address) { 
-        println!("Data account already exists");
+fn print_version(version: u64, stored_data: &StoredData, state: &str) {
+    println!(
+        "  {} ({}): {} bytes, data hash {}, content hash {}",
+        version,
+        state,
+        stored_data.data.len(),
+        Hash::new_from_array(stored_data.data_hash),
+        Hash::new_from_array(stored_data.content_hash),
+    );
+}
+
+// Third party functions
+// Lists the signer-seeded accounts that anyone can create next to the canonical one
+pub fn list_third_party(seed: &str, program_id: &str, rpc_url: Option<&str>) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let accounts = client.fetch_signer_seeded_metadata(&program_pubkey, seed)?;
+    if accounts.is_empty() {
+        println!("Program {} has no third party {}", program_pubkey, seed);
         return Ok(());
     }
 
-    println!("Initializing Data with seed: {}", seed);
+    println!("Third party {} of program {}:", seed, program_pubkey);
+    for (metadata_address, metadata) in accounts {
+        // The seed signer is not stored, it is only known while it is still the authority
+        let signer = if get_signer_seeded_metadata_address(seed, &program_pubkey, &metadata.authority) == metadata_address {
+            format!("signer {}", metadata.authority)
+        } else {
+            format!("authority {}, not the signer it was created by", metadata.authority)
+        };
+        let size = metadata.data.as_ref().map_or(0, |stored_data| stored_data.data.len());
+        println!("  {} ({}): version {}, {} bytes", metadata_address, signer, metadata.version, size);
+    }
+    Ok(())
+}
 
-    // Get program data address
-    let (program_data_address, _) = Pubkey::find_program_address(
-        &[program_pubkey.as_ref()],
-        &solana_sdk::bpf_loader_upgradeable::ID,
-    );
+// Status functions
+pub fn status(seed: &str, program_id: &str, rpc_url: Option<&str>) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata_address = get_metadata_address(seed, &program_pubkey);
+    let metadata = client.fetch_metadata(&metadata_address)?;
 
-    // Create initialize instruction using the generated code
-    let accounts = Initialize {
-        idl: metadata_address,
-        signer: signer.pubkey(),
-        system_program: solana_sdk::system_program::ID,
-        program_id: *program_pubkey,
-        program_data: program_data_address,
+    println!("The {} of program {} ({}):", seed, program_pubkey, metadata_address);
+    if metadata.frozen {
+        println!("  Authority: none, frozen");
+    } else {
+        println!("  Authority: {}", metadata.authority);
+    }
+    if let Some(pending_authority) = metadata.pending_authority {
+        println!("  Pending authority: {}", pending_authority);
+    }
+    let Some(stored_data) = &metadata.data else {
+        println!("  No data has been uploaded yet");
+        return Ok(());
     };
+    println!("  Version: {}", metadata.version);
+    println!("  Size: {} bytes", stored_data.data.len());
+
+    let recorded_slot = metadata.program_data_slot;
+    match client.fetch_program_deploy_slot(&program_pubkey)? {
+        None => println!("  The loader of the program does not record a deployment slot"),
+        // Set before the program recorded the deployment slot
+        Some(_) if recorded_slot == 0 => {
+            println!("  Program deployment slot at upload: unknown, upload again to record it")
+        }
+        Some(deploy_slot) if recorded_slot < deploy_slot => {
+            println!("  Program deployment slot at upload: {}", recorded_slot);
+            println!("  Program deployment slot now: {}", deploy_slot);
+            println!("Warning: the {} predates the last program upgrade", seed);
+        }
+        Some(deploy_slot) => {
+            println!("  Program deployment slot: {}, up to date", deploy_slot);
+        }
+    }
+    if let Some(executable_hash) = metadata.executable_hash {
+        println!("  Attested executable hash: {}", Hash::new_from_array(executable_hash));
+    }
+    Ok(())
+}
 
-    let args = InitializeInstructionArgs {
-        seed: seed.to_string(),
+// Executable hash functions
+// Hashes the deployed executable on chain and records the digest in the metadata account
+pub fn attest(
+    seed: &str,
+    program_id: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+
+    println!("Hashing the deployed executable of {}, this takes a few transactions", program_id);
+    let multisig = load_multisig(multisig)?;
+    let attestation = client.attest(&signer, &metadata_address, as_multisig_authority(&multisig).as_ref())?;
+
+    println!(
+        "Attested version {} of {} against executable hash {}. Signature: {}",
+        attestation.version,
+        metadata_address,
+        Hash::new_from_array(attestation.executable_hash),
+        attestation.signature,
+    );
+    Ok(())
+}
+
+// Compares the attested executable hash with the digest of the deployed program or of a local .so file
+pub fn verify(seed: &str, program_id: &str, so_path: Option<&str>, rpc_url: Option<&str>) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata = client.fetch_metadata(&get_metadata_address(seed, &program_pubkey))?;
+
+    let deployed_executable = client.fetch_deployed_executable(&program_pubkey)?;
+    let executable = match so_path {
+        Some(so_path) => {
+            let mut executable = fs::read(so_path)
+                .map_err(|e| anyhow!("Failed to read {}: {}", so_path, e))?;
+            // The program data account of the upgradeable loader is zero padded up to the reserved size
+            if executable.len() > deployed_executable.len() {
+                return Err(anyhow!("{} is larger than the deployed program", so_path));
+            }
+            executable.resize(deployed_executable.len(), 0);
+            executable
+        }
+        None => deployed_executable,
     };
+    let computed_hash = executable_hash(&executable);
+    println!("Executable hash of {}: {}", so_path.unwrap_or("the deployed program"), Hash::new_from_array(computed_hash));
+
+    match metadata.executable_hash {
+        None => Err(anyhow!("Version {} of the {} was not attested against an executable", metadata.version, seed)),
+        Some(attested_hash) if attested_hash == computed_hash => {
+            println!("Version {} of the {} was attested against this binary", metadata.version, seed);
+            Ok(())
+        }
+        Some(attested_hash) => Err(anyhow!(
+            "The {} was attested against a different binary with executable hash {}",
+            seed,
+            Hash::new_from_array(attested_hash),
+        )),
+    }
+}
+
+// Uploads an archived version again. This creates a new version with the old data
+// and archives the current one, so the history is never rewritten.
+pub fn rollback(
+    seed: &str,
+    program_id: &str,
+    version: u64,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata_address = get_metadata_address(seed, &program_pubkey);
 
-    let ix = accounts.instruction(args);
+    let stored_data = client.fetch_stored_data(&metadata_address, Some(version))?;
+    println!("Rolling back {} of program {} to version {}", seed, program_pubkey, version);
+    let multisig = load_multisig(multisig)?;
+    let options = UploadOptions {
+        archive: true,
+        multisig: as_multisig_authority(&multisig),
+        ..UploadOptions::default()
+    };
+    upload(&client, &signer, &program_pubkey, seed, &stored_data, &options)
+}
 
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| anyhow!("Failed to get recent blockhash: {}", e))?;
 
-    let transaction = simulate_and_create_transaction(
-        &rpc_client,
-        vec![ix],
-        &[signer],
-        priority_fees_per_cu,
-        recent_blockhash,
-    )?;
+//Close functions
+// Closes the metadata account and returns its rent to the authority
+pub fn close(
+    seed: &str,
+    program_id: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
 
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner_and_commitment(&transaction, CommitmentConfig::confirmed())
-        .map_err(|e| anyhow!("Failed to send transaction: {}", e))?;
+    let multisig = load_multisig(multisig)?;
+    let signature = client.close(&signer, &metadata_address, as_multisig_authority(&multisig).as_ref())?;
 
-    println!("Data initialized successfully!");
-    println!("Signature: {}", signature);
-    println!("Newly created metadata PDA address from seed {seed} : {metadata_address}");
-    
+    println!("Closed {}. Signature: {}", metadata_address, signature);
     Ok(())
 }
 
-fn create_buffer(
-    idl_data: Vec<u8>, 
-    rpc_client: &solana_client::rpc_client::RpcClient, 
-    signer: &Keypair, 
-    priority_fees_per_cu: u64
-) -> Result<(Vec<u8>, Keypair), anyhow::Error> {
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-    encoder.write_all(&idl_data)?;
-    let compressed_data = encoder.finish()?;
-    let data_len = compressed_data.len() + METADATA_OFFSET;
-    let buffer_keypair = Keypair::new();
-    let rent = rpc_client.get_minimum_balance_for_rent_exemption(data_len)
-        .map_err(|e| anyhow!("Failed to get rent: {}", e))?;
-    let create_account_ix = solana_sdk::system_instruction::create_account(
-        &signer.pubkey(),
-        &buffer_keypair.pubkey(),
-        rent,
-        data_len as u64,
-        &METADATA_PROGRAM_ID,
-    );
-    let create_buffer = CreateBufferBuilder::new()
-        .buffer(buffer_keypair.pubkey())
-        .authority(signer.pubkey())
-        .instruction();
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| anyhow!("Failed to get recent blockhash: {}", e))?;
-
-    
-
-    let transaction = simulate_and_create_transaction(
-            &rpc_client,
-            vec![create_account_ix, create_buffer],
-            &[signer, &buffer_keypair],
-            priority_fees_per_cu,
-            recent_blockhash,
-    )?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner_and_commitment(&transaction, CommitmentConfig::confirmed())
-        .map_err(|e| anyhow!("Failed to send transaction: {}", e))?;
-    println!("Buffer account created successfully: {}", buffer_keypair.pubkey());
-    println!("Signature: {}", signature);
-    Ok((compressed_data, buffer_keypair))
-} 
-
-fn write_buffer(
-    compressed_data: Vec<u8>, 
-    buffer_keypair: &Keypair, 
-    signer: &Keypair, 
-    rpc_client: &solana_client::rpc_client::RpcClient, 
-    priority_fees_per_cu: u64
-) -> Result<(), anyhow::Error> {
-    let mut offset = 0;
-    while offset < compressed_data.len() {
-        let chunk_end = std::cmp::min(offset + CHUNK_SIZE as usize, compressed_data.len());
-        let chunk = &compressed_data[offset..chunk_end];
-    
-        let write_buffer = WriteBuffer {
-            buffer: buffer_keypair.pubkey(),
-            signer: signer.pubkey(),
-            system_program: solana_sdk::system_program::ID,
-        };
-    
-        let write_args = WriteBufferInstructionArgs {
-            idl_data: chunk.to_vec(),
-        };
-    
-        let write_ix = write_buffer.instruction(write_args);
-    
-        let recent_blockhash = rpc_client
-            .get_latest_blockhash()
-            .map_err(|e| anyhow!("Failed to get write buffer blockhash: {}", e))?;
-
-
-        let write_transaction = simulate_and_create_transaction(
-            &rpc_client,
-            vec![write_ix],
-            &[signer],
-            priority_fees_per_cu,
-            recent_blockhash,
-        )?;
-    
-        let write_signature = rpc_client
-            .send_and_confirm_transaction_with_spinner_and_commitment(
-                &write_transaction,
-                CommitmentConfig::confirmed()
-            )
-            .map_err(|e| anyhow!("Failed to send write buffer transaction: {}", e))?;
-    
-        println!("Wrote chunk {} to {}, signature: {}", offset, chunk_end, write_signature);
-    
-        offset = chunk_end;
-    }
-    println!("All buffer chunks written successfully!");
+// Closes a buffer that is no longer needed, e.g. after an exported transaction set it
+pub fn close_buffer(
+    buffer: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let buffer = Pubkey::from_str(buffer).map_err(|e| anyhow!("Invalid buffer: {}", e))?;
 
+    let signature = client.close_buffer(&signer, &buffer)?;
+
+    println!("Closed buffer {}. Signature: {}", buffer, signature);
     Ok(())
 }
 
-fn set_and_close_buffer(
-    rpc_client: solana_client::rpc_client::RpcClient, 
-    idl_address: Pubkey, 
-    buffer_keypair: &Keypair, 
-    priority_fees_per_cu: u64, 
-    signer: &Keypair, 
-    program_pubkey: Pubkey,
+
+//Authority functions
+// Proposes a new authority. It has to be accepted by the new authority before it takes effect.
+pub fn set_authority(
     seed: &str,
+    program_id: &str,
+    new_authority: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
 ) -> Result<()> {
-    let idl_account_info = get_account_with_retry(&rpc_client, &idl_address, 10, 2)?;
-    let buffer_account_info = get_account_with_retry(&rpc_client, &buffer_keypair.pubkey(), 10, 2)?;
-
-    let idl_account_size = idl_account_info.data.len();
-    let buffer_account_size = buffer_account_info.data.len();
-    
-    println!("Data account size: {}, Buffer account size: {}", idl_account_size, buffer_account_size);
-    let mut instructions = vec![];
-    if buffer_account_size < idl_account_size {
-        // Shrink IDL account to buffer size
-        let resize = Resize {
-            idl: idl_address,
-            signer: signer.pubkey(),
-            system_program: solana_sdk::system_program::ID,
-            program_id: program_pubkey,
-        }.instruction(ResizeInstructionArgs {
-            len: buffer_account_size as u16,
-            seed: seed.to_string(),
-        });
-        instructions.push(resize);
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+    let new_authority = Pubkey::from_str(new_authority)
+        .map_err(|e| anyhow!("Invalid new authority: {}", e))?;
+
+    let multisig = load_multisig(multisig)?;
+    let signature = client.set_authority(&signer, &metadata_address, &new_authority, as_multisig_authority(&multisig).as_ref())?;
+
+    if new_authority == Pubkey::default() {
+        println!("Authority of {} erased. Signature: {}", metadata_address, signature);
     } else {
-        let mut current_size = idl_account_size;
-        let target_size = buffer_account_size;
-
-        while current_size < target_size {
-            let next_size = std::cmp::min(
-                current_size + MAX_RESIZE_STEP as usize,
-                target_size
-            );
-
-            let resize = Resize {
-                idl: idl_address,
-                signer: signer.pubkey(),
-                system_program: solana_sdk::system_program::ID,
-                program_id: program_pubkey,
-            }.instruction(ResizeInstructionArgs {
-                len: next_size as u16,
-                seed: seed.to_string(),
-            });
-            instructions.push(resize);
-
-            println!("Adding resize instruction to size {}", next_size);
-            current_size = next_size;
-        }
+        println!("Proposed {} as new authority of {}. Signature: {}", new_authority, metadata_address, signature);
+        println!("The new authority has to run accept-authority for the change to take effect.");
     }
-    let set_buffer = SetBuffer {
-        buffer: buffer_keypair.pubkey(),
-        idl: idl_address,
-        authority: signer.pubkey(),
-        program_id: program_pubkey,
-    }.instruction(SetBufferInstructionArgs {
-        seed: seed.to_string(),
-    });
-    println!("Set buffer instruction");
-    instructions.push(set_buffer);
-    let close_buffer = CloseBuffer {
-        buffer: buffer_keypair.pubkey(),
-        authority: signer.pubkey(),
-    }.instruction();
-    instructions.push(close_buffer);
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| anyhow!("Failed to get recent blockhash: {}", e))?;
-    
-    let transaction = simulate_and_create_transaction(
-        &rpc_client,
-        instructions,
-        &[signer],
-        priority_fees_per_cu,
-        recent_blockhash,
-    )?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner_and_commitment(
-            &transaction,
-            CommitmentConfig::confirmed()
-        )
-        .map_err(|e| anyhow!("Failed to send set buffer transaction: {}", e))?;
-    println!("Buffer set and closed successfully! Signature: {}", signature);
     Ok(())
 }
 
-
-//Download functions
-pub fn download_idl_to_file(
+// Signed by the proposed authority to complete the transfer
+pub fn accept_authority(
+    seed: &str,
     program_id: &str,
-    output_path: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
 ) -> Result<()> {
-    download_data_to_file(program_id, output_path, IDL_SEED, rpc_url)
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+
+    let multisig = load_multisig(multisig)?;
+    let signature = client.accept_authority(&signer, &metadata_address, as_multisig_authority(&multisig).as_ref())?;
+
+    let new_authority = multisig.map_or(signer.pubkey(), |(multisig, _)| multisig);
+    println!("{} is now the authority of {}. Signature: {}", new_authority, metadata_address, signature);
+    Ok(())
 }
 
-pub fn download_metadata_to_file(
+pub fn cancel_authority(
+    seed: &str,
     program_id: &str,
-    output_path: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
 ) -> Result<()> {
-    download_data_to_file(program_id, output_path, METADATA_SEED, rpc_url)
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+
+    let multisig = load_multisig(multisig)?;
+    let signature = client.cancel_authority(&signer, &metadata_address, as_multisig_authority(&multisig).as_ref())?;
+
+    println!("Cancelled the pending authority transfer of {}. Signature: {}", metadata_address, signature);
+    Ok(())
 }
 
-pub fn download_data_to_file(
-    program_id: &str,
-    output_path: &str,
+
+//Delegate functions
+// Lets the delegate upload new versions until the expiry slot, without giving it the authority
+#[allow(clippy::too_many_arguments)]
+pub fn add_delegate(
     seed: &str,
+    program_id: &str,
+    delegate: &str,
+    expiry_slot: Option<u64>,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
     rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
 ) -> Result<()> {
-    let (_, rpc_client) = get_user_config(rpc_url)?;
-    
-    // Parse program ID
-    let program_pubkey = Pubkey::from_str(program_id)
-        .map_err(|e| anyhow!("Invalid program ID: {}", e))?;
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+    let delegate = Pubkey::from_str(delegate)
+        .map_err(|e| anyhow!("Invalid delegate: {}", e))?;
 
-    // Get account address
-    let metadata_address = get_metadata_address(seed, &program_pubkey);
+    let multisig = load_multisig(multisig)?;
+    let signature = client.add_delegate(&signer, &metadata_address, &delegate, expiry_slot, as_multisig_authority(&multisig).as_ref())?;
 
-    // Get account data
-    let account = rpc_client.get_account(&metadata_address)
-        .map_err(|e| anyhow!("Failed to get account data: {}", e))?;
+    match expiry_slot {
+        Some(expiry_slot) => println!("Added {} as delegate of {} until slot {}. Signature: {}", delegate, metadata_address, expiry_slot, signature),
+        None => println!("Added {} as delegate of {}. Signature: {}", delegate, metadata_address, signature),
+    }
+    Ok(())
+}
 
-    // Get data length from account (4 bytes at offset 40)
-    let data_len_bytes = account.data[METADATA_OFFSET - 4..METADATA_OFFSET].try_into()
-        .map_err(|_| anyhow!("Failed to read data length"))?;
-    let data_length = u32::from_le_bytes(data_len_bytes);
+pub fn list_delegates(seed: &str, program_id: &str, rpc_url: Option<&str>) -> Result<()> {
+    let client = get_client(rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+    let authority = client.fetch_metadata(&metadata_address)?.authority;
+    let current_slot = client.rpc_client().get_slot()
+        .map_err(|e| anyhow!("Failed to get slot: {}", e))?;
 
-    // Get compressed data
-    let compressed_data = &account.data[METADATA_OFFSET..METADATA_OFFSET + data_length as usize];
+    let records = client.fetch_delegates(&metadata_address)?;
+    if records.is_empty() {
+        println!("{} has no delegates", metadata_address);
+        return Ok(());
+    }
 
-    // Decompress data
-    let mut decoder = GzDecoder::new(compressed_data);
-    let mut download_data = Vec::new();
-    decoder.read_to_end(&mut download_data)?;
+    println!("Delegates of {}:", metadata_address);
+    for (record_address, record) in records {
+        // Records of a previous authority and expired records can only be revoked
+        let state = if record.authority != authority {
+            "inactive, added by a previous authority".to_string()
+        } else if !record.is_active(&authority, current_slot) {
+            format!("expired at slot {}", record.expiry_slot)
+        } else if record.expiry_slot != 0 {
+            format!("active until slot {}", record.expiry_slot)
+        } else {
+            "active".to_string()
+        };
+        println!("  {} ({}), record {}", record.delegate, state, record_address);
+    }
+    Ok(())
+}
 
-    // Write to file
-    fs::write(output_path, download_data)
-        .map_err(|e| anyhow!("Failed to write to file: {}", e))?;
+pub fn revoke_delegate(
+    seed: &str,
+    program_id: &str,
+    delegate: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    multisig: &MultisigFlags,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let metadata_address = get_metadata_address(seed, &parse_pubkey(program_id)?);
+    let delegate = Pubkey::from_str(delegate)
+        .map_err(|e| anyhow!("Invalid delegate: {}", e))?;
 
-    println!("Successfully downloaded and saved data to {}", output_path);
+    let multisig = load_multisig(multisig)?;
+    let signature = client.revoke_delegate(&signer, &metadata_address, &delegate, as_multisig_authority(&multisig).as_ref())?;
+
+    println!("Revoked delegate {} of {}. Signature: {}", delegate, metadata_address, signature);
     Ok(())
 }
 
 
-// Utility functions
-fn get_account_with_retry(
-    rpc_client: &solana_client::rpc_client::RpcClient,
-    pubkey: &Pubkey,
-    max_attempts: u32,
-    retry_delay_secs: u64,
-) -> Result<solana_sdk::account::Account> {
-    let mut attempts = 0;
-    loop {
-        match rpc_client.get_account(pubkey) {
-            Ok(account) => return Ok(account),
-            Err(e) => {
-                attempts += 1;
-                if attempts >= max_attempts {
-                    return Err(anyhow!("Failed to get account {} after {} attempts: {}", pubkey, max_attempts, e));
-                }
-                println!("Failed to get account {}, retrying in {} seconds... (attempt {}/{})", 
-                    pubkey, retry_delay_secs, attempts, max_attempts);
-                std::thread::sleep(std::time::Duration::from_secs(retry_delay_secs));
-            }
-        }
+//Freeze functions
+// Permanently freezes the metadata account. This can not be undone.
+pub fn freeze(
+    seed: &str,
+    program_id: &str,
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+    skip_confirmation: bool,
+    multisig: &MultisigFlags,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata_address = get_metadata_address(seed, &program_pubkey);
+
+    if client.fetch_metadata(&metadata_address)?.frozen {
+        println!("{} is already frozen", metadata_address);
+        return Ok(());
     }
-}
 
-fn get_metadata_address(seed: &str, program_pubkey: &Pubkey) -> Pubkey {
-    let (metadata_address, _) = Pubkey::find_program_address(
-        &[seed.as_bytes(), program_pubkey.as_ref()],
-        &METADATA_PROGRAM_ID,
-    );
-    metadata_address
-}
-
-fn fetch_data_from_url(
-    url: &str
-) -> Result<Vec<u8>> {
-    let client = Client::new();
-    let response = client.get(url)
-        .send()
-        .map_err(|e| anyhow!("Failed to fetch from URL: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(anyhow!("Failed to fetch URL: HTTP {}", response.status()));
+    if !skip_confirmation {
+        println!("Freezing {} of program {} ({}) can not be undone.", seed, program_pubkey, metadata_address);
+        println!("Nobody, including the program upgrade authority, will be able to change or close it again.");
+        print!("Type 'freeze' to continue: ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if answer.trim() != "freeze" {
+            println!("Aborted");
+            return Ok(());
+        }
     }
 
-    response.bytes()
-        .map_err(|e| anyhow!("Failed to read response body: {}", e))
-        .map(|b| b.to_vec())
+    let multisig = load_multisig(multisig)?;
+    let signature = client.freeze(&signer, &metadata_address, as_multisig_authority(&multisig).as_ref())?;
+
+    println!("{} is now frozen! Signature: {}", metadata_address, signature);
+    Ok(())
 }
 
-// Add to utility functions section
-fn simulate_and_create_transaction(
-    rpc_client: &solana_client::rpc_client::RpcClient,
-    instructions: Vec<solana_sdk::instruction::Instruction>,
-    signers: &[&Keypair],
+
+//Migrate functions
+// Converts a MetadataAccount or MetadataAccount2 into a MetadataAccount3 at the same address
+pub fn migrate(
+    seed: &str,
+    program_id: &str,
+    keypair_path: Option<&str>,
     priority_fees_per_cu: u64,
-    recent_blockhash: solana_sdk::hash::Hash,
-) -> Result<Transaction> {
-    // Add compute budget instructions
-    let mut simulation_instructions = vec![
-        ComputeBudgetInstruction::set_compute_unit_price(priority_fees_per_cu),
-        ComputeBudgetInstruction::set_compute_unit_limit(200000),
-    ];
-    simulation_instructions.extend(instructions.clone());
-
-    // Create and simulate transaction
-    let transaction_to_simulate = Transaction::new_signed_with_payer(
-        &simulation_instructions,
-        Some(&signers[0].pubkey()),
-        signers,
-        recent_blockhash,
-    );
+    rpc_url: Option<&str>,
+) -> Result<()> {
+    let (signer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+    let program_pubkey = parse_pubkey(program_id)?;
+    let metadata_address = get_metadata_address(seed, &program_pubkey);
 
-    let simulation = rpc_client.simulate_transaction(&transaction_to_simulate)
-        .map_err(|e| anyhow!("Failed to simulate transaction: {}", e))?;
+    match client.migrate(&signer, &metadata_address, &program_pubkey)? {
+        Some(migration) => println!(
+            "Migrated {} {} to MetadataAccount3. Signature: {}",
+            migration.layout, metadata_address, migration.signature
+        ),
+        None => println!("{} is already a MetadataAccount3, nothing to migrate", metadata_address),
+    }
+    Ok(())
+}
 
-    let units_consumed: u32 = simulation.value.units_consumed.unwrap_or(0) as u32;
-    println!("Compute units from simulation: {}", units_consumed);
 
-    // Create final transaction with adjusted compute limit
-    let mut final_instructions = vec![
-        ComputeBudgetInstruction::set_compute_unit_price(priority_fees_per_cu),
-        ComputeBudgetInstruction::set_compute_unit_limit(units_consumed + 200),
-    ];
-    final_instructions.extend(instructions);
+// Utility functions
+pub(crate) fn get_signer_and_client(
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+) -> Result<(Keypair, MetadataClient)> {
+    let (signer, rpc_client) = if let Some(path) = keypair_path {
+        let keypair = solana_sdk::signature::read_keypair_file(path)
+            .map_err(|e| anyhow!("Failed to read keypair file: {}", e))?;
+        let rpc_client = get_user_config(rpc_url)
+            .map(|(_, client)| client)
+            .map_err(|e| anyhow!("Failed to get RPC client: {}", e))?;
+        (keypair, rpc_client)
+    } else {
+        get_user_config(rpc_url)
+            .map_err(|e| anyhow!("Failed to get user config: {}", e))?
+    };
+    Ok((signer, MetadataClient::new(rpc_client).with_priority_fees(priority_fees_per_cu)))
+}
 
-    Ok(Transaction::new_signed_with_payer(
-        &final_instructions,
-        Some(&signers[0].pubkey()),
-        signers,
-        recent_blockhash,
-    ))
+pub(crate) fn get_client(rpc_url: Option<&str>) -> Result<MetadataClient> {
+    let (_, rpc_client) = get_user_config(rpc_url)?;
+    Ok(MetadataClient::new(rpc_client))
 }
 
+fn parse_pubkey(program_id: &str) -> Result<Pubkey> {
+    Pubkey::from_str(program_id).map_err(|e| anyhow!("Invalid program ID: {}", e))
+}
 
+// Accepts a program id or the path of the program keypair. The keypair is only
+// needed to claim programs that have no authority.
+fn parse_program_id(program_id: &str) -> Result<(Pubkey, Option<Keypair>)> {
+    if let Ok(program_pubkey) = Pubkey::from_str(program_id) {
+        return Ok((program_pubkey, None));
+    }
+    let program_keypair = solana_sdk::signature::read_keypair_file(program_id)
+        .map_err(|_| anyhow!("Invalid program ID: {} is neither a public key nor a keypair file", program_id))?;
+    Ok((program_keypair.pubkey(), Some(program_keypair)))
+}
 
+// Reads the multisig address and the keypairs of the other signers
+fn load_multisig(flags: &MultisigFlags) -> Result<Option<(Pubkey, Vec<Keypair>)>> {
+    let Some(multisig) = &flags.multisig else {
+        if !flags.signers.is_empty() {
+            bail!("--multisig-signer requires --multisig");
+        }
+        return Ok(None);
+    };
+    let multisig = Pubkey::from_str(multisig).map_err(|e| anyhow!("Invalid multisig: {}", e))?;
+    let co_signers = flags.signers
+        .iter()
+        .map(|path| solana_sdk::signature::read_keypair_file(path)
+            .map_err(|e| anyhow!("Failed to read multisig signer {}: {}", path, e)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some((multisig, co_signers)))
+}
 
-    
\ No newline at end of file
+fn as_multisig_authority(multisig: &Option<(Pubkey, Vec<Keypair>)>) -> Option<MultisigAuthority<'_>> {
+    multisig.as_ref().map(|(multisig, co_signers)| MultisigAuthority {
+        multisig: *multisig,
+        co_signers,
+    })
+}
@@ -1 +1,3 @@
-pub mod idl; 
\ No newline at end of file
+pub mod idl;
+pub mod multisig;
+pub mod watch;
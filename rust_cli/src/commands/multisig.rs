@@ -0,0 +1,70 @@
+use anyhow::{Result, anyhow};
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use std::str::FromStr;
+use program_metadata_client::codama_sdk::instructions::{CreateMultisig, CreateMultisigInstructionArgs};
+use program_metadata_client::state::account_discriminator;
+use crate::commands::idl::{get_client, get_signer_and_client};
+
+// Mirror of the MetadataMultisig account of the metadata program
+#[derive(BorshDeserialize, Debug)]
+struct MetadataMultisig {
+    threshold: u8,
+    signers: Vec<Pubkey>,
+}
+
+// Creates a multisig account that can be set as the authority of a metadata account
+pub fn create_multisig(
+    threshold: u8,
+    signers: &[String],
+    keypair_path: Option<&str>,
+    priority_fees_per_cu: u64,
+    rpc_url: Option<&str>,
+) -> Result<()> {
+    let (payer, client) = get_signer_and_client(keypair_path, priority_fees_per_cu, rpc_url)?;
+
+    let signers = signers
+        .iter()
+        .map(|signer| Pubkey::from_str(signer).map_err(|e| anyhow!("Invalid signer {}: {}", signer, e)))
+        .collect::<Result<Vec<_>>>()?;
+    let multisig_keypair = Keypair::new();
+
+    let create_multisig_ix = CreateMultisig {
+        multisig: multisig_keypair.pubkey(),
+        payer: payer.pubkey(),
+        system_program: solana_sdk::system_program::ID,
+    }.instruction(CreateMultisigInstructionArgs {
+        threshold,
+        signers,
+    });
+
+    let signature = client.send_transaction(vec![create_multisig_ix], &[&payer, &multisig_keypair])
+        .map_err(|e| anyhow!("Failed to create multisig: {}", e))?;
+
+    println!("Created multisig {}. Signature: {}", multisig_keypair.pubkey(), signature);
+    println!("Propose it as authority with set-authority, then {} signers have to accept it.", threshold);
+    Ok(())
+}
+
+pub fn show_multisig(address: &str, rpc_url: Option<&str>) -> Result<()> {
+    let client = get_client(rpc_url)?;
+
+    let address = Pubkey::from_str(address)
+        .map_err(|e| anyhow!("Invalid multisig address: {}", e))?;
+    let account = client.rpc_client().get_account(&address)
+        .map_err(|e| anyhow!("Failed to get account data: {}", e))?;
+    let multisig = account.data
+        .strip_prefix(&account_discriminator("MetadataMultisig"))
+        .and_then(|mut data| MetadataMultisig::deserialize(&mut data).ok())
+        .ok_or_else(|| anyhow!("{} is not a multisig account", address))?;
+
+    println!("Multisig {}: {} of {} signers", address, multisig.threshold, multisig.signers.len());
+    for signer in multisig.signers {
+        println!("  {}", signer);
+    }
+    Ok(())
+}
@@ -0,0 +1,323 @@
+use anyhow::{Result, anyhow};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshDeserialize;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::{hash, Hash},
+    pubkey::Pubkey,
+};
+use std::fmt;
+use std::str::FromStr;
+use program_metadata_client::METADATA_PROGRAM_ID;
+use crate::config::get_websocket_url;
+
+// Anchor writes events with emit! as base64 encoded "Program data:" log lines
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+// Mirrors of the events of the metadata program
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataInitialized {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub signer_seeded: bool,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataSet {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub version: u64,
+    pub data_hash: [u8; 32],
+    pub content_hash: [u8; 32],
+    pub archived: bool,
+    pub program_data_slot: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorityChanged {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataResized {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub account_size: u32,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataFrozen {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub data_len: u32,
+    pub version: u64,
+    pub content_hash: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataMigrated {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+    pub data_len: u32,
+    pub version: u64,
+    pub content_hash: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct DelegateAdded {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub delegate: Pubkey,
+    pub expiry_slot: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct DelegateRevoked {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub delegate: Pubkey,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ExecutableHashed {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub version: u64,
+    pub program_data_slot: u64,
+    pub executable_hash: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct MetadataClosed {
+    pub metadata: Pubkey,
+    pub program_id: Pubkey,
+    pub data_type: String,
+    pub authority: Pubkey,
+}
+
+#[derive(Debug)]
+pub enum MetadataEvent {
+    Initialized(MetadataInitialized),
+    Set(MetadataSet),
+    AuthorityChanged(AuthorityChanged),
+    Resized(MetadataResized),
+    Frozen(MetadataFrozen),
+    Migrated(MetadataMigrated),
+    DelegateAdded(DelegateAdded),
+    DelegateRevoked(DelegateRevoked),
+    ExecutableHashed(ExecutableHashed),
+    Closed(MetadataClosed),
+}
+
+impl MetadataEvent {
+    // Decodes the data of a "Program data:" log line. Returns None for data that is not a metadata event.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut event_data) = data.split_at(8);
+        let event = if discriminator == event_discriminator("MetadataInitialized") {
+            Self::Initialized(MetadataInitialized::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("MetadataSet") {
+            Self::Set(MetadataSet::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("AuthorityChanged") {
+            Self::AuthorityChanged(AuthorityChanged::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("MetadataResized") {
+            Self::Resized(MetadataResized::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("MetadataFrozen") {
+            Self::Frozen(MetadataFrozen::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("MetadataMigrated") {
+            Self::Migrated(MetadataMigrated::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("DelegateAdded") {
+            Self::DelegateAdded(DelegateAdded::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("DelegateRevoked") {
+            Self::DelegateRevoked(DelegateRevoked::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("ExecutableHashed") {
+            Self::ExecutableHashed(ExecutableHashed::deserialize(&mut event_data).ok()?)
+        } else if discriminator == event_discriminator("MetadataClosed") {
+            Self::Closed(MetadataClosed::deserialize(&mut event_data).ok()?)
+        } else {
+            return None;
+        };
+        Some(event)
+    }
+
+    pub fn program_id(&self) -> &Pubkey {
+        match self {
+            Self::Initialized(event) => &event.program_id,
+            Self::Set(event) => &event.program_id,
+            Self::AuthorityChanged(event) => &event.program_id,
+            Self::Resized(event) => &event.program_id,
+            Self::Frozen(event) => &event.program_id,
+            Self::Migrated(event) => &event.program_id,
+            Self::DelegateAdded(event) => &event.program_id,
+            Self::DelegateRevoked(event) => &event.program_id,
+            Self::ExecutableHashed(event) => &event.program_id,
+            Self::Closed(event) => &event.program_id,
+        }
+    }
+}
+
+impl fmt::Display for MetadataEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Initialized(event) => write!(
+                f,
+                "Initialized {} {} of {}: authority {}{}",
+                event.data_type, event.metadata, event.program_id, event.authority,
+                if event.signer_seeded { " (signer seeded)" } else { "" },
+            ),
+            Self::Set(event) => write!(
+                f,
+                "Set {} {} of {} to version {}: {} bytes, data hash {}, content hash {}, authority {}, program deployed at slot {}{}",
+                event.data_type, event.metadata, event.program_id, event.version, event.data_len,
+                Hash::new_from_array(event.data_hash), Hash::new_from_array(event.content_hash), event.authority,
+                event.program_data_slot,
+                if event.archived { ", previous version archived" } else { "" },
+            ),
+            Self::AuthorityChanged(event) => {
+                write!(
+                    f,
+                    "Authority of {} {} of {}: {}",
+                    event.data_type, event.metadata, event.program_id, event.authority,
+                )?;
+                if event.pending_authority != Pubkey::default() {
+                    write!(f, ", pending {}", event.pending_authority)?;
+                }
+                Ok(())
+            }
+            Self::Resized(event) => write!(
+                f,
+                "Resized {} {} of {} to {} bytes: {} bytes of data, authority {}",
+                event.data_type, event.metadata, event.program_id, event.account_size, event.data_len, event.authority,
+            ),
+            Self::Frozen(event) => write!(
+                f,
+                "Froze {} {} of {} at version {}: {} bytes, content hash {}",
+                event.data_type, event.metadata, event.program_id, event.version, event.data_len,
+                Hash::new_from_array(event.content_hash),
+            ),
+            Self::Migrated(event) => write!(
+                f,
+                "Migrated {} {} of {} to version {}: {} bytes, content hash {}, authority {}",
+                event.data_type, event.metadata, event.program_id, event.version, event.data_len,
+                Hash::new_from_array(event.content_hash), event.authority,
+            ),
+            Self::DelegateAdded(event) => {
+                write!(
+                    f,
+                    "Added delegate {} to {} {} of {}",
+                    event.delegate, event.data_type, event.metadata, event.program_id,
+                )?;
+                if event.expiry_slot != 0 {
+                    write!(f, " until slot {}", event.expiry_slot)?;
+                }
+                Ok(())
+            }
+            Self::DelegateRevoked(event) => write!(
+                f,
+                "Revoked delegate {} of {} {} of {}",
+                event.delegate, event.data_type, event.metadata, event.program_id,
+            ),
+            Self::ExecutableHashed(event) => write!(
+                f,
+                "Attested version {} of {} {} of {} against executable hash {}, deployed at slot {}",
+                event.version, event.data_type, event.metadata, event.program_id,
+                Hash::new_from_array(event.executable_hash), event.program_data_slot,
+            ),
+            Self::Closed(event) => write!(
+                f,
+                "Closed {} {} of {}: authority {}",
+                event.data_type, event.metadata, event.program_id, event.authority,
+            ),
+        }
+    }
+}
+
+// Subscribes to the logs of the metadata program and prints every event until the connection closes
+pub fn watch(program_id: Option<&str>, rpc_url: Option<&str>) -> Result<()> {
+    let program_filter = program_id
+        .map(Pubkey::from_str)
+        .transpose()
+        .map_err(|e| anyhow!("Invalid program ID: {}", e))?;
+    let websocket_url = get_websocket_url(rpc_url)?;
+
+    let (_subscription, receiver) = PubsubClient::logs_subscribe(
+        &websocket_url,
+        RpcTransactionLogsFilter::Mentions(vec![METADATA_PROGRAM_ID.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )
+    .map_err(|e| anyhow!("Failed to subscribe to {}: {}", websocket_url, e))?;
+
+    println!("Watching metadata events on {}", websocket_url);
+    for response in receiver {
+        let logs = response.value;
+        if logs.err.is_some() {
+            continue;
+        }
+        for event in decode_events(&logs.logs) {
+            if program_filter.is_none_or(|program_filter| *event.program_id() == program_filter) {
+                println!("{} {}", logs.signature, event);
+            }
+        }
+    }
+
+    Err(anyhow!("Websocket subscription closed"))
+}
+
+// Only "Program data:" lines written while the metadata program itself is executing are
+// decoded, so events of other programs in the same transaction are ignored.
+fn decode_events(logs: &[String]) -> Vec<MetadataEvent> {
+    let metadata_program = METADATA_PROGRAM_ID.to_string();
+    let mut program_stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if program_stack.last() == Some(&metadata_program.as_str()) {
+                if let Some(event) = STANDARD.decode(data).ok().and_then(|data| MetadataEvent::decode(&data)) {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            let program = parts.next().unwrap_or_default();
+            match parts.next() {
+                Some("invoke") => program_stack.push(program),
+                Some("success") | Some("failed:") => {
+                    program_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+fn event_discriminator(event_name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("event:{}", event_name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
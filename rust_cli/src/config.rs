@@ -1,6 +1,6 @@
 use anyhow::{Result, anyhow};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::signature::Keypair;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
 use solana_cli_config::Config;
 
 pub fn get_user_config(rpc_url: Option<&str>) -> Result<(Keypair, RpcClient)> {
@@ -12,14 +12,32 @@ pub fn get_user_config(rpc_url: Option<&str>) -> Result<(Keypair, RpcClient)> {
     let signer = get_keypair_from_path(&cli_config.keypair_path)?;
 
     let rpc_client = if let Some(rpc_url) = rpc_url {
-        RpcClient::new(rpc_url.to_string())
+        RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed())
     } else {
-        RpcClient::new(cli_config.json_rpc_url.clone())
+        RpcClient::new_with_commitment(cli_config.json_rpc_url.clone(), CommitmentConfig::confirmed())
     };
     Ok((signer, rpc_client))
 }
 
+// Websocket URL that belongs to the given RPC URL or to the RPC URL of the config
+pub fn get_websocket_url(rpc_url: Option<&str>) -> Result<String> {
+    if let Some(rpc_url) = rpc_url {
+        return Ok(Config::compute_websocket_url(rpc_url));
+    }
+
+    let config_file = solana_cli_config::CONFIG_FILE
+        .as_ref()
+        .ok_or_else(|| anyhow!("Unable to get config file path"))?;
+    let cli_config: Config = Config::load(config_file)?;
+
+    if cli_config.websocket_url.is_empty() {
+        Ok(Config::compute_websocket_url(&cli_config.json_rpc_url))
+    } else {
+        Ok(cli_config.websocket_url)
+    }
+}
+
 pub fn get_keypair_from_path(path: &str) -> Result<Keypair> {
-    solana_clap_utils::keypair::keypair_from_path(&Default::default(), &path, "keypair", false)
+    solana_clap_utils::keypair::keypair_from_path(&Default::default(), path, "keypair", false)
         .map_err(|err| anyhow!("Unable to get signer from path: {}", err))
 } 
\ No newline at end of file
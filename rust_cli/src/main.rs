@@ -1,97 +1,42 @@
 mod commands;
 mod config;
 mod cli;
-mod codama_sdk;
-use codama_sdk::programs::METADATA_PROGRAM_ID;
 
 use anyhow::{Result, anyhow};
+use clap::ArgMatches;
+use commands::idl::{MultisigFlags, UploadFlags};
+use program_metadata_client::{IDL_SEED, METADATA_SEED};
 
 fn main() -> Result<()> {
     let matches = cli::get_clap_app().get_matches();
 
     match matches.subcommand() {
-        Some(("idl", idl_matches)) => {
-            match idl_matches.subcommand() {
-                Some(("upload", upload_matches)) => {
-                    let idl_path = upload_matches.get_one::<String>("idl-path").unwrap();
-                    let program_id = upload_matches.get_one::<String>("program-id").unwrap();
-                    let keypair_path = upload_matches.get_one::<String>("keypair");
-                    let priority_fees_per_cu = upload_matches
-                        .get_one::<String>("priority-fees-per-cu")
-                        .unwrap_or(&"0".to_string())
-                        .parse::<u64>()
-                        .map_err(|_| anyhow!("Invalid priority fees value"))?;
-                    let rpc_url = upload_matches.get_one::<String>("rpc-url");
-                    
-                    commands::idl::upload_idl_by_file_path(idl_path, program_id, keypair_path.map(|s| s.as_str()), priority_fees_per_cu, rpc_url.map(|s| s.as_str()))
-                }
-                Some(("upload-url", upload_matches)) => {
-                    let url = upload_matches.get_one::<String>("url").unwrap();
-                    let program_id = upload_matches.get_one::<String>("program-id").unwrap();
-                    let keypair_path = upload_matches.get_one::<String>("keypair");
-                    let priority_fees_per_cu = upload_matches
-                        .get_one::<String>("priority-fees-per-cu")
-                        .unwrap_or(&"0".to_string())
-                        .parse::<u64>()
-                        .map_err(|_| anyhow!("Invalid priority fees value"))?;
-                    let rpc_url = upload_matches.get_one::<String>("rpc-url");
-                    
-                    commands::idl::upload_idl_by_url(url, program_id, keypair_path.map(|s| s.as_str()), priority_fees_per_cu, rpc_url.map(|s| s.as_str()))
-                }
-                Some(("download", download_matches)) => {
-                    let program_id = download_matches.get_one::<String>("program-id").unwrap();
-                    let output_path = download_matches
-                        .get_one::<String>("output")
-                        .map(|s| s.as_str())
-                        .unwrap_or("idl.json");
-                    let rpc_url = download_matches.get_one::<String>("rpc-url");
-                    commands::idl::download_idl_to_file(program_id, output_path, rpc_url.map(|s| s.as_str()))
-                }
-                _ => {
-                    println!("Unknown IDL command. Use --help to see available commands");
-                    Ok(())
-                }
-            }
+        Some((seed @ (IDL_SEED | METADATA_SEED), data_matches)) => run_data_command(seed, data_matches),
+        Some(("watch", watch_matches)) => {
+            let program_id = str_arg(watch_matches, "program-id");
+            commands::watch::watch(program_id, rpc_url(watch_matches))
         }
-        Some(("metadata", metadata_matches)) => {
-            match metadata_matches.subcommand() {
-                Some(("upload", upload_matches)) => {
-                    let metadata_path = upload_matches.get_one::<String>("metadata-path").unwrap();
-                    let program_id = upload_matches.get_one::<String>("program-id").unwrap();
-                    let keypair_path = upload_matches.get_one::<String>("keypair");
-                    let priority_fees_per_cu = upload_matches
-                        .get_one::<String>("priority-fees-per-cu")
-                        .unwrap_or(&"0".to_string())
-                        .parse::<u64>()
-                        .map_err(|_| anyhow!("Invalid priority fees value"))?;
-                    let rpc_url = upload_matches.get_one::<String>("rpc-url");
-                    
-                    commands::idl::upload_metadata_by_file_path(metadata_path, program_id, keypair_path.map(|s| s.as_str()), priority_fees_per_cu, rpc_url.map(|s| s.as_str()))
+        Some(("multisig", multisig_matches)) => {
+            match multisig_matches.subcommand() {
+                Some(("create", create_matches)) => {
+                    let threshold = create_matches
+                        .get_one::<String>("threshold")
+                        .unwrap()
+                        .parse::<u8>()
+                        .map_err(|_| anyhow!("Invalid threshold"))?;
+                    let signers: Vec<String> = create_matches
+                        .get_many::<String>("signers")
+                        .unwrap()
+                        .cloned()
+                        .collect();
+                    commands::multisig::create_multisig(threshold, &signers, keypair_path(create_matches), priority_fees(create_matches), rpc_url(create_matches))
                 }
-                Some(("upload-url", upload_matches)) => {
-                    let url = upload_matches.get_one::<String>("url").unwrap();
-                    let program_id = upload_matches.get_one::<String>("program-id").unwrap();
-                    let keypair_path = upload_matches.get_one::<String>("keypair");
-                    let priority_fees_per_cu = upload_matches
-                        .get_one::<String>("priority-fees-per-cu")
-                        .unwrap_or(&"0".to_string())
-                        .parse::<u64>()
-                        .map_err(|_| anyhow!("Invalid priority fees value"))?;
-                    let rpc_url = upload_matches.get_one::<String>("rpc-url");
-                    
-                    commands::idl::upload_metadata_by_url(url, program_id, keypair_path.map(|s| s.as_str()), priority_fees_per_cu, rpc_url.map(|s| s.as_str()))
-                }
-                Some(("download", download_matches)) => {
-                    let program_id = download_matches.get_one::<String>("program-id").unwrap();
-                    let output_path = download_matches
-                        .get_one::<String>("output")
-                        .map(|s| s.as_str())
-                        .unwrap_or("metadata.json");
-                    let rpc_url = download_matches.get_one::<String>("rpc-url");
-                    commands::idl::download_metadata_to_file(program_id, output_path, rpc_url.map(|s| s.as_str()))
+                Some(("show", show_matches)) => {
+                    let address = show_matches.get_one::<String>("address").unwrap();
+                    commands::multisig::show_multisig(address, rpc_url(show_matches))
                 }
                 _ => {
-                    println!("Unknown metadata command. Use --help to see available commands");
+                    println!("Unknown multisig command. Use --help to see available commands");
                     Ok(())
                 }
             }
@@ -102,3 +47,147 @@ fn main() -> Result<()> {
         }
     }
 }
+
+// The idl and metadata subcommands, which work on the accounts with the seed
+fn run_data_command(seed: &str, matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("upload", upload_matches)) => {
+            let file_path = upload_matches.get_one::<String>(&format!("{}-path", seed)).unwrap();
+            let data_format = program_metadata_client::DataFormat::parse(
+                upload_matches.get_one::<String>("encoding").unwrap(),
+                upload_matches.get_one::<String>("compression").unwrap(),
+                upload_matches.get_one::<String>("format").unwrap(),
+            )?;
+            commands::idl::upload_by_file_path(seed, file_path, program_id(upload_matches), keypair_path(upload_matches), priority_fees(upload_matches), rpc_url(upload_matches), &upload_flags(upload_matches), &data_format)
+        }
+        Some(("upload-url", upload_matches)) => {
+            let url = upload_matches.get_one::<String>("url").unwrap();
+            let pin = upload_matches.get_flag("pin");
+            commands::idl::upload_by_url(seed, url, program_id(upload_matches), keypair_path(upload_matches), priority_fees(upload_matches), rpc_url(upload_matches), &upload_flags(upload_matches), pin)
+        }
+        Some(("upload-account", upload_matches)) => {
+            let pointer = upload_matches.get_one::<String>("account").unwrap();
+            let pin = upload_matches.get_flag("pin");
+            commands::idl::upload_by_account(seed, pointer, program_id(upload_matches), keypair_path(upload_matches), priority_fees(upload_matches), rpc_url(upload_matches), &upload_flags(upload_matches), pin)
+        }
+        Some(("download", download_matches)) => {
+            let default_output = format!("{}.json", seed);
+            let output_path = str_arg(download_matches, "output").unwrap_or(&default_output);
+            let version = download_matches
+                .get_one::<String>("version")
+                .map(|v| v.parse::<u64>())
+                .transpose()
+                .map_err(|_| anyhow!("Invalid version"))?;
+            let signer = str_arg(download_matches, "signer");
+            commands::idl::download_data_to_file(seed, program_id(download_matches), output_path, rpc_url(download_matches), version, signer)
+        }
+        Some(("versions", versions_matches)) => {
+            commands::idl::list_versions(seed, program_id(versions_matches), rpc_url(versions_matches))
+        }
+        Some(("status", status_matches)) => {
+            commands::idl::status(seed, program_id(status_matches), rpc_url(status_matches))
+        }
+        Some(("attest", attest_matches)) => {
+            commands::idl::attest(seed, program_id(attest_matches), keypair_path(attest_matches), priority_fees(attest_matches), rpc_url(attest_matches), &multisig_flags(attest_matches))
+        }
+        Some(("verify", verify_matches)) => {
+            let so_path = str_arg(verify_matches, "so");
+            commands::idl::verify(seed, program_id(verify_matches), so_path, rpc_url(verify_matches))
+        }
+        Some(("rollback", rollback_matches)) => {
+            let version = rollback_matches
+                .get_one::<String>("version")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|_| anyhow!("Invalid version"))?;
+            commands::idl::rollback(seed, program_id(rollback_matches), version, keypair_path(rollback_matches), priority_fees(rollback_matches), rpc_url(rollback_matches), &multisig_flags(rollback_matches))
+        }
+        Some(("migrate", migrate_matches)) => {
+            commands::idl::migrate(seed, program_id(migrate_matches), keypair_path(migrate_matches), priority_fees(migrate_matches), rpc_url(migrate_matches))
+        }
+        Some(("freeze", freeze_matches)) => {
+            let skip_confirmation = freeze_matches.get_flag("yes");
+            commands::idl::freeze(seed, program_id(freeze_matches), keypair_path(freeze_matches), priority_fees(freeze_matches), rpc_url(freeze_matches), skip_confirmation, &multisig_flags(freeze_matches))
+        }
+        Some(("set-authority", authority_matches)) => {
+            let new_authority = authority_matches.get_one::<String>("new-authority").unwrap();
+            commands::idl::set_authority(seed, program_id(authority_matches), new_authority, keypair_path(authority_matches), priority_fees(authority_matches), rpc_url(authority_matches), &multisig_flags(authority_matches))
+        }
+        Some(("accept-authority", authority_matches)) => {
+            commands::idl::accept_authority(seed, program_id(authority_matches), keypair_path(authority_matches), priority_fees(authority_matches), rpc_url(authority_matches), &multisig_flags(authority_matches))
+        }
+        Some(("cancel-authority", authority_matches)) => {
+            commands::idl::cancel_authority(seed, program_id(authority_matches), keypair_path(authority_matches), priority_fees(authority_matches), rpc_url(authority_matches), &multisig_flags(authority_matches))
+        }
+        Some(("close", close_matches)) => {
+            commands::idl::close(seed, program_id(close_matches), keypair_path(close_matches), priority_fees(close_matches), rpc_url(close_matches), &multisig_flags(close_matches))
+        }
+        Some(("close-buffer", close_matches)) => {
+            let buffer = close_matches.get_one::<String>("buffer").unwrap();
+            commands::idl::close_buffer(buffer, keypair_path(close_matches), priority_fees(close_matches), rpc_url(close_matches))
+        }
+        Some(("add-delegate", delegate_matches)) => {
+            let delegate = delegate_matches.get_one::<String>("delegate").unwrap();
+            let expiry_slot = delegate_matches
+                .get_one::<String>("expiry-slot")
+                .map(|expiry_slot| expiry_slot.parse::<u64>())
+                .transpose()
+                .map_err(|_| anyhow!("Invalid expiry slot"))?;
+            commands::idl::add_delegate(seed, program_id(delegate_matches), delegate, expiry_slot, keypair_path(delegate_matches), priority_fees(delegate_matches), rpc_url(delegate_matches), &multisig_flags(delegate_matches))
+        }
+        Some(("list-delegates", delegate_matches)) => {
+            commands::idl::list_delegates(seed, program_id(delegate_matches), rpc_url(delegate_matches))
+        }
+        Some(("list-third-party", third_party_matches)) => {
+            commands::idl::list_third_party(seed, program_id(third_party_matches), rpc_url(third_party_matches))
+        }
+        Some(("revoke-delegate", delegate_matches)) => {
+            let delegate = delegate_matches.get_one::<String>("delegate").unwrap();
+            commands::idl::revoke_delegate(seed, program_id(delegate_matches), delegate, keypair_path(delegate_matches), priority_fees(delegate_matches), rpc_url(delegate_matches), &multisig_flags(delegate_matches))
+        }
+        _ => {
+            println!("Unknown {} command. Use --help to see available commands", seed);
+            Ok(())
+        }
+    }
+}
+
+fn str_arg<'a>(matches: &'a ArgMatches, id: &str) -> Option<&'a str> {
+    matches.get_one::<String>(id).map(|s| s.as_str())
+}
+
+fn program_id(matches: &ArgMatches) -> &str {
+    str_arg(matches, "program-id").unwrap()
+}
+
+fn keypair_path(matches: &ArgMatches) -> Option<&str> {
+    str_arg(matches, "keypair")
+}
+
+fn rpc_url(matches: &ArgMatches) -> Option<&str> {
+    str_arg(matches, "rpc-url")
+}
+
+// Parsed by clap and defaults to 0
+fn priority_fees(matches: &ArgMatches) -> u64 {
+    *matches.get_one::<u64>("priority-fees-per-cu").unwrap()
+}
+
+fn multisig_flags(matches: &ArgMatches) -> MultisigFlags {
+    MultisigFlags {
+        multisig: matches.get_one::<String>("multisig").cloned(),
+        signers: matches.get_many::<String>("multisig-signer").unwrap_or_default().cloned().collect(),
+    }
+}
+
+fn upload_flags(matches: &ArgMatches) -> UploadFlags {
+    UploadFlags {
+        archive: matches.get_flag("archive"),
+        add_signer_seed: matches.get_flag("add-signer-seed"),
+        export_transaction: matches.get_one::<String>("export-transaction").cloned(),
+        authority: matches.get_one::<String>("authority").cloned(),
+        nonce: matches.get_one::<String>("nonce").cloned(),
+        nonce_authority: matches.get_one::<String>("nonce-authority").cloned(),
+        multisig: multisig_flags(matches),
+    }
+}
@@ -1,10 +1,15 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import {
+  compressData,
+  decompressData,
   fetchIDL,
   fetchProgramMetadata,
   getAssociatedIdlAddress,
   getAssociatedMetadataAddress,
+  getMetadataAddressBySeed,
+  ARCHIVE_OFFSET,
+  BUFFER_OFFSET,
   METADATA_OFFSET,
   setAuthority,
   uploadIdlByJsonPath,
@@ -13,10 +18,12 @@ import {
   uploadProgramMetadataByUrl,
 } from "../js_sdk/src/ProgramMetaData";
 import { assert } from "chai";
-import { inflate } from "pako";
+import { ungzip } from "pako";
 import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
 import { MetadataProgram } from "../target/types/metadata_program";
+import { MetadataSignerExample } from "../target/types/metadata_signer_example";
 import { bs58 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
+import { createHash } from "crypto";
 
 const IDL_PATH = "./tests/testidl.json";
 const META_DATA_JSON = "./tests/metadata.json";
@@ -24,7 +31,15 @@ const META_DATA_JSON = "./tests/metadata.json";
 const TEST_IDL_PROGRAM = new PublicKey(
   "6XzaKuAwqP7Nn37vwRdUqpuzNX6K8s1ADE6tHXSZG17A"
 );
+const BPF_LOADER_UPGRADEABLE = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+// Bytes of data per write_buffer_at instruction and bytes an account can grow by per instruction
+const WRITE_CHUNK_SIZE = 900;
+const MAX_RESIZE_STEP = 10240;
 const program = anchor.workspace.MetadataProgram as Program<MetadataProgram>;
+const signerExample = anchor.workspace
+  .MetadataSignerExample as Program<MetadataSignerExample>;
 
 const rpcUrl = "http://localhost:8899";
 const connection = new anchor.web3.Connection(rpcUrl, "confirmed");
@@ -38,6 +53,53 @@ const keypair = Keypair.fromSecretKey(
   ])
 );
 
+// Programs of the other loaders, loaded from tests/accounts by Anchor.toml. The loader-v4
+// program headers store `keypair` as authority, the finalized one can not be changed anymore.
+const BPF_LOADER_PROGRAM = Keypair.fromSecretKey(
+  Uint8Array.from([
+    232, 118, 178, 71, 117, 24, 188, 246, 232, 238, 204, 169, 76, 112, 68, 124,
+    39, 105, 163, 144, 140, 28, 205, 121, 146, 168, 44, 153, 68, 31, 52, 137,
+    140, 124, 161, 11, 168, 34, 39, 234, 123, 131, 169, 238, 210, 90, 144, 198,
+    158, 240, 23, 28, 163, 10, 39, 123, 134, 47, 147, 71, 137, 222, 203, 70,
+  ])
+);
+
+const BPF_LOADER_DEPRECATED_PROGRAM = Keypair.fromSecretKey(
+  Uint8Array.from([
+    126, 123, 147, 30, 155, 197, 56, 27, 133, 231, 226, 76, 140, 22, 48, 201,
+    181, 36, 171, 214, 160, 182, 17, 75, 53, 60, 159, 55, 18, 119, 180, 82, 45,
+    225, 152, 200, 81, 79, 68, 231, 12, 199, 149, 218, 170, 162, 240, 89, 100,
+    146, 120, 45, 75, 31, 85, 195, 114, 215, 79, 85, 27, 218, 105, 202,
+  ])
+);
+
+const REVOKED_UPGRADEABLE_PROGRAM = Keypair.fromSecretKey(
+  Uint8Array.from([
+    116, 190, 137, 34, 239, 239, 27, 146, 160, 52, 56, 44, 18, 255, 38, 132, 34,
+    40, 15, 2, 219, 172, 152, 122, 196, 10, 104, 202, 147, 140, 229, 159, 69,
+    208, 160, 114, 146, 83, 15, 69, 117, 157, 0, 47, 90, 176, 27, 114, 201, 126,
+    22, 174, 202, 7, 1, 168, 70, 166, 86, 13, 42, 62, 20, 56,
+  ])
+);
+
+const LOADER_V4_PROGRAM = Keypair.fromSecretKey(
+  Uint8Array.from([
+    98, 218, 88, 127, 163, 122, 228, 102, 33, 242, 193, 141, 36, 45, 236, 36,
+    137, 176, 195, 13, 246, 30, 34, 74, 245, 227, 100, 178, 156, 118, 145, 215,
+    18, 111, 247, 98, 58, 38, 177, 246, 108, 21, 148, 177, 24, 15, 42, 187, 161,
+    65, 44, 130, 55, 107, 96, 131, 152, 205, 239, 97, 35, 242, 37, 27,
+  ])
+);
+
+const FINALIZED_LOADER_V4_PROGRAM = Keypair.fromSecretKey(
+  Uint8Array.from([
+    114, 229, 196, 81, 214, 109, 46, 124, 1, 254, 175, 63, 242, 66, 75, 88, 54,
+    85, 169, 233, 161, 7, 39, 114, 37, 185, 141, 162, 206, 107, 22, 37, 253,
+    252, 48, 190, 152, 211, 17, 69, 13, 55, 73, 15, 39, 63, 177, 249, 153, 153,
+    33, 83, 59, 46, 211, 65, 54, 45, 182, 150, 182, 130, 175, 126,
+  ])
+);
+
 describe("Test metadata program with idl and program metadata", () => {
   before(async () => {
     const airdropSig = await connection.requestAirdrop(
@@ -67,7 +129,8 @@ describe("Test metadata program with idl and program metadata", () => {
       METADATA_OFFSET + idl.dataLen
     );
 
-    const decompressedData = inflate(rawData);
+    assert.deepEqual(idl.compression, { gzip: {} });
+    const decompressedData = ungzip(rawData);
     const decompressedUrl = Buffer.from(decompressedData).toString("utf8");
     console.log("Decompressed Url:", decompressedUrl);
     const fetchedAnchorAccount = await program.account.metadataAccount3.fetch(
@@ -91,6 +154,28 @@ describe("Test metadata program with idl and program metadata", () => {
     );
   });
 
+  it("Gzip data round trips between the JS SDK and the Rust client", () => {
+    const content = Buffer.from('{"name":"cross-sdk"}', "utf8");
+
+    // Written by compress(.., Compression::Gzip) of the Rust client
+    const rustData = Buffer.from(
+      "H4sIAAAAAAAA/6tWykvMTVWyUkouyi8u1i1OyVaqBQD+H+s1FAAAAA==",
+      "base64"
+    );
+    assert.deepEqual(
+      Buffer.from(decompressData(rustData, { gzip: {} })),
+      content
+    );
+
+    // The Rust client decodes these exact bytes in gzip_data_of_the_js_sdk_is_decoded
+    const jsData = Buffer.from(compressData(content, { gzip: {} }));
+    assert.equal(
+      jsData.toString("base64"),
+      "H4sIAAAAAAAAA6tWykvMTVWyUkouyi8u1i1OyVaqBQD+H+s1FAAAAA=="
+    );
+    assert.deepEqual(Buffer.from(decompressData(jsData, { gzip: {} })), content);
+  });
+
   it("Write IDL json !", async () => {
     await uploadIdlByJsonPath(
       IDL_PATH,
@@ -142,6 +227,27 @@ describe("Test metadata program with idl and program metadata", () => {
     console.log("Parsed IDL JSON:", idlJson);
   });
 
+  it("Uploading an IDL increments the version", async () => {
+    const idlAccount = getAssociatedIdlAddress(TEST_IDL_PROGRAM);
+    const before = await program.account.metadataAccount3.fetch(idlAccount);
+
+    await uploadIdlByJsonPath(
+      IDL_PATH,
+      TEST_IDL_PROGRAM,
+      keypair,
+      rpcUrl,
+      0,
+      false
+    );
+
+    const after = await program.account.metadataAccount3.fetch(idlAccount);
+    assert.equal(
+      after.version.toNumber(),
+      before.version.toNumber() + 1,
+      "Every set_buffer should increment the version"
+    );
+  });
+
   it("Write program metadata json", async () => {
     const LogoUrl =
       "https://upload.wikimedia.org/wikipedia/en/b/b9/Solana_logo.png";
@@ -329,7 +435,1273 @@ describe("Test metadata program with idl and program metadata", () => {
     assert.equal(metadata.logo, LogoUrl, "The saved metadata should match the input");
   });
 
+  it("Resending a chunk with writeBufferAt does not duplicate data", async () => {
+    const buffer = Keypair.generate();
+    const chunk = Buffer.from("hello world", "utf8");
+    const chunkHash = createHash("sha256").update(chunk).digest();
+
+    const createAccountIx = anchor.web3.SystemProgram.createAccount({
+      fromPubkey: keypair.publicKey,
+      newAccountPubkey: buffer.publicKey,
+      lamports: await connection.getMinimumBalanceForRentExemption(
+        BUFFER_OFFSET + chunk.length
+      ),
+      space: BUFFER_OFFSET + chunk.length,
+      programId: program.programId,
+    });
+    const createBufferIx = await program.methods
+      .createBuffer(
+        "idl",
+        { utf8: {} },
+        { none: {} },
+        { text: {} },
+        { direct: {} },
+        Array.from(chunkHash),
+        Array.from(chunkHash)
+      )
+      .accounts({
+        buffer: buffer.publicKey,
+        authority: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+      })
+      .instruction();
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(createAccountIx, createBufferIx),
+      [keypair, buffer],
+      { commitment: "confirmed" }
+    );
+
+    // Send the same chunk twice, like a retry after a lost confirmation would
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .writeBufferAt(0, null, chunk)
+        .accounts({ buffer: buffer.publicKey, signer: keypair.publicKey })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const bufferAccount = await program.account.metadataBuffer.fetch(
+      buffer.publicKey,
+      "confirmed"
+    );
+    assert.equal(
+      bufferAccount.dataLen,
+      chunk.length,
+      "Resending a chunk should not grow the buffer"
+    );
+  });
+
+  it("A metadata buffer can not be set on the IDL account", async () => {
+    const buffer = Keypair.generate();
+    const data = Buffer.from("{}", "utf8");
+    const dataHash = createHash("sha256").update(data).digest();
+
+    const createAccountIx = anchor.web3.SystemProgram.createAccount({
+      fromPubkey: keypair.publicKey,
+      newAccountPubkey: buffer.publicKey,
+      lamports: await connection.getMinimumBalanceForRentExemption(
+        BUFFER_OFFSET + data.length
+      ),
+      space: BUFFER_OFFSET + data.length,
+      programId: program.programId,
+    });
+    const createBufferIx = await program.methods
+      .createBuffer(
+        "metadata",
+        { utf8: {} },
+        { none: {} },
+        { json: {} },
+        { direct: {} },
+        Array.from(dataHash),
+        Array.from(dataHash)
+      )
+      .accounts({
+        buffer: buffer.publicKey,
+        authority: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+      })
+      .instruction();
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(createAccountIx, createBufferIx),
+      [keypair, buffer],
+      { commitment: "confirmed" }
+    );
+    await program.methods
+      .writeBufferAt(0, null, data)
+      .accounts({ buffer: buffer.publicKey, signer: keypair.publicKey })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+
+    try {
+      await program.methods
+        .setBuffer()
+        .accounts({
+          buffer: buffer.publicKey,
+          pda: getAssociatedIdlAddress(TEST_IDL_PROGRAM),
+          authority: keypair.publicKey,
+          programId: TEST_IDL_PROGRAM,
+          programData: null,
+          archive: null,
+          payer: null,
+          systemProgram: null,
+          delegateRecord: null,
+          multisig: null,
+        })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" });
+      assert.fail("Setting a metadata buffer on the IDL account should fail");
+    } catch (error) {
+      assert.include(error.toString(), "DataTypeMismatch");
+    }
+  });
+
+  it("A frozen metadata account can not be changed or closed", async () => {
+    const pda = await initializeMetadata("frozen");
+    await setData(pda, "frozen", Buffer.from('{"frozen":true}', "utf8"));
+
+    await program.methods
+      .freeze()
+      .accountsPartial({ pda, authority: keypair.publicKey, multisig: null })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+
+    const frozen = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(frozen.frozen);
+    assert.ok(frozen.authority.equals(PublicKey.default));
+
+    // Not even the program upgrade authority can overwrite it
+    await expectError(
+      setData(pda, "frozen", Buffer.from('{"frozen":"no"}', "utf8")),
+      "AccountFrozen"
+    );
+    await expectError(
+      program.methods
+        .closeMetadataAccount3()
+        .accountsPartial({
+          metadataAccount: pda,
+          authority: keypair.publicKey,
+          multisig: null,
+        })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" }),
+      "AccountFrozen"
+    );
+
+    const after = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.equal(after.version.toNumber(), frozen.version.toNumber());
+  });
+
+  it("An authority transfer takes effect once it is accepted", async () => {
+    const pda = await initializeMetadata("transfer");
+    const newAuthority = Keypair.generate();
+    const proposeAuthority = (authority: Keypair, pending: PublicKey) =>
+      program.methods
+        .setAuthority(pending)
+        .accountsPartial({ pda, authority: authority.publicKey, multisig: null })
+        .signers([authority])
+        .rpc({ commitment: "confirmed" });
+    const acceptAuthority = (signer: Keypair) =>
+      program.methods
+        .acceptAuthority()
+        .accountsPartial({ pda, newAuthority: signer.publicKey, multisig: null })
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+
+    // A proposal can be cancelled
+    await proposeAuthority(keypair, newAuthority.publicKey);
+    let account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.authority.equals(keypair.publicKey));
+    assert.ok(account.pendingAuthority.equals(newAuthority.publicKey));
+
+    await program.methods
+      .cancelAuthority()
+      .accountsPartial({ pda, authority: keypair.publicKey, multisig: null })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.pendingAuthority.equals(PublicKey.default));
+    await expectError(acceptAuthority(newAuthority), "NoPendingAuthority");
+
+    // Only the proposed authority can accept
+    await proposeAuthority(keypair, newAuthority.publicKey);
+    await expectError(acceptAuthority(Keypair.generate()), "InvalidPendingAuthority");
+    await acceptAuthority(newAuthority);
+    account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.authority.equals(newAuthority.publicKey));
+    assert.ok(account.pendingAuthority.equals(PublicKey.default));
+
+    // The previous authority lost its rights
+    await expectError(
+      proposeAuthority(keypair, keypair.publicKey),
+      "InvalidAuthority"
+    );
+  });
+
+  it("Every change of a metadata account emits an event", async () => {
+    const pda = getMetadataAddressBySeed(TEST_IDL_PROGRAM, "events");
+    const initializeSignature = await program.methods
+      .initialize("events", { utf8: {} }, { none: {} }, { json: {} }, { direct: {} })
+      .accountsPartial({
+        pda,
+        signer: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+        programData: getProgramDataAddress(TEST_IDL_PROGRAM),
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    const initialized = await getEvent(initializeSignature, "metadataInitialized");
+    assert.ok(initialized.metadata.equals(pda));
+    assert.ok(initialized.programId.equals(TEST_IDL_PROGRAM));
+    assert.equal(initialized.dataType, "events");
+    assert.ok(initialized.authority.equals(keypair.publicKey));
+    assert.isFalse(initialized.signerSeeded);
+
+    const data = Buffer.from('{"events":true}', "utf8");
+    const setSignature = await setData(pda, "events", data);
+    const resized = await getEvent(setSignature, "metadataResized");
+    assert.equal(resized.accountSize, METADATA_OFFSET + data.length);
+    const set = await getEvent(setSignature, "metadataSet");
+    assert.equal(set.version.toNumber(), 1);
+    assert.equal(set.dataLen, data.length);
+    assert.deepEqual(
+      Buffer.from(set.contentHash),
+      createHash("sha256").update(data).digest()
+    );
+
+    const pendingAuthority = Keypair.generate().publicKey;
+    const setAuthoritySignature = await program.methods
+      .setAuthority(pendingAuthority)
+      .accountsPartial({ pda, authority: keypair.publicKey, multisig: null })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    const authorityChanged = await getEvent(setAuthoritySignature, "authorityChanged");
+    assert.ok(authorityChanged.authority.equals(keypair.publicKey));
+    assert.ok(authorityChanged.pendingAuthority.equals(pendingAuthority));
+
+    const closeSignature = await program.methods
+      .closeMetadataAccount3()
+      .accountsPartial({
+        metadataAccount: pda,
+        authority: keypair.publicKey,
+        multisig: null,
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    const closed = await getEvent(closeSignature, "metadataClosed");
+    assert.ok(closed.metadata.equals(pda));
+    assert.equal(closed.dataType, "events");
+  });
+
+  it("A delegate can set new versions until it is revoked or expires", async () => {
+    const pda = await initializeMetadata("delegated");
+    await setData(pda, "delegated", Buffer.from('{"version":1}', "utf8"));
+
+    const delegate = Keypair.generate();
+    const expiredDelegate = Keypair.generate();
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        ...[delegate, expiredDelegate].map((key) =>
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: keypair.publicKey,
+            toPubkey: key.publicKey,
+            lamports: 50000000,
+          })
+        )
+      ),
+      [keypair],
+      { commitment: "confirmed" }
+    );
+    const addDelegate = async (key: PublicKey, expirySlot: number) => {
+      const [delegateRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate"), pda.toBuffer(), key.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .addDelegate(key, new anchor.BN(expirySlot))
+        .accountsPartial({
+          delegateRecord,
+          pda,
+          authority: keypair.publicKey,
+          multisig: null,
+        })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" });
+      return delegateRecord;
+    };
+
+    const delegateRecord = await addDelegate(delegate.publicKey, 0);
+    const record = await program.account.metadataDelegate.fetch(
+      delegateRecord,
+      "confirmed"
+    );
+    assert.ok(record.metadata.equals(pda));
+    assert.ok(record.delegate.equals(delegate.publicKey));
+    assert.ok(record.authority.equals(keypair.publicKey));
+
+    await setData(pda, "delegated", Buffer.from('{"version":2,"by":"delegate"}', "utf8"), {
+      authority: delegate,
+      delegateRecord,
+    });
+    const account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.equal(account.version.toNumber(), 2);
+    assert.ok(
+      account.authority.equals(keypair.publicKey),
+      "A delegate does not become the authority"
+    );
+
+    // The rent refund of a shrink goes to the signer, so only the authority can shrink
+    const resize = (signer: Keypair, len: number, record: PublicKey | null) =>
+      program.methods
+        .resize(len, null)
+        .accountsPartial({
+          pda,
+          signer: signer.publicKey,
+          programId: TEST_IDL_PROGRAM,
+          delegateRecord: record,
+          multisig: null,
+        })
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+    const size = METADATA_OFFSET + account.dataLen;
+    await resize(keypair, size + 100, null);
+    await expectError(
+      resize(delegate, size, delegateRecord),
+      "DelegateCannotShrink"
+    );
+    await resize(keypair, size, null);
+    const shrunk = await connection.getAccountInfo(pda, "confirmed");
+    assert.equal(shrunk.data.length, size);
+
+    // A delegate can not transfer the authority
+    await expectError(
+      program.methods
+        .setAuthority(delegate.publicKey)
+        .accountsPartial({ pda, authority: delegate.publicKey, multisig: null })
+        .signers([delegate])
+        .rpc({ commitment: "confirmed" }),
+      "InvalidAuthority"
+    );
+
+    // Slot 1 has long passed
+    const expiredRecord = await addDelegate(expiredDelegate.publicKey, 1);
+    await expectError(
+      setData(pda, "delegated", Buffer.from('{"version":3,"by":"expired delegate"}', "utf8"), {
+        authority: expiredDelegate,
+        delegateRecord: expiredRecord,
+      }),
+      "DelegateExpired"
+    );
+
+    await program.methods
+      .revokeDelegate()
+      .accountsPartial({
+        delegateRecord,
+        pda,
+        authority: keypair.publicKey,
+        multisig: null,
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    assert.isNull(await connection.getAccountInfo(delegateRecord, "confirmed"));
+    await expectError(
+      setData(pda, "delegated", Buffer.from('{"version":3,"by":"revoked delegate"}', "utf8"), {
+        authority: delegate,
+        delegateRecord,
+      }),
+      "AccountNotInitialized"
+    );
+  });
+
+  it("A multisig authority needs threshold of its signers for every authority instruction", async () => {
+    const [signerA, signerB, signerC, outsider] = [0, 1, 2, 3].map(() =>
+      Keypair.generate()
+    );
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        ...[signerA, signerB, signerC, outsider].map((key) =>
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: keypair.publicKey,
+            toPubkey: key.publicKey,
+            lamports: 50000000,
+          })
+        )
+      ),
+      [keypair],
+      { commitment: "confirmed" }
+    );
+    const createMultisig = async (threshold: number, signers: PublicKey[]) => {
+      const multisig = Keypair.generate();
+      await program.methods
+        .createMultisig(threshold, signers)
+        .accounts({ multisig: multisig.publicKey, payer: keypair.publicKey })
+        .signers([keypair, multisig])
+        .rpc({ commitment: "confirmed" });
+      return multisig.publicKey;
+    };
+
+    const keys = [signerA, signerB, signerC].map((key) => key.publicKey);
+    await expectError(createMultisig(0, keys), "InvalidThreshold");
+    await expectError(createMultisig(4, keys), "InvalidThreshold");
+    await expectError(
+      createMultisig(2, [signerA.publicKey, signerB.publicKey, signerA.publicKey]),
+      "DuplicateSigner"
+    );
+    await expectError(
+      createMultisig(1, Array.from({ length: 11 }, () => Keypair.generate().publicKey)),
+      "TooManySigners"
+    );
+    const multisig = await createMultisig(2, keys);
+    const multisigAccount = await program.account.metadataMultisig.fetch(
+      multisig,
+      "confirmed"
+    );
+    assert.equal(multisigAccount.threshold, 2);
+    assert.deepEqual(
+      multisigAccount.signers.map((key) => key.toBase58()),
+      keys.map((key) => key.toBase58())
+    );
+
+    // The multisig accepts the authority with threshold of its signers
+    const pda = await initializeMetadata("multisig");
+    await program.methods
+      .setAuthority(multisig)
+      .accountsPartial({ pda, authority: keypair.publicKey, multisig: null })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    const acceptAuthority = (cosigners: Keypair[]) =>
+      program.methods
+        .acceptAuthority()
+        .accountsPartial({ pda, newAuthority: signerA.publicKey, multisig })
+        .remainingAccounts(signerMetas(cosigners))
+        .signers([signerA, ...cosigners])
+        .rpc({ commitment: "confirmed" });
+    await expectError(acceptAuthority([]), "InvalidPendingAuthority");
+    await acceptAuthority([signerB]);
+    let account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.authority.equals(multisig));
+
+    const proposeAuthority = (
+      signer: Keypair,
+      remainingAccounts: { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[],
+      cosigners: Keypair[],
+      pending: PublicKey
+    ) =>
+      program.methods
+        .setAuthority(pending)
+        .accountsPartial({ pda, authority: signer.publicKey, multisig })
+        .remainingAccounts(remainingAccounts)
+        .signers([signer, ...cosigners])
+        .rpc({ commitment: "confirmed" });
+    const newAuthority = Keypair.generate().publicKey;
+
+    // One signer misses the threshold
+    await expectError(
+      proposeAuthority(signerA, [], [], newAuthority),
+      "InvalidAuthority"
+    );
+    // A signer passed twice only counts once
+    await expectError(
+      proposeAuthority(signerA, signerMetas([signerA, signerA]), [], newAuthority),
+      "InvalidAuthority"
+    );
+    // Signatures of keys that are not signers of the multisig do not count
+    await expectError(
+      proposeAuthority(signerA, signerMetas([outsider]), [outsider], newAuthority),
+      "InvalidAuthority"
+    );
+    await expectError(
+      proposeAuthority(outsider, signerMetas([signerA]), [signerA], newAuthority),
+      "InvalidAuthority"
+    );
+    // A signer of the multisig that is passed without signing does not count
+    await expectError(
+      proposeAuthority(
+        signerA,
+        [{ pubkey: signerB.publicKey, isSigner: false, isWritable: false }],
+        [],
+        newAuthority
+      ),
+      "InvalidAuthority"
+    );
+    // Without the multisig account the signers can not act for it
+    await expectError(
+      program.methods
+        .setAuthority(newAuthority)
+        .accountsPartial({ pda, authority: signerA.publicKey, multisig: null })
+        .remainingAccounts(signerMetas([signerB]))
+        .signers([signerA, signerB])
+        .rpc({ commitment: "confirmed" }),
+      "InvalidAuthority"
+    );
+
+    // Threshold of the signers sets new versions
+    const data = Buffer.from('{"multisig":true}', "utf8");
+    await expectError(
+      setData(pda, "multisig", data, { authority: signerA }),
+      "InvalidAuthority"
+    );
+    await setData(pda, "multisig", data, {
+      authority: signerA,
+      multisig: { address: multisig, cosigners: [signerC] },
+    });
+    account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.equal(account.version.toNumber(), 1);
+    assert.ok(account.authority.equals(multisig));
+    const accountInfo = await connection.getAccountInfo(pda, "confirmed");
+    assert.deepEqual(
+      accountInfo.data.subarray(METADATA_OFFSET, METADATA_OFFSET + account.dataLen),
+      data
+    );
+
+    // and transfers the authority
+    await proposeAuthority(signerB, signerMetas([signerC]), [signerC], newAuthority);
+    account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.authority.equals(multisig));
+    assert.ok(account.pendingAuthority.equals(newAuthority));
+  });
+
+  it("Legacy metadata accounts are migrated in place", async () => {
+    const legacyAccounts = [
+      { dataType: "legacy", layout: "MetadataAccount", compression: "gzip" },
+      { dataType: "legacy2", layout: "MetadataAccount2", compression: "gzip" },
+      // MetadataAccount3 with the old 96 byte header keeps its own data header
+      { dataType: "legacy3", layout: "MetadataAccount3", compression: "none" },
+    ];
+    const migrate = (pda: PublicKey, authority: Keypair, contentHash: number[]) =>
+      program.methods
+        .migrate(contentHash)
+        .accountsPartial({
+          pda,
+          authority: authority.publicKey,
+          programId: TEST_IDL_PROGRAM,
+        })
+        .signers([authority])
+        .rpc({ commitment: "confirmed" });
+
+    for (const { dataType, layout, compression } of legacyAccounts) {
+      // Loaded from tests/accounts by Anchor.toml
+      const pda = getMetadataAddressBySeed(TEST_IDL_PROGRAM, dataType);
+      const legacy = await connection.getAccountInfo(pda, "confirmed");
+      const legacyData = legacy.data.subarray(legacy.data.length - legacyDataLength(legacy.data, layout));
+      const content = compression === "gzip" ? Buffer.from(ungzip(legacyData)) : legacyData;
+      assert.equal(JSON.parse(content.toString("utf8")).legacy, layout);
+      const contentHash = Array.from(createHash("sha256").update(content).digest());
+
+      await expectError(migrate(pda, Keypair.generate(), contentHash), "InvalidAuthority");
+      await migrate(pda, keypair, contentHash);
+
+      const account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+      assert.ok(account.authority.equals(keypair.publicKey), layout);
+      assert.ok(account.programId.equals(TEST_IDL_PROGRAM), layout);
+      assert.equal(
+        Buffer.from(account.dataType).toString("utf8").split("\0")[0],
+        dataType
+      );
+      assert.deepEqual(account.compression, { [compression]: {} });
+      assert.deepEqual(account.format, { json: {} });
+      assert.deepEqual(account.dataSource, { direct: {} });
+      assert.equal(account.dataLen, legacyData.length);
+      assert.equal(account.version.toNumber(), 1);
+      assert.deepEqual(account.contentHash, contentHash);
+      assert.deepEqual(
+        Buffer.from(account.dataHash),
+        createHash("sha256").update(legacyData).digest()
+      );
+      const migrated = await connection.getAccountInfo(pda, "confirmed");
+      assert.equal(migrated.data.length, METADATA_OFFSET + legacyData.length);
+      assert.deepEqual(migrated.data.subarray(METADATA_OFFSET), legacyData);
+
+      // A migrated account is a current MetadataAccount3
+      await expectError(migrate(pda, keypair, contentHash), "NotALegacyAccount");
+    }
+  });
+
+  it("set_buffer records the deployment slot of the program", async () => {
+    const pda = await initializeMetadata("slot");
+    await setData(pda, "slot", Buffer.from('{"slot":true}', "utf8"));
+
+    // UpgradeableLoaderState::ProgramData: a u32 variant tag followed by the deployment slot
+    const programData = await connection.getAccountInfo(
+      getProgramDataAddress(TEST_IDL_PROGRAM),
+      "confirmed"
+    );
+    const deploySlot = programData.data.readBigUInt64LE(4);
+
+    const account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.equal(account.programDataSlot.toString(), deploySlot.toString());
+  });
+
+  it("The executable is hashed in chunks to attest a version", async () => {
+    const CHUNK_SIZE = 65536;
+    // Size of the UpgradeableLoaderState::ProgramData header before the executable
+    const PROGRAM_DATA_HEADER_SIZE = 45;
+    const pda = await initializeMetadata("attested");
+    await setData(pda, "attested", Buffer.from('{"attested":true}', "utf8"));
+
+    const programData = getProgramDataAddress(TEST_IDL_PROGRAM);
+    const [hashState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("executable_hash"), pda.toBuffer()],
+      program.programId
+    );
+    const finish = () =>
+      program.methods
+        .finishExecutableHash()
+        .accountsPartial({
+          hashState,
+          pda,
+          payer: keypair.publicKey,
+          programId: TEST_IDL_PROGRAM,
+          programData,
+        })
+        .rpc({ commitment: "confirmed" });
+
+    await program.methods
+      .startExecutableHash()
+      .accountsPartial({
+        hashState,
+        pda,
+        signer: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+        programData,
+        delegateRecord: null,
+        multisig: null,
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+
+    const executable = (
+      await connection.getAccountInfo(programData, "confirmed")
+    ).data.subarray(PROGRAM_DATA_HEADER_SIZE);
+    let state = await program.account.metadataExecutableHash.fetch(
+      hashState,
+      "confirmed"
+    );
+    assert.equal(state.executableLen, executable.length);
+    assert.equal(state.offset, 0);
+    await expectError(finish(), "ExecutableHashIncomplete");
+
+    // One chunk per transaction, every chunk is chained onto the digest of the previous ones
+    let digest = Buffer.alloc(32);
+    while (state.offset < executable.length) {
+      const start = state.offset;
+      await program.methods
+        .hashExecutable(1)
+        .accountsPartial({ hashState, pda, programId: TEST_IDL_PROGRAM, programData })
+        .rpc({ commitment: "confirmed" });
+      state = await program.account.metadataExecutableHash.fetch(hashState, "confirmed");
+      const end = Math.min(start + CHUNK_SIZE, executable.length);
+      assert.equal(state.offset, end);
+      digest = createHash("sha256")
+        .update(digest)
+        .update(executable.subarray(start, end))
+        .digest();
+      assert.deepEqual(Buffer.from(state.digest), digest);
+    }
+    assert.isAbove(executable.length, CHUNK_SIZE, "The test program should need several chunks");
+
+    await finish();
+    const account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.deepEqual(Buffer.from(account.executableHash), digest);
+    assert.isNull(
+      await connection.getAccountInfo(hashState, "confirmed"),
+      "Finishing returns the rent of the hash state"
+    );
+  });
+
+  it("set_buffer only accepts valid account pointers", async () => {
+    const pda = await initializeMetadata("pointer");
+    const target = getAssociatedIdlAddress(TEST_IDL_PROGRAM);
+    const range = (offset: number, length: number) => {
+      const bytes = Buffer.alloc(8);
+      bytes.writeUInt32LE(offset, 0);
+      bytes.writeUInt32LE(length, 4);
+      return bytes;
+    };
+    const setPointer = (pointer: Buffer) =>
+      setData(pda, "pointer", pointer, { dataSource: { account: {} } });
+
+    await setPointer(target.toBuffer());
+    let account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.deepEqual(account.dataSource, { account: {} });
+    assert.equal(account.dataLen, 32);
+
+    await setPointer(Buffer.concat([target.toBuffer(), range(METADATA_OFFSET, 10)]));
+    account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.equal(account.dataLen, 40);
+
+    for (const pointer of [
+      // Neither a pubkey nor a pubkey with a range
+      Buffer.concat([target.toBuffer(), Buffer.from([0])]),
+      // Empty and overflowing ranges
+      Buffer.concat([target.toBuffer(), range(0, 0)]),
+      Buffer.concat([target.toBuffer(), range(0xffffffff, 2)]),
+      // An account can not point to itself
+      pda.toBuffer(),
+    ]) {
+      await expectError(setPointer(pointer), "InvalidAccountPointer");
+    }
+  });
+
+  it("A bpf_loader program is claimed by signing with the program keypair", async () => {
+    const pda = await initializeIdl(BPF_LOADER_PROGRAM.publicKey, {
+      programSigner: BPF_LOADER_PROGRAM,
+    });
+
+    const idl = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(idl.authority.equals(keypair.publicKey));
+  });
+
+  it("A bpf_loader_deprecated program can only be claimed by the deployer", async () => {
+    try {
+      await initializeIdl(BPF_LOADER_DEPRECATED_PROGRAM.publicKey);
+      assert.fail("Claiming a program without an authority needs the program keypair");
+    } catch (error) {
+      assert.include(error.toString(), "WrongAuthority");
+    }
+
+    const pda = await initializeIdl(BPF_LOADER_DEPRECATED_PROGRAM.publicKey, {
+      programSigner: BPF_LOADER_DEPRECATED_PROGRAM,
+    });
+    const idl = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(idl.authority.equals(keypair.publicKey));
+  });
+
+  it("An upgradeable program with a revoked authority can not be claimed", async () => {
+    const programData = getProgramDataAddress(
+      REVOKED_UPGRADEABLE_PROGRAM.publicKey
+    );
+    try {
+      await initializeIdl(REVOKED_UPGRADEABLE_PROGRAM.publicKey, {
+        programSigner: REVOKED_UPGRADEABLE_PROGRAM,
+        programData,
+      });
+      assert.fail("An immutable upgradeable program should not be claimable");
+    } catch (error) {
+      assert.include(error.toString(), "WrongAuthority");
+    }
+  });
+
+  it("A loader-v4 program is claimed by the authority in its header", async () => {
+    const pda = await initializeIdl(LOADER_V4_PROGRAM.publicKey);
+
+    const idl = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(idl.authority.equals(keypair.publicKey));
+  });
+
+  it("A finalized loader-v4 program can not be claimed", async () => {
+    for (const programSigner of [undefined, FINALIZED_LOADER_V4_PROGRAM]) {
+      try {
+        await initializeIdl(FINALIZED_LOADER_V4_PROGRAM.publicKey, {
+          programSigner,
+        });
+        assert.fail("A finalized loader-v4 program should not be claimable");
+      } catch (error) {
+        assert.include(error.toString(), "WrongAuthority");
+      }
+    }
+  });
+
+  it("A program publishes its own metadata through CPI", async () => {
+    const exampleId = signerExample.programId;
+    const [programSigner] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata_authority")],
+      exampleId
+    );
+    const programData = getProgramDataAddress(exampleId);
+    const pda = getMetadataAddressBySeed(exampleId, "config");
+
+    // The program signer pays the rent when the metadata account grows
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: keypair.publicKey,
+          toPubkey: programSigner,
+          lamports: 100000000,
+        })
+      ),
+      [keypair],
+      { commitment: "confirmed" }
+    );
+    await signerExample.methods
+      .initialize()
+      .accountsPartial({
+        metadata: pda,
+        payer: keypair.publicKey,
+        program: exampleId,
+        metadataProgram: program.programId,
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+
+    let account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+    assert.ok(account.authority.equals(programSigner));
+
+    // Publish a bigger then a smaller payload to grow and shrink the account
+    const payloads = [
+      JSON.stringify({ name: "example", fee: 5, admins: ["a", "b", "c"] }),
+      JSON.stringify({ fee: 1 }),
+    ];
+    for (const [index, payload] of payloads.entries()) {
+      const data = Buffer.from(payload, "utf8");
+      const buffer = Keypair.generate();
+      const createAccountIx = anchor.web3.SystemProgram.createAccount({
+        fromPubkey: keypair.publicKey,
+        newAccountPubkey: buffer.publicKey,
+        lamports: await connection.getMinimumBalanceForRentExemption(
+          BUFFER_OFFSET + data.length
+        ),
+        space: BUFFER_OFFSET + data.length,
+        programId: program.programId,
+      });
+      const publishIx = await signerExample.methods
+        .publish(data)
+        .accountsPartial({
+          buffer: buffer.publicKey,
+          metadata: pda,
+          program: exampleId,
+          programData,
+          metadataProgram: program.programId,
+        })
+        .instruction();
+      await anchor.web3.sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(createAccountIx, publishIx),
+        [keypair, buffer],
+        { commitment: "confirmed" }
+      );
+
+      account = await program.account.metadataAccount3.fetch(pda, "confirmed");
+      assert.equal(account.version.toNumber(), index + 1);
+      assert.equal(account.dataLen, data.length);
+      const info = await connection.getAccountInfo(pda, "confirmed");
+      assert.equal(info.data.length, METADATA_OFFSET + data.length);
+      assert.equal(info.data.subarray(METADATA_OFFSET).toString("utf8"), payload);
+      assert.isNull(
+        await connection.getAccountInfo(buffer.publicKey, "confirmed"),
+        "The buffer should be closed by the program"
+      );
+    }
+  });
+
+  it("resize grows by at most 10 KiB per instruction and never cuts off data", async () => {
+    const pda = await initializeMetadata("steps");
+    await setData(pda, "steps", Buffer.from('{"steps":1}', "utf8"));
+    const resize = (len: number) =>
+      program.methods
+        .resize(len, null)
+        .accountsPartial({
+          pda,
+          signer: keypair.publicKey,
+          programId: TEST_IDL_PROGRAM,
+          delegateRecord: null,
+          multisig: null,
+        })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" });
+
+    const accountInfo = await connection.getAccountInfo(pda, "confirmed");
+    await expectError(
+      resize(accountInfo.data.length + MAX_RESIZE_STEP + 1),
+      "ResizeTooLarge"
+    );
+    await expectError(resize(accountInfo.data.length - 1), "ResizeTooSmall");
+
+    // More than 64 KiB, which needs several resize steps in one transaction
+    const large = Buffer.from(
+      JSON.stringify({ steps: 2, padding: "b".repeat(70000) }),
+      "utf8"
+    );
+    await setData(pda, "steps", large);
+    const metadata = await program.account.metadataAccount3.fetch(
+      pda,
+      "confirmed"
+    );
+    assert.equal(metadata.dataLen, large.length);
+    const grown = await connection.getAccountInfo(pda, "confirmed");
+    assert.deepEqual(
+      grown.data.subarray(METADATA_OFFSET, METADATA_OFFSET + large.length),
+      large
+    );
+  });
+
+  it("resize only accepts the metadata account of the program, data type and seed signer", async () => {
+    const pda = await initializeMetadata("resized");
+    const signerSeeded = getMetadataAddressBySeed(
+      TEST_IDL_PROGRAM,
+      "resized",
+      keypair.publicKey
+    );
+    await program.methods
+      .initializeWithSignerSeed(
+        "resized",
+        { utf8: {} },
+        { none: {} },
+        { json: {} },
+        { direct: {} }
+      )
+      .accountsPartial({
+        pda: signerSeeded,
+        signer: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+        programData: getProgramDataAddress(TEST_IDL_PROGRAM),
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+
+    const resize = (
+      account: PublicKey,
+      programId: PublicKey,
+      seedSigner: PublicKey | null
+    ) =>
+      program.methods
+        .resize(METADATA_OFFSET + 100, seedSigner)
+        .accountsPartial({
+          pda: account,
+          signer: keypair.publicKey,
+          programId,
+          delegateRecord: null,
+          multisig: null,
+        })
+        .signers([keypair])
+        .rpc({ commitment: "confirmed" });
+
+    // The account of the test program is not derived from another program id
+    await expectError(
+      resize(pda, program.programId, null),
+      "InvalidMetadataAddress"
+    );
+    // A signer-seeded account needs the key it was derived with
+    await expectError(
+      resize(signerSeeded, TEST_IDL_PROGRAM, null),
+      "InvalidMetadataAddress"
+    );
+    await expectError(
+      resize(signerSeeded, TEST_IDL_PROGRAM, Keypair.generate().publicKey),
+      "InvalidMetadataAddress"
+    );
+
+    await resize(pda, TEST_IDL_PROGRAM, null);
+    await resize(signerSeeded, TEST_IDL_PROGRAM, keypair.publicKey);
+    for (const account of [pda, signerSeeded]) {
+      const accountInfo = await connection.getAccountInfo(account, "confirmed");
+      assert.equal(accountInfo.data.length, METADATA_OFFSET + 100);
+    }
+  });
+
+  it("Versions larger than 10 KiB are archived in steps and can be rolled back", async () => {
+    const pda = await initializeMetadata("archive");
+    const large = Buffer.from(
+      JSON.stringify({ version: 1, padding: "a".repeat(15000) }),
+      "utf8"
+    );
+    await setData(pda, "archive", large);
+
+    // set_buffer only writes the last step of an archive
+    const version2 = Buffer.from('{"version":2}', "utf8");
+    await expectError(
+      setData(pda, "archive", version2, { archive: { writeArchiveSteps: 0 } }),
+      "ArchiveTooLarge"
+    );
+    await setData(pda, "archive", version2, { archive: {} });
+    await setData(pda, "archive", Buffer.from('{"version":3}', "utf8"), {
+      archive: {},
+    });
+
+    const archive = await connection.getAccountInfo(
+      getArchiveAddress(pda, 1),
+      "confirmed"
+    );
+    assert.equal(archive.data.length, ARCHIVE_OFFSET + large.length);
+    assert.deepEqual(archive.data.subarray(ARCHIVE_OFFSET), large);
+
+    // Every archive of the account, like the versions command lists them
+    const archives = await program.account.metadataArchive.all([
+      { memcmp: { offset: 8, bytes: pda.toBase58() } },
+    ]);
+    const versions = archives
+      .map((entry) => entry.account.version.toNumber())
+      .sort();
+    assert.deepEqual(versions, [1, 2]);
+
+    // A rollback uploads the archived data as a new version
+    await setData(pda, "archive", archive.data.subarray(ARCHIVE_OFFSET), {
+      archive: {},
+    });
+    const metadata = await program.account.metadataAccount3.fetch(
+      pda,
+      "confirmed"
+    );
+    assert.equal(metadata.version.toNumber(), 4);
+    const accountInfo = await connection.getAccountInfo(pda, "confirmed");
+    assert.deepEqual(
+      accountInfo.data.subarray(METADATA_OFFSET, METADATA_OFFSET + metadata.dataLen),
+      large
+    );
+  });
+
   // Helper functions for the test
+  // Returns the data of the first event with the name in the logs of the transaction
+  async function getEvent(signature: string, name: string): Promise<any> {
+    const transaction = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    for (const event of parser.parseLogs(transaction.meta.logMessages)) {
+      if (event.name === name) {
+        return event.data;
+      }
+    }
+    assert.fail(`No ${name} event in ${signature}`);
+  }
+
+  function getProgramDataAddress(programId: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    )[0];
+  }
+
+  async function expectError(promise: Promise<unknown>, error: string) {
+    try {
+      await promise;
+    } catch (e) {
+      // Errors of sendAndConfirmTransaction only carry the error name in the program logs
+      const logs: string[] = e.logs ?? [];
+      assert.include([e.toString(), ...logs].join("\n"), error);
+      return;
+    }
+    assert.fail(`Expected ${error}`);
+  }
+
+  // Creates a metadata account of the test program for its own data type, so tests do not interfere
+  async function initializeMetadata(dataType: string): Promise<PublicKey> {
+    const pda = getMetadataAddressBySeed(TEST_IDL_PROGRAM, dataType);
+    await program.methods
+      .initialize(dataType, { utf8: {} }, { none: {} }, { json: {} }, { direct: {} })
+      .accountsPartial({
+        pda,
+        signer: keypair.publicKey,
+        programId: TEST_IDL_PROGRAM,
+        programData: getProgramDataAddress(TEST_IDL_PROGRAM),
+      })
+      .signers([keypair])
+      .rpc({ commitment: "confirmed" });
+    return pda;
+  }
+
+  // Writes data to a new buffer, grows the metadata account if needed, sets the buffer and closes it.
+  // Data larger than one chunk is written to the buffer in transactions of its own first. Returns the
+  // signature of the transaction that sets the buffer.
+  async function setData(
+    pda: PublicKey,
+    dataType: string,
+    data: Buffer,
+    options: {
+      authority?: Keypair;
+      delegateRecord?: PublicKey;
+      // The multisig authority of the metadata account and the signers that sign besides `authority`
+      multisig?: { address: PublicKey; cosigners: Keypair[] };
+      dataSource?: { url: {} } | { account: {} } | { direct: {} };
+      // Archives the current version. Without writeArchiveSteps as many as the archive needs
+      archive?: { writeArchiveSteps?: number };
+    } = {}
+  ): Promise<string> {
+    const authority = options.authority ?? keypair;
+    const cosigners = options.multisig?.cosigners ?? [];
+    const buffer = Keypair.generate();
+    const dataHash = Array.from(createHash("sha256").update(data).digest());
+
+    const writeInstructions = [];
+    for (let offset = 0; offset < data.length; offset += WRITE_CHUNK_SIZE) {
+      writeInstructions.push(
+        await program.methods
+          .writeBufferAt(
+            offset,
+            null,
+            data.subarray(offset, offset + WRITE_CHUNK_SIZE)
+          )
+          .accounts({ buffer: buffer.publicKey, signer: authority.publicKey })
+          .instruction()
+      );
+    }
+
+    let transaction = new Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: buffer.publicKey,
+        lamports: await connection.getMinimumBalanceForRentExemption(
+          BUFFER_OFFSET + data.length
+        ),
+        space: BUFFER_OFFSET + data.length,
+        programId: program.programId,
+      }),
+      await program.methods
+        .createBuffer(
+          dataType,
+          { utf8: {} },
+          { none: {} },
+          { json: {} },
+          options.dataSource ?? { direct: {} },
+          dataHash,
+          dataHash
+        )
+        .accounts({
+          buffer: buffer.publicKey,
+          authority: authority.publicKey,
+          programId: TEST_IDL_PROGRAM,
+        })
+        .instruction()
+    );
+    if (writeInstructions.length > 1) {
+      await anchor.web3.sendAndConfirmTransaction(
+        connection,
+        transaction,
+        [authority, buffer],
+        { commitment: "confirmed" }
+      );
+      for (const writeInstruction of writeInstructions) {
+        await anchor.web3.sendAndConfirmTransaction(
+          connection,
+          new Transaction().add(writeInstruction),
+          [authority],
+          { commitment: "confirmed" }
+        );
+      }
+      transaction = new Transaction();
+    } else {
+      transaction.add(...writeInstructions);
+    }
+
+    const metadata = await program.account.metadataAccount3.fetch(
+      pda,
+      "confirmed"
+    );
+    let archive: PublicKey | null = null;
+    if (options.archive) {
+      archive = getArchiveAddress(pda, metadata.version.toNumber());
+      const steps =
+        options.archive.writeArchiveSteps ??
+        Math.ceil((ARCHIVE_OFFSET + metadata.dataLen) / MAX_RESIZE_STEP) - 1;
+      for (let step = 0; step < steps; step++) {
+        transaction.add(
+          await program.methods
+            .writeArchive()
+            .accounts({ pda, archive, payer: authority.publicKey })
+            .instruction()
+        );
+      }
+    }
+
+    const accountInfo = await connection.getAccountInfo(pda, "confirmed");
+    const targetSize = METADATA_OFFSET + data.length;
+    let size = accountInfo.data.length;
+    while (size < targetSize) {
+      size = Math.min(size + MAX_RESIZE_STEP, targetSize);
+      transaction.add(
+        await program.methods
+          .resize(size, null)
+          .accountsPartial({
+            pda,
+            signer: authority.publicKey,
+            programId: TEST_IDL_PROGRAM,
+            delegateRecord: options.delegateRecord ?? null,
+            multisig: options.multisig?.address ?? null,
+          })
+          .remainingAccounts(signerMetas(cosigners))
+          .instruction()
+      );
+    }
+    transaction.add(
+      await program.methods
+        .setBuffer()
+        .accountsPartial({
+          buffer: buffer.publicKey,
+          pda,
+          authority: authority.publicKey,
+          programId: TEST_IDL_PROGRAM,
+          programData: getProgramDataAddress(TEST_IDL_PROGRAM),
+          archive,
+          payer: archive ? authority.publicKey : null,
+          systemProgram: archive ? anchor.web3.SystemProgram.programId : null,
+          delegateRecord: options.delegateRecord ?? null,
+          multisig: options.multisig?.address ?? null,
+        })
+        .remainingAccounts(signerMetas(cosigners))
+        .instruction(),
+      await program.methods
+        .closeBuffer()
+        .accounts({ buffer: buffer.publicKey, authority: authority.publicKey })
+        .instruction()
+    );
+    const signers = writeInstructions.length > 1 ? [authority] : [authority, buffer];
+    signers.push(...cosigners);
+    return anchor.web3.sendAndConfirmTransaction(
+      connection,
+      transaction,
+      signers,
+      { commitment: "confirmed" }
+    );
+  }
+
+  // Reads the data_len of a legacy metadata account, stored right in front of its data
+  function legacyDataLength(data: Buffer, layout: string): number {
+    const dataLenOffset = { MetadataAccount: 56, MetadataAccount2: 88, MetadataAccount3: 92 }[layout];
+    return data.readUInt32LE(dataLenOffset);
+  }
+
+  // Remaining accounts of the co-signers of a multisig authority
+  function signerMetas(signers: Keypair[]) {
+    return signers.map((signer) => ({
+      pubkey: signer.publicKey,
+      isSigner: true,
+      isWritable: false,
+    }));
+  }
+
+  function getArchiveAddress(pda: PublicKey, version: number): PublicKey {
+    const versionBytes = Buffer.alloc(8);
+    versionBytes.writeBigUInt64LE(BigInt(version));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("archive"), pda.toBuffer(), versionBytes],
+      program.programId
+    )[0];
+  }
+
+  async function initializeIdl(
+    programId: PublicKey,
+    options: { programSigner?: Keypair; programData?: PublicKey } = {}
+  ): Promise<PublicKey> {
+    const pda = getAssociatedIdlAddress(programId);
+    const initializeIx = await program.methods
+      .initialize("idl", { utf8: {} }, { gzip: {} }, { json: {} }, { direct: {} })
+      .accountsPartial({
+        pda,
+        signer: keypair.publicKey,
+        programId,
+        programData: options.programData ?? null,
+      })
+      .instruction();
+    const signers = [keypair];
+    if (options.programSigner) {
+      // The program account is not a signer in the IDL, it only signs as attestation of the deployer
+      initializeIx.keys.find((key) => key.pubkey.equals(programId)).isSigner =
+        true;
+      signers.push(options.programSigner);
+    }
+    await anchor.web3.sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(initializeIx),
+      signers,
+      { commitment: "confirmed" }
+    );
+    return pda;
+  }
   function decodeAndVerifyTransaction(base58Tx: string): Transaction {
     const messageBytes = bs58.decode(base58Tx);
     const message = anchor.web3.Message.from(Buffer.from(messageBytes));